@@ -17,6 +17,7 @@
 //! Polkadot CLI library.
 
 use clap::Parser;
+use std::path::PathBuf;
 
 #[allow(missing_docs)]
 #[derive(Debug, Parser)]
@@ -73,6 +74,13 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Inspect or rewrite the GRANDPA authority set kept in the aux store.
+	GrandpaRecovery(GrandpaRecoveryCmd),
+
+	/// Check or repair the parachains DB.
+	#[command(subcommand)]
+	ParachainsDb(ParachainsDbCmd),
 }
 
 #[allow(missing_docs)]
@@ -82,6 +90,153 @@ pub struct ValidationWorkerCommand {
 	pub socket_path: String,
 }
 
+/// Inspect or rewrite the GRANDPA authority set kept in the aux store.
+///
+/// Without any rewrite option the current authority set is printed. The node must not be
+/// running while the database is modified.
+#[allow(missing_docs)]
+#[derive(Debug, Parser)]
+pub struct GrandpaRecoveryCmd {
+	/// Replace the authority set id.
+	#[arg(long)]
+	pub set_id: Option<u64>,
+
+	/// Replace the authorities with the given comma separated SS58 GRANDPA keys, all with
+	/// weight 1.
+	#[arg(long, value_delimiter = ',')]
+	pub authorities: Option<Vec<String>>,
+
+	/// Drop all pending standard and forced authority set changes.
+	#[arg(long)]
+	pub clear_pending_changes: bool,
+
+	/// Read the rewrite from a JSON file. Options given on the command line take precedence.
+	#[arg(long, value_name = "PATH")]
+	pub from_json: Option<PathBuf>,
+
+	/// Keep the voter set state instead of letting the voter recreate it on startup.
+	#[arg(long)]
+	pub keep_voter_state: bool,
+
+	/// Print the rewritten authority set without writing it.
+	#[arg(long)]
+	pub dry_run: bool,
+
+	/// Save the current GRANDPA aux values to a new file before writing.
+	#[arg(long, value_name = "PATH")]
+	pub backup: Option<PathBuf>,
+
+	/// Restore GRANDPA aux values from a file written by `--backup`. With `--dry-run` the
+	/// authority set of the file is printed without restoring anything.
+	#[arg(
+		long,
+		value_name = "PATH",
+		conflicts_with_all = [
+			"set_id",
+			"authorities",
+			"clear_pending_changes",
+			"from_json",
+			"backup",
+			"keep_voter_state",
+		],
+	)]
+	pub restore: Option<PathBuf>,
+
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[clap(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for GrandpaRecoveryCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// Check or repair the parachains DB. The node must not be running.
+#[derive(Debug, clap::Subcommand)]
+pub enum ParachainsDbCmd {
+	/// Cross-check the subsystem data against the imported blocks and report gaps.
+	Check(ParachainsDbParams),
+
+	/// Rebuild missing chain selection entries from the imported block headers.
+	Repair(ParachainsDbParams),
+
+	/// Delete all data of one subsystem, which starts over from the unfinalized blocks.
+	ResetColumn(ParachainsDbResetColumnCmd),
+}
+
+impl ParachainsDbCmd {
+	fn params(&self) -> &ParachainsDbParams {
+		match self {
+			ParachainsDbCmd::Check(params) | ParachainsDbCmd::Repair(params) => params,
+			ParachainsDbCmd::ResetColumn(cmd) => &cmd.params,
+		}
+	}
+}
+
+impl sc_cli::CliConfiguration for ParachainsDbCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.params().shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.params().pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.params().database_params)
+	}
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, Parser)]
+pub struct ParachainsDbParams {
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[clap(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, Parser)]
+pub struct ParachainsDbResetColumnCmd {
+	/// The subsystem whose data is deleted.
+	#[arg(value_enum)]
+	pub column: ParachainsDbColumn,
+
+	#[clap(flatten)]
+	pub params: ParachainsDbParams,
+}
+
+/// A subsystem column of the parachains DB.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ParachainsDbColumn {
+	/// Chain selection data.
+	ChainSelection,
+	/// Approval voting data.
+	ApprovalVoting,
+	/// Dispute coordinator data. All dispute votes are lost.
+	DisputeCoordinator,
+}
+
 #[allow(missing_docs)]
 #[derive(Debug, Parser)]
 #[group(skip)]
@@ -143,6 +298,70 @@ pub struct RunCmd {
 	/// **Dangerous!** Do not touch unless explicitly adviced to.
 	#[arg(long)]
 	pub overseer_channel_capacity_override: Option<usize>,
+
+	/// Fall back to the longest chain while the chain selection, approval voting or dispute
+	/// coordinator subsystems keep failing, and switch back once they answer again.
+	///
+	/// **Dangerous!** While falling back, blocks which are not approved or are disputed can be
+	/// finalized.
+	///
+	/// Always enabled on THX Network mainnet until the parachains DB of its validators has been
+	/// checked.
+	#[arg(long)]
+	pub chain_selection_fallback: bool,
+
+	#[clap(flatten)]
+	pub validator_health: ValidatorHealthParams,
+
+	#[clap(flatten)]
+	pub finality_watchdog: FinalityWatchdogParams,
+}
+
+/// Thresholds and actions of the finality watchdog, which warns while finality lags behind.
+#[derive(Debug, Clone, Parser)]
+pub struct FinalityWatchdogParams {
+	/// Number of unfinalized blocks from which finality is considered lagging.
+	#[arg(long, value_name = "BLOCKS", default_value_t = 50)]
+	pub finality_lagging_blocks: u32,
+
+	/// Number of unfinalized blocks from which finality is considered stalled.
+	#[arg(long, value_name = "BLOCKS", default_value_t = 200)]
+	pub finality_stalled_blocks: u32,
+
+	/// Seconds without GRANDPA round progress after which lagging finality is considered
+	/// stalled.
+	#[arg(long, value_name = "SECONDS", default_value_t = 120)]
+	pub finality_stalled_round_secs: u64,
+
+	/// Directory to write a diagnostic bundle to whenever finality stalls.
+	///
+	/// The bundle holds the GRANDPA aux values, which can be restored with
+	/// `grandpa-recovery --restore`, along with the leaves and the peer set.
+	#[arg(long, value_name = "PATH")]
+	pub finality_diagnostics_dir: Option<PathBuf>,
+}
+
+/// Thresholds of the validator health, served at `/health/validator` on the Prometheus port and by
+/// the unsafe `system_validatorHealth` RPC, which fail until all of them are met.
+#[derive(Debug, Clone, Parser)]
+pub struct ValidatorHealthParams {
+	/// Minimum number of connected peers.
+	#[arg(long, value_name = "COUNT", default_value_t = 3)]
+	pub health_min_peers: usize,
+
+	/// Minimum share of the other validators of the current session, in percent, whose
+	/// addresses authority discovery has resolved.
+	#[arg(
+		long,
+		value_name = "PERCENT",
+		default_value_t = 66,
+		value_parser = clap::value_parser!(u8).range(0..=100)
+	)]
+	pub health_min_authority_discovery: u8,
+
+	/// Maximum number of GRANDPA rounds since the last vote of the local authority.
+	#[arg(long, value_name = "ROUNDS", default_value_t = 5)]
+	pub health_max_rounds_without_vote: u64,
 }
 
 #[allow(missing_docs)]
@@ -39,6 +39,118 @@ impl From<String> for Error {
 
 type Result<T> = std::result::Result<T, Error>;
 
+fn grandpa_recovery(
+	cmd: &crate::cli::GrandpaRecoveryCmd,
+	backend: &service::FullBackend,
+) -> Result<()> {
+	use service::grandpa_recovery::{self, AuthoritySetRewrite, AuxBackup};
+
+	let blockchain_err = |err| Error::PolkadotService(service::Error::Blockchain(err));
+
+	if let Some(path) = &cmd.restore {
+		let backup = AuxBackup::load(path).map_err(blockchain_err)?;
+		match backup.decode_authority_set().map_err(blockchain_err)? {
+			Some(set) => println!("Restored authority set:\n{}", set),
+			None => println!("No authority set in {}", path.display()),
+		}
+		if cmd.dry_run {
+			return Ok(())
+		}
+		backup.restore(backend).map_err(blockchain_err)?;
+		info!("Restored GRANDPA aux values from {}", path.display());
+		return Ok(())
+	}
+
+	let current = grandpa_recovery::read_authority_set(backend)
+		.map_err(blockchain_err)?
+		.ok_or_else(|| Error::Other("No GRANDPA authority set in the aux store".into()))?;
+	println!("Current authority set:\n{}", current);
+
+	let mut rewrite = match &cmd.from_json {
+		Some(path) => AuthoritySetRewrite::from_json_file(path).map_err(blockchain_err)?,
+		None => AuthoritySetRewrite::default(),
+	};
+	if cmd.set_id.is_some() {
+		rewrite.set_id = cmd.set_id;
+	}
+	if let Some(authorities) = &cmd.authorities {
+		rewrite.authorities = Some(
+			authorities
+				.iter()
+				.map(|key| grandpa_recovery::parse_authority(key.trim()).map(|id| (id, 1)))
+				.collect::<std::result::Result<_, _>>()
+				.map_err(blockchain_err)?,
+		);
+	}
+	rewrite.clear_pending_changes |= cmd.clear_pending_changes;
+
+	if rewrite.is_empty() {
+		return Ok(())
+	}
+
+	let rewritten = rewrite.apply(current);
+	println!("Rewritten authority set:\n{}", rewritten);
+	if cmd.dry_run {
+		return Ok(())
+	}
+
+	if let Some(path) = &cmd.backup {
+		AuxBackup::read(backend)
+			.and_then(|backup| backup.save(path))
+			.map_err(blockchain_err)?;
+		info!("Saved GRANDPA aux values to {}", path.display());
+	}
+	grandpa_recovery::write_authority_set(backend, &rewritten, !cmd.keep_voter_state)
+		.map_err(blockchain_err)?;
+	info!("Wrote GRANDPA authority set with set_id {}", rewritten.set_id);
+
+	Ok(())
+}
+
+fn parachains_db(
+	cmd: &crate::cli::ParachainsDbCmd,
+	backend: &service::FullBackend,
+	db: std::sync::Arc<dyn service::Database>,
+) -> Result<()> {
+	use crate::cli::{ParachainsDbCmd, ParachainsDbColumn};
+	use service::parachains_db_integrity::{self as integrity, SubsystemColumn};
+
+	let blockchain_err = |err| Error::PolkadotService(service::Error::Blockchain(err));
+
+	match cmd {
+		ParachainsDbCmd::Check(_) => {
+			let report = integrity::check(backend, db).map_err(blockchain_err)?;
+			print!("{}", report);
+			if !report.missing_approval_voting.is_empty() {
+				log::warn!(
+					"Approval voting lacks {} unfinalized blocks, expected after a major sync",
+					report.missing_approval_voting.len(),
+				);
+			}
+			if !report.is_consistent() {
+				return Err(Error::Other("The chain selection data has gaps".into()))
+			}
+		},
+		ParachainsDbCmd::Repair(_) => {
+			let repaired =
+				integrity::repair_chain_selection(backend, db).map_err(blockchain_err)?;
+			info!("Rebuilt {} chain selection entries", repaired);
+		},
+		ParachainsDbCmd::ResetColumn(cmd) => {
+			let column = match cmd.column {
+				ParachainsDbColumn::ChainSelection => SubsystemColumn::ChainSelection,
+				ParachainsDbColumn::ApprovalVoting => SubsystemColumn::ApprovalVoting,
+				ParachainsDbColumn::DisputeCoordinator => SubsystemColumn::DisputeCoordinator,
+			};
+			let deleted = integrity::reset_column(db, column)
+				.map_err(|err| Error::PolkadotService(service::Error::Io(err)))?;
+			info!("Deleted {} entries of the {:?} column", deleted, column);
+		},
+	}
+
+	Ok(())
+}
+
 fn get_exec_name() -> Option<String> {
 	std::env::current_exe()
 		.ok()
@@ -354,6 +466,23 @@ where
 			cli.run.overseer_channel_capacity_override,
 			maybe_malus_finality_delay,
 			hwbench,
+			service::ValidatorHealthConfig {
+				min_peers: cli.run.validator_health.health_min_peers,
+				min_authority_discovery_percent: cli
+					.run
+					.validator_health
+					.health_min_authority_discovery,
+				max_rounds_without_vote: cli.run.validator_health.health_max_rounds_without_vote,
+			},
+			service::FinalityWatchdogConfig {
+				lagging_blocks: cli.run.finality_watchdog.finality_lagging_blocks,
+				stalled_blocks: cli.run.finality_watchdog.finality_stalled_blocks,
+				stalled_round_duration: std::time::Duration::from_secs(
+					cli.run.finality_watchdog.finality_stalled_round_secs,
+				),
+				diagnostics_dir: cli.run.finality_watchdog.finality_diagnostics_dir.clone(),
+			},
+			cli.run.chain_selection_fallback,
 		)
 		.map(|full| full.task_manager)?;
 
@@ -720,6 +849,29 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			Ok(runner.sync_run(|config| cmd.run::<service::Block>(&config))?)
 		},
+		Some(Subcommand::GrandpaRecovery(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			let chain_spec = &runner.config().chain_spec;
+
+			set_default_ss58_version(chain_spec);
+
+			Ok(runner.async_run(|mut config| {
+				let (_, backend, _, task_manager) = service::new_chain_ops(&mut config, None)?;
+				Ok((async move { grandpa_recovery(cmd, &*backend) }, task_manager))
+			})?)
+		},
+		Some(Subcommand::ParachainsDb(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			let chain_spec = &runner.config().chain_spec;
+
+			set_default_ss58_version(chain_spec);
+
+			Ok(runner.async_run(|mut config| {
+				let (_, backend, _, task_manager) = service::new_chain_ops(&mut config, None)?;
+				let db = service::open_database(&config.database)?;
+				Ok((async move { parachains_db(cmd, &*backend, db) }, task_manager))
+			})?)
+		},
 	}?;
 
 	#[cfg(feature = "pyroscope")]
@@ -89,7 +89,7 @@ mod persisted_entries;
 mod time;
 
 use crate::{
-	approval_db::v1::{Config as DatabaseConfig, DbBackend},
+	approval_db::v1::{Config as DatabaseConfig, DbBackend, StoredBlockRange},
 	backend::{Backend, OverlayedBackend},
 };
 
@@ -381,6 +381,72 @@ impl ApprovalVotingSubsystem {
 		let ops = overlay.into_write_ops();
 		backend.write(ops)
 	}
+
+	/// Return the blocks among `blocks` which have no entry in the database.
+	pub fn missing_blocks(
+		&self,
+		blocks: impl IntoIterator<Item = Hash>,
+	) -> Result<Vec<Hash>, SubsystemError> {
+		let config = approval_db::v1::Config {
+			col_approval_data: self.db_config.col_approval_data,
+			col_session_data: self.db_config.col_session_data,
+		};
+
+		let mut missing = Vec::new();
+		for hash in blocks {
+			if approval_db::v1::load_block_entry(&*self.db, &config, &hash)?.is_none() {
+				missing.push(hash);
+			}
+		}
+
+		Ok(missing)
+	}
+
+	/// Delete all entries of the database, found by walking the stored block range, and return
+	/// their number.
+	///
+	/// Unlike iterating over the column, this also works where the column is not ordered.
+	pub fn clear_db(&self) -> Result<usize, SubsystemError> {
+		let config = approval_db::v1::Config {
+			col_approval_data: self.db_config.col_approval_data,
+			col_session_data: self.db_config.col_session_data,
+		};
+		let mut backend = approval_db::v1::DbBackend::new(self.db.clone(), config);
+
+		let mut deleted = 0;
+		let ops = {
+			let mut overlay = OverlayedBackend::new(&backend);
+			let mut candidates = HashSet::new();
+			if let Some(StoredBlockRange(start, end)) = overlay.load_stored_blocks()? {
+				for height in start..end {
+					for hash in overlay.load_blocks_at_height(&height)? {
+						let entry = match overlay.load_block_entry(&hash)? {
+							Some(entry) => entry,
+							None => continue,
+						};
+						for (_, candidate_hash) in entry.candidates() {
+							if candidates.insert(*candidate_hash) &&
+								overlay.load_candidate_entry(candidate_hash)?.is_some()
+							{
+								overlay.delete_candidate_entry(candidate_hash);
+								deleted += 1;
+							}
+						}
+						overlay.delete_block_entry(&hash);
+						deleted += 1;
+					}
+					overlay.delete_blocks_at_height(height);
+					deleted += 1;
+				}
+				overlay.delete_stored_block_range();
+				deleted += 1;
+			}
+			overlay.into_write_ops().collect::<Vec<_>>()
+		};
+
+		backend.write(ops)?;
+		Ok(deleted)
+	}
 }
 
 // Checks and logs approval vote db state. It is perfectly normal to start with an
@@ -346,6 +346,65 @@ impl ChainSelectionSubsystem {
 
 		backend.write(ops)
 	}
+
+	/// Return the blocks among `blocks` which have no entry in the database.
+	pub fn missing_blocks(
+		&self,
+		blocks: impl IntoIterator<Item = Hash>,
+	) -> Result<Vec<Hash>, Error> {
+		let config = db_backend::v1::Config { col_data: self.config.col_data };
+		let backend = db_backend::v1::DbBackend::new(self.db.clone(), config);
+
+		let mut missing = Vec::new();
+		for hash in blocks {
+			if backend.load_block_entry(&hash)?.is_none() {
+				missing.push(hash);
+			}
+		}
+
+		Ok(missing)
+	}
+
+	/// Import the given unfinalized blocks and their weights, in ascending order, the same way
+	/// new active leaves are imported. Blocks which already have an entry are skipped.
+	///
+	/// Used to rebuild the database from the block headers after entries went missing.
+	/// Returns the number of imported blocks.
+	pub fn import_missing_blocks(
+		&self,
+		blocks: impl IntoIterator<Item = (Header, BlockWeight)>,
+	) -> Result<usize, Error> {
+		let config = db_backend::v1::Config { col_data: self.config.col_data };
+		let mut backend = db_backend::v1::DbBackend::new(self.db.clone(), config);
+		let stagnant_at = SystemClock.timestamp_now() + STAGNANT_TIMEOUT;
+
+		let mut imported = 0;
+		let ops = {
+			let mut overlay = OverlayedBackend::new(&backend);
+			for (header, weight) in blocks {
+				let hash = header.hash();
+				if overlay.load_block_entry(&hash)?.is_some() {
+					continue
+				}
+
+				let reversion_logs = extract_reversion_logs(&header);
+				tree::import_block(
+					&mut overlay,
+					hash,
+					header.number,
+					header.parent_hash,
+					reversion_logs,
+					weight,
+					stagnant_at,
+				)?;
+				imported += 1;
+			}
+			overlay.into_write_ops().collect::<Vec<_>>()
+		};
+
+		backend.write(ops)?;
+		Ok(imported)
+	}
 }
 
 #[overseer::subsystem(ChainSelection, error = SubsystemError, prefix = self::overseer)]
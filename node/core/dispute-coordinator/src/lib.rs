@@ -42,7 +42,9 @@ use polkadot_node_subsystem_util::{
 	database::Database,
 	rolling_session_window::{DatabaseParams, RollingSessionWindow},
 };
-use polkadot_primitives::{DisputeStatement, ScrapedOnChainVotes, SessionInfo, ValidatorIndex};
+use polkadot_primitives::{
+	DisputeStatement, ScrapedOnChainVotes, SessionIndex, SessionInfo, ValidatorIndex,
+};
 
 use crate::{
 	error::{FatalResult, JfyiError, Result},
@@ -168,6 +170,20 @@ impl DisputeCoordinatorSubsystem {
 		Self { store, config, keystore, metrics }
 	}
 
+	/// Load the earliest session kept in the database and the number of recent disputes.
+	///
+	/// Fails if the database cannot be read or its entries cannot be decoded.
+	pub fn load_db_overview(&self) -> Result<(Option<SessionIndex>, usize), SubsystemError> {
+		let config = self.config.column_config();
+		let earliest_session = db::v1::load_earliest_session(&*self.store, &config)
+			.map_err(|e| SubsystemError::with_origin("dispute-coordinator", e))?;
+		let recent_disputes = db::v1::load_recent_disputes(&*self.store, &config)
+			.map_err(|e| SubsystemError::with_origin("dispute-coordinator", e))?
+			.map_or(0, |disputes| disputes.len());
+
+		Ok((earliest_session, recent_disputes))
+	}
+
 	/// Initialize and afterwards run `Initialized::run`.
 	async fn run<B, Context>(
 		self,
@@ -75,6 +75,27 @@ pub struct Extensions {
 	///
 	/// This value will be set by the `sync-state rpc` implementation.
 	pub light_sync_state: sc_sync_state_rpc::LightSyncStateExtension,
+	/// GRANDPA authority set hard forks.
+	pub grandpa_hard_forks: Option<Vec<GrandpaHardFork>>,
+}
+
+/// A GRANDPA authority set hard fork, see `sc_consensus_grandpa::AuthoritySetHardFork`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GrandpaHardFork {
+	/// The new authority set id.
+	pub set_id: u64,
+	/// The hash of the block at which the hard fork should be applied.
+	pub hash: polkadot_primitives::Hash,
+	/// The number of the block at which the hard fork should be applied.
+	pub number: polkadot_primitives::BlockNumber,
+	/// The authorities in the new set, with their weights.
+	pub authorities: Vec<(GrandpaId, u64)>,
+	/// The latest block number that was finalized before this hard fork. When defined, the
+	/// authority set change is forced, i.e. it is enacted without waiting for the block above
+	/// to be finalized.
+	#[serde(default)]
+	pub last_finalized: Option<polkadot_primitives::BlockNumber>,
 }
 
 /// The `ChainSpec` parameterized for the polkadot runtime.
@@ -2172,10 +2193,81 @@ pub fn thxnet_mainnet_config() -> Result<ThxnetChainSpec, String> {
 		Some(THXNET_DEFAULT_PROTOCOL_ID),
 		None,
 		Some(thxnet_mainnet_chain_spec_properties()),
-		Default::default(),
+		Extensions {
+			grandpa_hard_forks: Some(thxnet_mainnet_grandpa_hard_forks()),
+			..Default::default()
+		},
 	))
 }
 
+/// GRANDPA hard forks for THX Network mainnet.
+///
+/// During the finality deadlock incident (2026-02-20), manual `setStorage`
+/// interventions and forced authority changes created chaotic GRANDPA state
+/// at blocks 14,205,952 through 14,206,626. Only 3 real ForcedChange consensus
+/// logs exist in block headers (at 14,206,555, 14,206,564, 14,206,591).
+/// The migration at block 14,206,625 incremented runtime set_id but did NOT
+/// emit a ForcedChange log, causing a client/runtime set_id divergence.
+///
+/// These 4 hard fork entries use the GRANDPA CLIENT's internal set_id values
+/// (987→988→989→990), each incrementing by exactly 1. After all 4, the client
+/// reaches set_id=991, matching the healthy archive nodes on the network.
+///
+/// This is NOT a chain fork — block hashes and the canonical chain are unchanged.
+/// Only the GRANDPA finality gadget's authority tracking is overridden.
+pub(crate) fn thxnet_mainnet_grandpa_hard_forks() -> Vec<GrandpaHardFork> {
+	use hex_literal::hex;
+	use sp_core::crypto::Ss58Codec;
+
+	// Post-incident authority set, 10 validators, verified from both archive-001 and
+	// archive-002 at block #14,210,000.
+	let authorities = [
+		"5DQjEK2cWN2Qnp5sFdJQAoQ5RLaveyCxYpCbc8kWK2mbkrHi",
+		"5CLCUaSjUhmukZEsp9bTgWi6gBDCMEVLXebN79U46q68Qzh1",
+		"5FMYd9YVje234kxfCwZ5UmWoEQ6Zjz78GjjN3hQLM7SH3wDi",
+		"5CNfCS5SZ6zEu9YtW1HKeyBxWibrwedgd6by4y9W1D2R1NbA",
+		"5CKRFQnViKUtpyEmETsG2TxmzbWHDpGt9n9r1NWEVh9CU4RY",
+		"5FW1LVeZKtrJB8RE3uWSEVsXSyFEkJA6PF5oEeKAnwi8cUMq",
+		"5Dn9oyDjpcm6yp3bNRnsHEDgzxnkRgqvinChpt3WfZScjt48",
+		"5FWBTpBSv4vCR4SC5Q5XT4zGvXF3cAT7AHfe7i45yRdUxwAL",
+		"5Fv7rAvMJaKGEWJr1DNxhn5AeaiPot2TuHNvsCzAk9LyPDLR",
+		"5ECrXnTf7R7W5wF8bv4xJiJYYyQUgnZfGy6uce4t36puANrT",
+	]
+	.into_iter()
+	.map(|address| {
+		(
+			GrandpaId::from_ss58check(address)
+				.expect("hard fork authority addresses are static and they should be carefully defined; qed."),
+			1,
+		)
+	})
+	.collect::<Vec<_>>();
+
+	// (client_set_id, block_hash, block_number)
+	// client_set_id = the GRANDPA client's internal set_id WHEN that block is reached.
+	let forks = [
+		// Override 1st real ForcedChange log (chaotic incident)
+		(987, hex!["9cd4f37aed551dbb8fc422dea295d832b5efffc0230007c86714cac444bd5cff"], 14_206_555),
+		// Override 2nd real ForcedChange log
+		(988, hex!["b24efda871e72649a6512d418e75b5e5e5921307ee04564042f3bd1cdd721d04"], 14_206_564),
+		// Override 3rd real ForcedChange log
+		(989, hex!["323b1605b3030e79bae563f64e5c7f5cad9147632a0230764744d6f04e190b9f"], 14_206_591),
+		// ADD missing ForcedChange (migration at 14,206,625 failed to emit log)
+		(990, hex!["9db27f4ec24dc50ca5c314a76f55384748ee6d0a1af3f719ec07166238a8200c"], 14_206_626),
+	];
+
+	forks
+		.into_iter()
+		.map(|(set_id, hash, number)| GrandpaHardFork {
+			set_id,
+			hash: hash.into(),
+			number,
+			authorities: authorities.clone(),
+			last_finalized: Some(14_205_952),
+		})
+		.collect()
+}
+
 /// Staging testnet config.
 #[cfg(feature = "kusama-native")]
 pub fn kusama_staging_testnet_config() -> Result<KusamaChainSpec, String> {
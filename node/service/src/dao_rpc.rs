@@ -0,0 +1,63 @@
+// Copyright 2026 THX Network Contributors.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! The `dao_*` RPC of the thxnet chains.
+//!
+//! The thxnet chains run on the client of the Polkadot native runtime, whose runtime API does
+//! not declare `DaoApi`. The RPC calls the thxnet runtime API over that same client instead, so
+//! no other runtime has to carry the API. Both thxnet runtimes declare the same `DaoApi`, so the
+//! mainnet runtime API serves the testnet as well.
+
+use polkadot_primitives::{Block, BlockNumber, Hash, Header};
+use polkadot_rpc::{
+	dao::{Dao, DaoApiServer},
+	RpcExtension,
+};
+use sp_api::{ApiRef, CallApiAt, ConstructRuntimeApi, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use std::sync::Arc;
+
+/// A client providing the runtime API of the thxnet runtimes.
+pub(crate) struct ThxnetClient<C>(Arc<C>);
+
+impl<C> ProvideRuntimeApi<Block> for ThxnetClient<C>
+where
+	C: CallApiAt<Block> + 'static,
+{
+	type Api = <thxnet_runtime::RuntimeApi as ConstructRuntimeApi<Block, C>>::RuntimeApi;
+
+	fn runtime_api(&self) -> ApiRef<'_, Self::Api> {
+		thxnet_runtime::RuntimeApi::construct_runtime_api(&*self.0)
+	}
+}
+
+impl<C: HeaderBackend<Block>> HeaderBackend<Block> for ThxnetClient<C> {
+	fn header(&self, hash: Hash) -> sp_blockchain::Result<Option<Header>> {
+		self.0.header(hash)
+	}
+
+	fn info(&self) -> sp_blockchain::Info<Block> {
+		self.0.info()
+	}
+
+	fn status(&self, hash: Hash) -> sp_blockchain::Result<sp_blockchain::BlockStatus> {
+		self.0.status(hash)
+	}
+
+	fn number(&self, hash: Hash) -> sp_blockchain::Result<Option<BlockNumber>> {
+		self.0.number(hash)
+	}
+
+	fn hash(&self, number: BlockNumber) -> sp_blockchain::Result<Option<Hash>> {
+		self.0.hash(number)
+	}
+}
+
+/// Adds the `dao_*` RPC methods, backed by `client`, to `io`.
+pub(crate) fn extend_rpc<C>(io: &mut RpcExtension, client: Arc<C>) -> Result<(), service::Error>
+where
+	C: CallApiAt<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+{
+	io.merge(Dao::new(Arc::new(ThxnetClient(client))).into_rpc())
+		.map_err(|e| service::Error::Application(e.into()))
+}
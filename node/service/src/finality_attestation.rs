@@ -0,0 +1,171 @@
+// Copyright 2026 THX Network Contributors.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Submission of finality attestations for the `finality-rescue` pallet.
+//!
+//! The pallet judges whether finality has stalled from the finalized blocks the GRANDPA
+//! authorities attest on-chain. A node holding a GRANDPA key of the current set signs its
+//! latest finalized block every [`ATTEST_EVERY`] finalized blocks, and at least once per set,
+//! and submits it through the unsigned `attest_finality` call. Once finality stalls no new
+//! attestations are made, so the attested block falls behind the chain head on-chain.
+
+use codec::Encode;
+use futures::StreamExt;
+use grandpa_primitives::{AuthorityId, AuthoritySignature, GrandpaApi, SetId, KEY_TYPE};
+use pallet_finality_rescue::FinalityAttestation;
+use polkadot_primitives::{Block, BlockId, BlockNumber};
+use sc_client_api::BlockchainEvents;
+use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::crypto::ByteArray;
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+use sp_runtime::{traits::Header as _, OpaqueExtrinsic};
+use std::sync::Arc;
+
+const LOG_TARGET: &str = "parachain::finality-attestation";
+
+/// Number of finalized blocks between two attestations of the same authority.
+const ATTEST_EVERY: BlockNumber = 10;
+
+/// Builds the opaque `attest_finality` extrinsic of a runtime.
+pub type AttestationBuilder =
+	fn(FinalityAttestation<BlockNumber>, AuthoritySignature) -> OpaqueExtrinsic;
+
+/// The attestation builder of the runtime of `chain_spec`, if it includes the
+/// `finality-rescue` pallet.
+pub fn builder_for(chain_spec: &Box<dyn sc_chain_spec::ChainSpec>) -> Option<AttestationBuilder> {
+	use crate::IdentifyVariant;
+
+	if chain_spec.is_thxnet_mainnet() {
+		Some(thxnet_mainnet_attestation)
+	} else if chain_spec.is_thxnet_testnet() {
+		Some(thxnet_testnet_attestation)
+	} else {
+		None
+	}
+}
+
+fn thxnet_mainnet_attestation(
+	attestation: FinalityAttestation<BlockNumber>,
+	signature: AuthoritySignature,
+) -> OpaqueExtrinsic {
+	let call = thxnet_runtime::RuntimeCall::FinalityRescue(
+		pallet_finality_rescue::Call::attest_finality { attestation, signature },
+	);
+	let xt = thxnet_runtime::UncheckedExtrinsic::new_unsigned(call);
+	OpaqueExtrinsic::from_bytes(&xt.encode()).expect("encoded extrinsic is a valid extrinsic; qed")
+}
+
+fn thxnet_testnet_attestation(
+	attestation: FinalityAttestation<BlockNumber>,
+	signature: AuthoritySignature,
+) -> OpaqueExtrinsic {
+	let call = thxnet_testnet_runtime::RuntimeCall::FinalityRescue(
+		pallet_finality_rescue::Call::attest_finality { attestation, signature },
+	);
+	let xt = thxnet_testnet_runtime::UncheckedExtrinsic::new_unsigned(call);
+	OpaqueExtrinsic::from_bytes(&xt.encode()).expect("encoded extrinsic is a valid extrinsic; qed")
+}
+
+/// Sign and submit an attestation of the finalized block whenever it advanced far enough.
+///
+/// The authority set is read from the runtime at the best block, since that is the set the
+/// pallet checks attestations against.
+pub async fn run<C, P>(
+	client: Arc<C>,
+	transaction_pool: Arc<P>,
+	keystore: SyncCryptoStorePtr,
+	build: AttestationBuilder,
+) where
+	C: BlockchainEvents<Block> + HeaderBackend<Block> + ProvideRuntimeApi<Block>,
+	C::Api: GrandpaApi<Block>,
+	P: TransactionPool<Block = Block>,
+{
+	let mut finality_notifications = client.finality_notification_stream();
+	let mut last_attested: Option<(SetId, BlockNumber)> = None;
+
+	while let Some(notification) = finality_notifications.next().await {
+		let finalized_number = *notification.header.number();
+		let best_hash = client.info().best_hash;
+
+		let runtime_api = client.runtime_api();
+		let (set_id, authorities) = match (
+			runtime_api.current_set_id(best_hash),
+			runtime_api.grandpa_authorities(best_hash),
+		) {
+			(Ok(set_id), Ok(authorities)) => (set_id, authorities),
+			(Err(e), _) | (_, Err(e)) => {
+				gum::debug!(
+					target: LOG_TARGET,
+					err = ?e,
+					"Failed to read the GRANDPA authority set",
+				);
+				continue
+			},
+		};
+
+		match last_attested {
+			Some((last_set_id, last_number))
+				if last_set_id == set_id && finalized_number < last_number + ATTEST_EVERY =>
+				continue,
+			_ => {},
+		}
+
+		let local = authorities.iter().enumerate().find(|(_, (id, _))| {
+			SyncCryptoStore::has_keys(&*keystore, &[(id.to_raw_vec(), KEY_TYPE)])
+		});
+		let (authority_index, authority_id) = match local {
+			Some((index, (id, _))) => (index as u32, id.clone()),
+			// Not an authority of the current set.
+			None => continue,
+		};
+
+		let attestation = FinalityAttestation { set_id, authority_index, finalized_number };
+		let signature = match sign(&keystore, &authority_id, &attestation.signing_payload()) {
+			Some(signature) => signature,
+			None => {
+				gum::warn!(
+					target: LOG_TARGET,
+					?set_id,
+					?authority_index,
+					"Failed to sign the finality attestation",
+				);
+				continue
+			},
+		};
+
+		let xt = build(attestation, signature);
+		match transaction_pool
+			.submit_one(&BlockId::Hash(best_hash), TransactionSource::Local, xt)
+			.await
+		{
+			Ok(_) => {
+				gum::debug!(
+					target: LOG_TARGET,
+					?set_id,
+					?authority_index,
+					?finalized_number,
+					"Submitted finality attestation",
+				);
+				last_attested = Some((set_id, finalized_number));
+			},
+			Err(e) => gum::debug!(
+				target: LOG_TARGET,
+				err = ?e,
+				?finalized_number,
+				"Finality attestation was rejected by the transaction pool",
+			),
+		}
+	}
+}
+
+fn sign(
+	keystore: &SyncCryptoStorePtr,
+	authority_id: &AuthorityId,
+	payload: &[u8],
+) -> Option<AuthoritySignature> {
+	let signature =
+		SyncCryptoStore::sign_with(&**keystore, KEY_TYPE, &authority_id.into(), payload).ok()??;
+	signature.try_into().ok()
+}
@@ -0,0 +1,508 @@
+// Copyright 2026 THX Network Contributors.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! A watchdog raising the alarm when finality falls behind.
+//!
+//! It tracks how far the finalized block lags behind the best block and for how long the
+//! GRANDPA voter has been stuck in the same round. Past the configured thresholds it emits
+//! warnings and telemetry events, and once finality is considered stalled it can write a
+//! diagnostic bundle with the GRANDPA aux values, the leaves and the peer set to disk. The raw
+//! aux values in the bundle can be restored with `grandpa-recovery --restore`.
+
+use crate::grandpa_recovery::{self, AuxBackup};
+use consensus_common::SyncOracle;
+use futures::{
+	channel::oneshot,
+	future::{self, Either},
+};
+use polkadot_node_subsystem::messages::ChainSelectionMessage;
+use polkadot_node_subsystem_util::metrics::{self, prometheus};
+use polkadot_overseer::Handle;
+use polkadot_primitives::{Block, BlockNumber, Hash};
+use sc_client_api::{blockchain::Backend as _, Backend};
+use sc_network_sync::SyncingService;
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use std::{
+	path::PathBuf,
+	sync::Arc,
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use telemetry::{telemetry, TelemetryHandle, CONSENSUS_INFO, CONSENSUS_WARN};
+
+const LOG_TARGET: &str = "parachain::finality-watchdog";
+
+/// How often finality is checked.
+const CHECK_INTERVAL: Duration = Duration::from_secs(6);
+
+/// How often the alarm is repeated while finality is lagging or stalled.
+const REPEAT_INTERVAL: Duration = Duration::from_secs(600);
+
+/// How long to wait for the chain selection subsystem when writing a diagnostic bundle.
+const LEAVES_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Thresholds and actions of the finality watchdog.
+#[derive(Debug, Clone)]
+pub struct FinalityWatchdogConfig {
+	/// Number of unfinalized blocks from which finality is considered lagging.
+	pub lagging_blocks: BlockNumber,
+	/// Number of unfinalized blocks from which finality is considered stalled.
+	pub stalled_blocks: BlockNumber,
+	/// Time without GRANDPA round progress after which finality is considered stalled.
+	pub stalled_round_duration: Duration,
+	/// Directory to write a diagnostic bundle to whenever finality stalls.
+	pub diagnostics_dir: Option<PathBuf>,
+}
+
+impl Default for FinalityWatchdogConfig {
+	fn default() -> Self {
+		Self {
+			lagging_blocks: 50,
+			stalled_blocks: 200,
+			stalled_round_duration: Duration::from_secs(120),
+			diagnostics_dir: None,
+		}
+	}
+}
+
+/// How bad finality is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+	Healthy,
+	Lagging,
+	Stalled,
+}
+
+impl Level {
+	fn as_str(self) -> &'static str {
+		match self {
+			Level::Healthy => "healthy",
+			Level::Lagging => "lagging",
+			Level::Stalled => "stalled",
+		}
+	}
+}
+
+/// Prometheus metrics of the finality watchdog.
+#[derive(Debug, Default, Clone)]
+struct Metrics(Option<MetricsInner>);
+
+#[derive(Debug, Clone)]
+struct MetricsInner {
+	finality_lag: prometheus::Gauge<prometheus::U64>,
+	round_stall: prometheus::Gauge<prometheus::U64>,
+	level: prometheus::Gauge<prometheus::U64>,
+	escalations_total: prometheus::CounterVec<prometheus::U64>,
+}
+
+impl metrics::Metrics for Metrics {
+	fn try_register(registry: &prometheus::Registry) -> Result<Self, prometheus::PrometheusError> {
+		let metrics = MetricsInner {
+			finality_lag: prometheus::register(
+				prometheus::Gauge::new(
+					"polkadot_finality_watchdog_lag_blocks",
+					"Number of blocks between the best and the finalized block",
+				)?,
+				registry,
+			)?,
+			round_stall: prometheus::register(
+				prometheus::Gauge::new(
+					"polkadot_finality_watchdog_round_stall_seconds",
+					"Seconds since the GRANDPA voter last moved to a new round",
+				)?,
+				registry,
+			)?,
+			level: prometheus::register(
+				prometheus::Gauge::new(
+					"polkadot_finality_watchdog_level",
+					"Finality health: 0 healthy, 1 lagging, 2 stalled",
+				)?,
+				registry,
+			)?,
+			escalations_total: prometheus::register(
+				prometheus::CounterVec::new(
+					prometheus::Opts::new(
+						"polkadot_finality_watchdog_escalations_total",
+						"Number of times finality became lagging or stalled",
+					),
+					&["level"],
+				)?,
+				registry,
+			)?,
+		};
+
+		Ok(Metrics(Some(metrics)))
+	}
+}
+
+impl Metrics {
+	fn note_check(&self, lag: BlockNumber, round_stall: Duration, level: Level) {
+		if let Some(ref metrics) = self.0 {
+			metrics.finality_lag.set(lag as _);
+			metrics.round_stall.set(round_stall.as_secs());
+			metrics.level.set(level as _);
+		}
+	}
+
+	fn note_escalation(&self, level: Level) {
+		if let Some(ref metrics) = self.0 {
+			metrics.escalations_total.with_label_values(&[level.as_str()]).inc();
+		}
+	}
+}
+
+/// The escalation state of the watchdog, independent of where its inputs come from.
+struct Escalation {
+	config: FinalityWatchdogConfig,
+	level: Level,
+	last_alarm: Option<Instant>,
+	round: Option<(u64, u64)>,
+	round_since: Instant,
+}
+
+/// What the watchdog should do after a check.
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+	/// Nothing changed.
+	None,
+	/// Raise the alarm for the current level, again if it did not change.
+	Alarm { escalated: bool },
+	/// Finality is healthy again.
+	Recovered,
+}
+
+impl Escalation {
+	fn new(config: FinalityWatchdogConfig, now: Instant) -> Self {
+		Self { config, level: Level::Healthy, last_alarm: None, round: None, round_since: now }
+	}
+
+	/// Note the GRANDPA `(set_id, round)` of the voter and return for how long it has not
+	/// changed.
+	fn note_round(&mut self, round: Option<(u64, u64)>, now: Instant) -> Duration {
+		if round.is_none() || round != self.round {
+			self.round = round;
+			self.round_since = now;
+		}
+		now.saturating_duration_since(self.round_since)
+	}
+
+	fn check(&mut self, lag: BlockNumber, round_stall: Duration, now: Instant) -> Action {
+		let level = if lag >= self.config.stalled_blocks ||
+			(lag >= self.config.lagging_blocks &&
+				round_stall >= self.config.stalled_round_duration)
+		{
+			Level::Stalled
+		} else if lag >= self.config.lagging_blocks {
+			Level::Lagging
+		} else {
+			Level::Healthy
+		};
+
+		let previous = std::mem::replace(&mut self.level, level);
+		if level == Level::Healthy {
+			self.last_alarm = None;
+			return if previous == Level::Healthy { Action::None } else { Action::Recovered }
+		}
+
+		let escalated = level > previous;
+		let repeat = self.last_alarm.map_or(true, |last_alarm| {
+			now.saturating_duration_since(last_alarm) >= REPEAT_INTERVAL
+		});
+		if escalated || repeat {
+			self.last_alarm = Some(now);
+			Action::Alarm { escalated }
+		} else {
+			Action::None
+		}
+	}
+}
+
+/// A peer as listed in the diagnostic bundle.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PeerSummary {
+	peer_id: String,
+	roles: String,
+	best_hash: Hash,
+	best_number: BlockNumber,
+}
+
+/// The state of the node when finality stalled.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiagnosticBundle {
+	unix_time: u64,
+	best_number: BlockNumber,
+	best_hash: Hash,
+	finalized_number: BlockNumber,
+	finalized_hash: Hash,
+	grandpa_set_id: u64,
+	grandpa_round: Option<u64>,
+	grandpa_round_stall_secs: u64,
+	/// The aux-store authority set with its pending changes, as printed by `grandpa-recovery`.
+	authority_set: Option<Vec<String>>,
+	/// The raw GRANDPA aux values, restorable with `grandpa-recovery --restore`.
+	grandpa_aux: AuxBackup,
+	block_leaves: Vec<Hash>,
+	/// `None` if the chain selection subsystem did not answer.
+	chain_selection_leaves: Option<Vec<Hash>>,
+	peers: Vec<PeerSummary>,
+}
+
+/// Watches finality and escalates while it lags behind.
+pub(crate) struct FinalityWatchdog<B> {
+	config: FinalityWatchdogConfig,
+	backend: Arc<B>,
+	sync_service: Arc<SyncingService<Block>>,
+	overseer_handle: Option<Handle>,
+	shared_voter_state: grandpa::SharedVoterState,
+	shared_authority_set: grandpa::SharedAuthoritySet<Hash, BlockNumber>,
+	telemetry: Option<TelemetryHandle>,
+	metrics: Metrics,
+}
+
+impl<B> FinalityWatchdog<B>
+where
+	B: Backend<Block> + 'static,
+{
+	pub(crate) fn new(
+		config: FinalityWatchdogConfig,
+		backend: Arc<B>,
+		sync_service: Arc<SyncingService<Block>>,
+		overseer_handle: Option<Handle>,
+		shared_voter_state: grandpa::SharedVoterState,
+		shared_authority_set: grandpa::SharedAuthoritySet<Hash, BlockNumber>,
+		telemetry: Option<TelemetryHandle>,
+		registry: Option<&prometheus::Registry>,
+	) -> Result<Self, prometheus::PrometheusError> {
+		Ok(Self {
+			config,
+			backend,
+			sync_service,
+			overseer_handle,
+			shared_voter_state,
+			shared_authority_set,
+			telemetry,
+			metrics: metrics::Metrics::register(registry)?,
+		})
+	}
+
+	fn current_round(&self) -> Option<(u64, u64)> {
+		let voter_state = self.shared_voter_state.voter_state()?;
+		Some((self.shared_authority_set.set_id(), voter_state.best_round.0))
+	}
+
+	/// Run the watchdog until the node shuts down.
+	pub(crate) async fn run(self) {
+		let mut escalation = Escalation::new(self.config.clone(), Instant::now());
+
+		loop {
+			futures_timer::Delay::new(CHECK_INTERVAL).await;
+
+			// Finality is only imported every few hundred blocks while syncing.
+			if self.sync_service.is_major_syncing() {
+				let _ = escalation.note_round(None, Instant::now());
+				continue
+			}
+
+			let now = Instant::now();
+			let info = self.backend.blockchain().info();
+			let lag = info.best_number.saturating_sub(info.finalized_number);
+			let round = self.current_round();
+			let round_stall = escalation.note_round(round, now);
+
+			let action = escalation.check(lag, round_stall, now);
+			self.metrics.note_check(lag, round_stall, escalation.level);
+
+			match action {
+				Action::None => {},
+				Action::Alarm { escalated } => {
+					let level = escalation.level;
+					if escalated {
+						self.metrics.note_escalation(level);
+					}
+					gum::warn!(
+						target: LOG_TARGET,
+						status = level.as_str(),
+						lag,
+						best = info.best_number,
+						finalized = info.finalized_number,
+						set_id = ?round.map(|(set_id, _)| set_id),
+						round = ?round.map(|(_, round)| round),
+						round_stall_secs = round_stall.as_secs(),
+						"Finality is {}",
+						level.as_str(),
+					);
+					telemetry!(
+						self.telemetry;
+						CONSENSUS_WARN;
+						"finality_watchdog.alarm";
+						"level" => level.as_str(),
+						"lag" => lag,
+						"finalized" => info.finalized_number,
+						"round_stall_secs" => round_stall.as_secs(),
+					);
+
+					let diagnostics_dir = self
+						.config
+						.diagnostics_dir
+						.as_ref()
+						.filter(|_| escalated && level == Level::Stalled);
+					if let Some(dir) = diagnostics_dir {
+						let bundle = self.diagnostic_bundle(round, round_stall).await;
+						match write_bundle(dir, &bundle) {
+							Ok(path) => gum::warn!(
+								target: LOG_TARGET,
+								path = %path.display(),
+								"Wrote finality diagnostic bundle",
+							),
+							Err(err) => gum::error!(
+								target: LOG_TARGET,
+								?err,
+								"Cannot write finality diagnostic bundle",
+							),
+						}
+					}
+				},
+				Action::Recovered => {
+					gum::info!(
+						target: LOG_TARGET,
+						finalized = info.finalized_number,
+						lag,
+						"Finality is healthy again",
+					);
+					telemetry!(
+						self.telemetry;
+						CONSENSUS_INFO;
+						"finality_watchdog.recovered";
+						"finalized" => info.finalized_number,
+					);
+				},
+			}
+		}
+	}
+
+	async fn chain_selection_leaves(&self) -> Option<Vec<Hash>> {
+		let mut overseer_handle = self.overseer_handle.clone()?;
+		let (tx, rx) = oneshot::channel();
+		let request = Box::pin(async move {
+			overseer_handle
+				.send_msg(ChainSelectionMessage::Leaves(tx), std::any::type_name::<Self>())
+				.await;
+			rx.await.ok()
+		});
+
+		match future::select(request, futures_timer::Delay::new(LEAVES_TIMEOUT)).await {
+			Either::Left((leaves, _)) => leaves,
+			Either::Right(_) => None,
+		}
+	}
+
+	async fn diagnostic_bundle(
+		&self,
+		round: Option<(u64, u64)>,
+		round_stall: Duration,
+	) -> DiagnosticBundle {
+		let blockchain = self.backend.blockchain();
+		let info = blockchain.info();
+
+		let authority_set = match grandpa_recovery::read_authority_set(&*self.backend) {
+			Ok(set) => set.map(|set| set.to_string().lines().map(Into::into).collect()),
+			Err(err) => Some(vec![format!("cannot read: {}", err)]),
+		};
+		let peers = self
+			.sync_service
+			.peers_info()
+			.await
+			.unwrap_or_default()
+			.into_iter()
+			.map(|(peer_id, info)| PeerSummary {
+				peer_id: peer_id.to_base58(),
+				roles: format!("{:?}", info.roles),
+				best_hash: info.best_hash,
+				best_number: info.best_number,
+			})
+			.collect();
+
+		DiagnosticBundle {
+			unix_time: SystemTime::now()
+				.duration_since(UNIX_EPOCH)
+				.map_or(0, |time| time.as_secs()),
+			best_number: info.best_number,
+			best_hash: info.best_hash,
+			finalized_number: info.finalized_number,
+			finalized_hash: info.finalized_hash,
+			grandpa_set_id: self.shared_authority_set.set_id(),
+			grandpa_round: round.map(|(_, round)| round),
+			grandpa_round_stall_secs: round_stall.as_secs(),
+			authority_set,
+			grandpa_aux: AuxBackup::read(&*self.backend).unwrap_or_default(),
+			block_leaves: blockchain.leaves().unwrap_or_default(),
+			chain_selection_leaves: self.chain_selection_leaves().await,
+			peers,
+		}
+	}
+}
+
+fn write_bundle(dir: &std::path::Path, bundle: &DiagnosticBundle) -> std::io::Result<PathBuf> {
+	std::fs::create_dir_all(dir)?;
+	let path = dir.join(format!(
+		"finality-diagnostics-{}-{}.json",
+		bundle.finalized_number, bundle.unix_time
+	));
+	let file = std::fs::OpenOptions::new().write(true).create_new(true).open(&path)?;
+	serde_json::to_writer_pretty(file, bundle)?;
+	Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn escalation_follows_lag_and_round_progress() {
+		let start = Instant::now();
+		let config = FinalityWatchdogConfig {
+			lagging_blocks: 10,
+			stalled_blocks: 100,
+			stalled_round_duration: Duration::from_secs(60),
+			diagnostics_dir: None,
+		};
+		let mut escalation = Escalation::new(config, start);
+
+		assert_eq!(escalation.check(2, Duration::ZERO, start), Action::None);
+		assert_eq!(escalation.check(10, Duration::ZERO, start), Action::Alarm { escalated: true });
+		// No repeated alarm before the repeat interval elapsed.
+		assert_eq!(escalation.check(20, Duration::ZERO, start), Action::None);
+		assert_eq!(
+			escalation.check(20, Duration::ZERO, start + REPEAT_INTERVAL),
+			Action::Alarm { escalated: false },
+		);
+		assert_eq!(escalation.level, Level::Lagging);
+
+		// A stuck round escalates to stalled even below the stalled lag.
+		assert_eq!(
+			escalation.check(20, Duration::from_secs(60), start + REPEAT_INTERVAL),
+			Action::Alarm { escalated: true },
+		);
+		assert_eq!(escalation.level, Level::Stalled);
+
+		assert_eq!(escalation.check(1, Duration::ZERO, start), Action::Recovered);
+		assert_eq!(escalation.level, Level::Healthy);
+		assert_eq!(escalation.check(1, Duration::ZERO, start), Action::None);
+	}
+
+	#[test]
+	fn round_stall_resets_on_new_round_or_set() {
+		let start = Instant::now();
+		let mut escalation = Escalation::new(FinalityWatchdogConfig::default(), start);
+
+		assert_eq!(escalation.note_round(Some((1, 5)), start), Duration::ZERO);
+		let later = start + Duration::from_secs(30);
+		assert_eq!(escalation.note_round(Some((1, 5)), later), Duration::from_secs(30));
+		assert_eq!(escalation.note_round(Some((1, 6)), later), Duration::ZERO);
+		assert_eq!(escalation.note_round(Some((2, 6)), later), Duration::ZERO);
+		// Without a running voter there is no round to be stuck in.
+		assert_eq!(escalation.note_round(None, later), Duration::ZERO);
+	}
+}
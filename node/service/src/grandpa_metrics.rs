@@ -0,0 +1,227 @@
+// Copyright 2026 THX Network Contributors.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Prometheus metrics of the GRANDPA round state.
+//!
+//! The GRANDPA voter only exports the rounds it completed and its own votes. These metrics are
+//! sampled from the shared voter state instead, so that a round in which too few authorities
+//! vote shows up while it is in progress, together with the pending authority set changes kept
+//! in aux storage.
+
+use crate::grandpa_recovery::{self, AuthoritySet, ForkTreeNodeCompat};
+use futures::future::Future;
+use polkadot_primitives::{BlockNumber, Hash};
+use prometheus_endpoint::{register, Gauge, GaugeVec, Opts, PrometheusError, Registry, U64};
+use sc_client_api::AuxStore;
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+/// How often the voter state is sampled.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often the pending changes are read from aux storage, unless the authority set changes.
+/// Decoding the stored authority set is too expensive to do on every sample.
+const PENDING_CHANGES_INTERVAL: Duration = Duration::from_secs(30);
+
+const VOTES: [&str; 2] = ["prevote", "precommit"];
+
+struct Metrics {
+	round: Gauge<U64>,
+	set_id: Gauge<U64>,
+	total_weight: Gauge<U64>,
+	threshold_weight: Gauge<U64>,
+	vote_weight: GaugeVec<U64>,
+	votes: GaugeVec<U64>,
+	missing_voters: GaugeVec<U64>,
+	pending_changes: GaugeVec<U64>,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			round: register(
+				Gauge::new("polkadot_grandpa_round", "Current round of the GRANDPA voter")?,
+				registry,
+			)?,
+			set_id: register(
+				Gauge::new("polkadot_grandpa_set_id", "Current GRANDPA authority set id")?,
+				registry,
+			)?,
+			total_weight: register(
+				Gauge::new(
+					"polkadot_grandpa_round_total_weight",
+					"Total weight of the GRANDPA authorities in the current round",
+				)?,
+				registry,
+			)?,
+			threshold_weight: register(
+				Gauge::new(
+					"polkadot_grandpa_round_threshold_weight",
+					"Vote weight needed for a supermajority in the current round",
+				)?,
+				registry,
+			)?,
+			vote_weight: register(
+				GaugeVec::new(
+					Opts::new(
+						"polkadot_grandpa_round_vote_weight",
+						"Weight of the prevotes or precommits seen in the current round",
+					),
+					&["vote"],
+				)?,
+				registry,
+			)?,
+			votes: register(
+				GaugeVec::new(
+					Opts::new(
+						"polkadot_grandpa_round_votes",
+						"Number of authorities whose prevote or precommit was seen in the current round",
+					),
+					&["vote"],
+				)?,
+				registry,
+			)?,
+			missing_voters: register(
+				GaugeVec::new(
+					Opts::new(
+						"polkadot_grandpa_round_missing_voters",
+						"Number of authorities whose prevote or precommit was not seen yet in the current round",
+					),
+					&["vote"],
+				)?,
+				registry,
+			)?,
+			pending_changes: register(
+				GaugeVec::new(
+					Opts::new(
+						"polkadot_grandpa_pending_changes",
+						"Number of pending standard or forced GRANDPA authority set changes",
+					),
+					&["kind"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+/// The number of pending standard and forced changes of `set`.
+fn pending_changes(set: &AuthoritySet) -> (usize, usize) {
+	fn count(nodes: &[ForkTreeNodeCompat<Hash, BlockNumber>]) -> usize {
+		nodes.iter().map(|node| 1 + count(&node.children)).sum()
+	}
+
+	(count(&set.pending_standard_changes.roots), set.pending_forced_changes.len())
+}
+
+/// Register the metrics and return the task sampling them.
+pub(crate) fn run<B: AuxStore + Send + Sync + 'static>(
+	backend: Arc<B>,
+	shared_voter_state: grandpa::SharedVoterState,
+	shared_authority_set: grandpa::SharedAuthoritySet<Hash, BlockNumber>,
+	registry: &Registry,
+) -> Result<impl Future<Output = ()>, PrometheusError> {
+	let metrics = Metrics::register(registry)?;
+
+	Ok(async move {
+		// The set id and time of the last read of the pending changes.
+		let mut pending_changes_read: Option<(u64, Instant)> = None;
+		loop {
+			futures_timer::Delay::new(SAMPLE_INTERVAL).await;
+
+			let set_id = shared_authority_set.set_id();
+			metrics.set_id.set(set_id);
+			let read_pending_changes = match pending_changes_read {
+				Some((read_set_id, read_at)) =>
+					read_set_id != set_id || read_at.elapsed() >= PENDING_CHANGES_INTERVAL,
+				None => true,
+			};
+			if read_pending_changes {
+				pending_changes_read = Some((set_id, Instant::now()));
+				if let Ok(Some(set)) = grandpa_recovery::read_authority_set(&*backend) {
+					let (standard, forced) = pending_changes(&set);
+					metrics.pending_changes.with_label_values(&["standard"]).set(standard as _);
+					metrics.pending_changes.with_label_values(&["forced"]).set(forced as _);
+				}
+			}
+
+			let voter_state = match shared_voter_state.voter_state() {
+				Some(voter_state) => voter_state,
+				None => continue,
+			};
+			let (round, state) = &voter_state.best_round;
+			metrics.round.set(*round);
+			metrics.total_weight.set(state.total_weight.get());
+			metrics.threshold_weight.set(state.threshold_weight.get());
+
+			let authorities = shared_authority_set.current_authorities();
+			let seen = [
+				(state.prevote_current_weight.0, &state.prevote_ids),
+				(state.precommit_current_weight.0, &state.precommit_ids),
+			];
+			for (vote, (weight, ids)) in VOTES.into_iter().zip(seen) {
+				let missing = authorities.iter().filter(|(id, _)| !ids.contains(*id)).count();
+				metrics.vote_weight.with_label_values(&[vote]).set(weight);
+				metrics.votes.with_label_values(&[vote]).set(ids.len() as _);
+				metrics.missing_voters.with_label_values(&[vote]).set(missing as _);
+			}
+		}
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::grandpa_recovery::{
+		AuthoritySetCompat, DelayKindCompat, ForkTreeCompat, PendingChangeCompat,
+	};
+
+	fn change(
+		canon_height: BlockNumber,
+		delay_kind: DelayKindCompat<BlockNumber>,
+	) -> PendingChangeCompat<Hash, BlockNumber> {
+		PendingChangeCompat {
+			next_authorities: Vec::new(),
+			delay: 0,
+			canon_height,
+			canon_hash: Hash::repeat_byte(canon_height as u8),
+			delay_kind,
+		}
+	}
+
+	fn node(
+		number: BlockNumber,
+		children: Vec<ForkTreeNodeCompat<Hash, BlockNumber>>,
+	) -> ForkTreeNodeCompat<Hash, BlockNumber> {
+		ForkTreeNodeCompat {
+			hash: Hash::repeat_byte(number as u8),
+			number,
+			data: change(number, DelayKindCompat::Finalized),
+			children,
+		}
+	}
+
+	#[test]
+	fn pending_changes_counts_the_whole_fork_tree() {
+		let set = AuthoritySetCompat {
+			current_authorities: Vec::new(),
+			set_id: 1,
+			pending_standard_changes: ForkTreeCompat {
+				roots: vec![
+					node(10, vec![node(11, vec![node(12, Vec::new())]), node(13, Vec::new())]),
+					node(20, Vec::new()),
+				],
+				best_finalized_number: None,
+			},
+			pending_forced_changes: vec![change(
+				30,
+				DelayKindCompat::Best { median_last_finalized: 25 },
+			)],
+			authority_set_changes: Vec::new(),
+		};
+
+		assert_eq!(pending_changes(&set), (5, 1));
+	}
+}
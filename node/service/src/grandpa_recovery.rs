@@ -0,0 +1,507 @@
+// Copyright 2026 THX Network Contributors.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Inspection and recovery of the GRANDPA state kept in aux storage.
+//!
+//! When GRANDPA finality is stuck because the client's authority set diverged from the chain
+//! (for example a set_id mismatch after an emergency forced change), the hard fork matching in
+//! `block_import_with_authority_set_hard_forks` does not help for blocks that are already
+//! `InChain`. This module reads and rewrites the aux-store `AuthoritySet` directly, and backs up
+//! and restores the original values. It is driven by the `grandpa-recovery` subcommand.
+
+use codec::{Decode, Encode};
+use polkadot_primitives::{BlockNumber, Hash};
+use sc_client_api::AuxStore;
+use serde::{Deserialize, Serialize};
+use sp_core::{crypto::Ss58Codec, Bytes};
+use std::{fmt, path::Path};
+
+/// Aux storage keys matching sc-consensus-grandpa's internal constants.
+const AUTHORITY_SET_KEY: &[u8] = b"grandpa_voters";
+const SET_STATE_KEY: &[u8] = b"grandpa_completed_round";
+const VERSION_KEY: &[u8] = b"grandpa_schema_version";
+const CURRENT_VERSION: u32 = 3;
+
+/// A GRANDPA authority and its weight.
+pub type Authority = (grandpa_primitives::AuthorityId, u64);
+
+/// SCALE-compatible replica of `sc_consensus_grandpa::AuthoritySet<H, N>`.
+/// Field order must match exactly for correct decoding/encoding.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct AuthoritySetCompat<H, N> {
+	pub current_authorities: Vec<Authority>,
+	pub set_id: u64,
+	pub pending_standard_changes: ForkTreeCompat<H, N>,
+	pub pending_forced_changes: Vec<PendingChangeCompat<H, N>>,
+	pub authority_set_changes: Vec<(u64, N)>,
+}
+
+/// SCALE-compatible replica of `fork_tree::ForkTree` serialization.
+/// ForkTree serializes as (roots: Vec<Node>, best_finalized_number: Option<N>).
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct ForkTreeCompat<H, N> {
+	pub roots: Vec<ForkTreeNodeCompat<H, N>>,
+	pub best_finalized_number: Option<N>,
+}
+
+/// SCALE-compatible replica of `fork_tree::Node`.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct ForkTreeNodeCompat<H, N> {
+	pub hash: H,
+	pub number: N,
+	pub data: PendingChangeCompat<H, N>,
+	pub children: Vec<ForkTreeNodeCompat<H, N>>,
+}
+
+/// SCALE-compatible replica of `PendingChange<H, N>`.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct PendingChangeCompat<H, N> {
+	pub next_authorities: Vec<Authority>,
+	pub delay: N,
+	pub canon_height: N,
+	pub canon_hash: H,
+	pub delay_kind: DelayKindCompat<N>,
+}
+
+/// SCALE-compatible replica of `DelayKind<N>`.
+#[derive(Debug, Clone, Encode, Decode)]
+pub enum DelayKindCompat<N> {
+	Finalized,
+	Best { median_last_finalized: N },
+}
+
+/// The GRANDPA authority set of a relay chain node.
+pub type AuthoritySet = AuthoritySetCompat<Hash, BlockNumber>;
+
+impl<H: fmt::Debug, N: fmt::Display> fmt::Display for PendingChangeCompat<H, N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"at #{} ({:?}), delay {}, {} authorities",
+			self.canon_height,
+			self.canon_hash,
+			self.delay,
+			self.next_authorities.len(),
+		)?;
+		match &self.delay_kind {
+			DelayKindCompat::Finalized => write!(f, ", standard"),
+			DelayKindCompat::Best { median_last_finalized } =>
+				write!(f, ", forced with median #{}", median_last_finalized),
+		}
+	}
+}
+
+impl<H: fmt::Debug, N: fmt::Display> fmt::Display for AuthoritySetCompat<H, N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fn fmt_nodes<H: fmt::Debug, N: fmt::Display>(
+			f: &mut fmt::Formatter,
+			nodes: &[ForkTreeNodeCompat<H, N>],
+			depth: usize,
+		) -> fmt::Result {
+			for node in nodes {
+				writeln!(f, "    {}- {}", "  ".repeat(depth), node.data)?;
+				fmt_nodes(f, &node.children, depth + 1)?;
+			}
+			Ok(())
+		}
+
+		writeln!(f, "  set_id: {}", self.set_id)?;
+		writeln!(f, "  authorities ({}):", self.current_authorities.len())?;
+		for (id, weight) in &self.current_authorities {
+			writeln!(f, "    - {} (weight {})", id.to_ss58check(), weight)?;
+		}
+		writeln!(f, "  pending standard changes:")?;
+		fmt_nodes(f, &self.pending_standard_changes.roots, 0)?;
+		writeln!(f, "  pending forced changes ({}):", self.pending_forced_changes.len())?;
+		for change in &self.pending_forced_changes {
+			writeln!(f, "    - {}", change)?;
+		}
+		writeln!(f, "  authority set changes ({}):", self.authority_set_changes.len())?;
+		for (set_id, number) in &self.authority_set_changes {
+			writeln!(f, "    - set {} finalized at #{}", set_id, number)?;
+		}
+		Ok(())
+	}
+}
+
+/// Changes to apply to the stored authority set. Unset fields are left as they are.
+///
+/// Can be read from a JSON file, for example:
+///
+/// ```json
+/// { "setId": 992, "authorities": [["5DQj...", 1]], "clearPendingChanges": true }
+/// ```
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AuthoritySetRewrite {
+	#[serde(default)]
+	pub set_id: Option<u64>,
+	#[serde(default)]
+	pub authorities: Option<Vec<Authority>>,
+	#[serde(default)]
+	pub clear_pending_changes: bool,
+	#[serde(default)]
+	pub authority_set_changes: Option<Vec<(u64, BlockNumber)>>,
+}
+
+impl AuthoritySetRewrite {
+	/// Read a rewrite from the JSON file at `path`.
+	pub fn from_json_file(path: &Path) -> sp_blockchain::Result<Self> {
+		let file = std::fs::File::open(path).map_err(|e| {
+			sp_blockchain::Error::Backend(format!("Cannot open {}: {}", path.display(), e))
+		})?;
+		serde_json::from_reader(file).map_err(|e| {
+			sp_blockchain::Error::Backend(format!("Invalid rewrite in {}: {}", path.display(), e))
+		})
+	}
+
+	/// Whether applying the rewrite would leave the authority set unchanged.
+	pub fn is_empty(&self) -> bool {
+		self.set_id.is_none() &&
+			self.authorities.is_none() &&
+			!self.clear_pending_changes &&
+			self.authority_set_changes.is_none()
+	}
+
+	/// Apply the rewrite to `set`.
+	pub fn apply(&self, mut set: AuthoritySet) -> AuthoritySet {
+		if let Some(set_id) = self.set_id {
+			set.set_id = set_id;
+		}
+		if let Some(authorities) = &self.authorities {
+			set.current_authorities = authorities.clone();
+		}
+		if self.clear_pending_changes {
+			set.pending_standard_changes.roots.clear();
+			set.pending_forced_changes.clear();
+		}
+		if let Some(changes) = &self.authority_set_changes {
+			set.authority_set_changes = changes.clone();
+		}
+		set
+	}
+}
+
+/// Parse an SS58 encoded GRANDPA authority key.
+pub fn parse_authority(ss58: &str) -> sp_blockchain::Result<grandpa_primitives::AuthorityId> {
+	grandpa_primitives::AuthorityId::from_ss58check(ss58).map_err(|e| {
+		sp_blockchain::Error::Backend(format!("Invalid GRANDPA key {}: {:?}", ss58, e))
+	})
+}
+
+/// Read the authority set from aux storage, if there is one.
+pub fn read_authority_set<B: AuxStore>(backend: &B) -> sp_blockchain::Result<Option<AuthoritySet>> {
+	backend
+		.get_aux(AUTHORITY_SET_KEY)?
+		.map(|raw| decode_authority_set(&raw))
+		.transpose()
+}
+
+fn decode_authority_set(raw: &[u8]) -> sp_blockchain::Result<AuthoritySet> {
+	AuthoritySet::decode(&mut &raw[..])
+		.map_err(|e| sp_blockchain::Error::Backend(format!("Failed to decode AuthoritySet: {}", e)))
+}
+
+/// Write `set` as the authority set, together with the current schema version.
+///
+/// With `reset_voter_state`, the `VoterSetState` is deleted so that `load_persistent`
+/// recreates it from the new authority set, avoiding set_id mismatch errors.
+pub fn write_authority_set<B: AuxStore>(
+	backend: &B,
+	set: &AuthoritySet,
+	reset_voter_state: bool,
+) -> sp_blockchain::Result<()> {
+	let encoded_authority_set = set.encode();
+	let version_encoded = CURRENT_VERSION.encode();
+	let delete: &[&[u8]] = if reset_voter_state { &[SET_STATE_KEY] } else { &[] };
+	backend.insert_aux(
+		&[(AUTHORITY_SET_KEY, &encoded_authority_set[..]), (VERSION_KEY, &version_encoded[..])],
+		delete,
+	)
+}
+
+/// Raw GRANDPA aux values, as saved before a rewrite.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuxBackup {
+	pub authority_set: Option<Bytes>,
+	pub voter_set_state: Option<Bytes>,
+	pub schema_version: Option<Bytes>,
+}
+
+impl AuxBackup {
+	/// Read the current GRANDPA aux values from `backend`.
+	pub fn read<B: AuxStore>(backend: &B) -> sp_blockchain::Result<Self> {
+		Ok(Self {
+			authority_set: backend.get_aux(AUTHORITY_SET_KEY)?.map(Bytes),
+			voter_set_state: backend.get_aux(SET_STATE_KEY)?.map(Bytes),
+			schema_version: backend.get_aux(VERSION_KEY)?.map(Bytes),
+		})
+	}
+
+	/// The saved authority set, if there was one.
+	pub fn decode_authority_set(&self) -> sp_blockchain::Result<Option<AuthoritySet>> {
+		self.authority_set
+			.as_ref()
+			.map(|raw| decode_authority_set(&raw[..]))
+			.transpose()
+	}
+
+	/// Write the saved values back to `backend`, deleting the ones that were absent.
+	pub fn restore<B: AuxStore>(&self, backend: &B) -> sp_blockchain::Result<()> {
+		let entries = [
+			(AUTHORITY_SET_KEY, &self.authority_set),
+			(SET_STATE_KEY, &self.voter_set_state),
+			(VERSION_KEY, &self.schema_version),
+		];
+		let insert: Vec<(&[u8], &[u8])> = entries
+			.iter()
+			.filter_map(|(key, value)| value.as_ref().map(|value| (*key, &value[..])))
+			.collect();
+		let delete: Vec<&[u8]> = entries
+			.iter()
+			.filter(|(_, value)| value.is_none())
+			.map(|(key, _)| *key)
+			.collect();
+		backend.insert_aux(&insert, &delete)
+	}
+
+	/// Save the backup as JSON to `path`. Refuses to overwrite an existing file.
+	pub fn save(&self, path: &Path) -> sp_blockchain::Result<()> {
+		let file =
+			std::fs::OpenOptions::new()
+				.write(true)
+				.create_new(true)
+				.open(path)
+				.map_err(|e| {
+					sp_blockchain::Error::Backend(format!(
+						"Cannot create {}: {}",
+						path.display(),
+						e
+					))
+				})?;
+		serde_json::to_writer_pretty(file, self).map_err(|e| {
+			sp_blockchain::Error::Backend(format!("Cannot write {}: {}", path.display(), e))
+		})
+	}
+
+	/// Load a backup saved with [`AuxBackup::save`].
+	pub fn load(path: &Path) -> sp_blockchain::Result<Self> {
+		let file = std::fs::File::open(path).map_err(|e| {
+			sp_blockchain::Error::Backend(format!("Cannot open {}: {}", path.display(), e))
+		})?;
+		serde_json::from_reader(file).map_err(|e| {
+			sp_blockchain::Error::Backend(format!("Invalid backup in {}: {}", path.display(), e))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{collections::HashMap, sync::Mutex};
+
+	/// In-memory AuxStore for testing.
+	struct MockAuxStore {
+		data: Mutex<HashMap<Vec<u8>, Vec<u8>>>,
+	}
+
+	impl MockAuxStore {
+		fn new() -> Self {
+			Self { data: Mutex::new(HashMap::new()) }
+		}
+	}
+
+	impl AuxStore for MockAuxStore {
+		fn insert_aux<
+			'a,
+			'b: 'a,
+			'c: 'a,
+			I: IntoIterator<Item = &'a (&'c [u8], &'c [u8])>,
+			D: IntoIterator<Item = &'a &'b [u8]>,
+		>(
+			&self,
+			insert: I,
+			delete: D,
+		) -> sp_blockchain::Result<()> {
+			let mut data = self.data.lock().unwrap();
+			for del_key in delete {
+				let _ = data.remove(*del_key);
+			}
+			for (key, value) in insert {
+				let _ = data.insert(key.to_vec(), value.to_vec());
+			}
+			Ok(())
+		}
+
+		fn get_aux(&self, key: &[u8]) -> sp_blockchain::Result<Option<Vec<u8>>> {
+			Ok(self.data.lock().unwrap().get(key).cloned())
+		}
+	}
+
+	fn test_authorities() -> Vec<Authority> {
+		let addr = "5DQjEK2cWN2Qnp5sFdJQAoQ5RLaveyCxYpCbc8kWK2mbkrHi";
+		vec![(parse_authority(addr).unwrap(), 1)]
+	}
+
+	fn pending_change(number: BlockNumber, forced: bool) -> PendingChangeCompat<Hash, BlockNumber> {
+		PendingChangeCompat {
+			next_authorities: test_authorities(),
+			delay: 0,
+			canon_height: number,
+			canon_hash: Hash::repeat_byte(number as u8),
+			delay_kind: if forced {
+				DelayKindCompat::Best { median_last_finalized: number - 1 }
+			} else {
+				DelayKindCompat::Finalized
+			},
+		}
+	}
+
+	fn stuck_authority_set() -> AuthoritySet {
+		AuthoritySetCompat {
+			current_authorities: test_authorities(),
+			set_id: 987,
+			pending_standard_changes: ForkTreeCompat {
+				roots: vec![ForkTreeNodeCompat {
+					hash: Hash::repeat_byte(1),
+					number: 14_206_000,
+					data: pending_change(14_206_000, false),
+					children: Vec::new(),
+				}],
+				best_finalized_number: Some(14_205_952),
+			},
+			pending_forced_changes: vec![pending_change(14_206_100, true)],
+			authority_set_changes: vec![(100, 1000), (200, 2000), (987, 14_205_952)],
+		}
+	}
+
+	#[test]
+	fn authority_set_encode_decode_roundtrip() {
+		let original = AuthoritySetCompat::<Hash, u32> {
+			current_authorities: test_authorities(),
+			set_id: 991,
+			pending_standard_changes: ForkTreeCompat {
+				roots: Vec::new(),
+				best_finalized_number: None,
+			},
+			pending_forced_changes: Vec::new(),
+			authority_set_changes: vec![(987, 14_206_555), (988, 14_206_564)],
+		};
+
+		let encoded = original.encode();
+		let decoded = AuthoritySetCompat::<Hash, u32>::decode(&mut &encoded[..])
+			.expect("decode should succeed");
+
+		assert_eq!(decoded.set_id, 991);
+		assert_eq!(decoded.current_authorities.len(), 1);
+		assert_eq!(decoded.authority_set_changes.len(), 2);
+		assert!(decoded.pending_standard_changes.roots.is_empty());
+		assert!(decoded.pending_forced_changes.is_empty());
+	}
+
+	#[test]
+	fn rewrite_writes_correct_data() {
+		let store = MockAuxStore::new();
+		write_authority_set(&store, &stuck_authority_set(), false).unwrap();
+		store
+			.insert_aux(&[(&SET_STATE_KEY[..], b"fake_voter_state" as &[u8])], &[])
+			.unwrap();
+
+		let rewrite = AuthoritySetRewrite {
+			set_id: Some(991),
+			clear_pending_changes: true,
+			..Default::default()
+		};
+		let current = read_authority_set(&store).unwrap().expect("should exist");
+		write_authority_set(&store, &rewrite.apply(current), true).unwrap();
+
+		let decoded = read_authority_set(&store).unwrap().expect("should exist");
+		assert_eq!(decoded.set_id, 991);
+		assert_eq!(decoded.current_authorities, test_authorities());
+		assert!(decoded.pending_standard_changes.roots.is_empty());
+		assert!(decoded.pending_forced_changes.is_empty());
+		// Untouched fields are preserved, authority_set_changes are needed for warp sync.
+		assert_eq!(
+			decoded.authority_set_changes,
+			vec![(100, 1000), (200, 2000), (987, 14_205_952)]
+		);
+
+		// Verify VoterSetState was deleted
+		assert!(store.get_aux(SET_STATE_KEY).unwrap().is_none());
+
+		// Verify schema version
+		let ver_raw = store.get_aux(VERSION_KEY).unwrap().expect("should exist");
+		let ver = u32::decode(&mut &ver_raw[..]).unwrap();
+		assert_eq!(ver, CURRENT_VERSION);
+	}
+
+	#[test]
+	fn rewrite_can_keep_voter_state() {
+		let store = MockAuxStore::new();
+		store
+			.insert_aux(&[(&SET_STATE_KEY[..], b"fake_voter_state" as &[u8])], &[])
+			.unwrap();
+
+		write_authority_set(&store, &stuck_authority_set(), false).unwrap();
+
+		assert!(store.get_aux(SET_STATE_KEY).unwrap().is_some());
+	}
+
+	#[test]
+	fn rewrite_from_json() {
+		let rewrite: AuthoritySetRewrite = serde_json::from_str(
+			r#"{
+				"setId": 992,
+				"authorities": [["5DQjEK2cWN2Qnp5sFdJQAoQ5RLaveyCxYpCbc8kWK2mbkrHi", 1]],
+				"authoritySetChanges": [[991, 14206626]]
+			}"#,
+		)
+		.unwrap();
+		assert!(!rewrite.is_empty());
+		assert!(!rewrite.clear_pending_changes);
+
+		let set = rewrite.apply(stuck_authority_set());
+		assert_eq!(set.set_id, 992);
+		assert_eq!(set.current_authorities, test_authorities());
+		assert_eq!(set.authority_set_changes, vec![(991, 14_206_626)]);
+		assert_eq!(set.pending_forced_changes.len(), 1);
+
+		assert!(serde_json::from_str::<AuthoritySetRewrite>(r#"{"set_id": 1}"#).is_err());
+		assert!(AuthoritySetRewrite::default().is_empty());
+	}
+
+	#[test]
+	fn backup_and_restore_roundtrip() {
+		let store = MockAuxStore::new();
+		write_authority_set(&store, &stuck_authority_set(), false).unwrap();
+		let backup = AuxBackup::read(&store).unwrap();
+		assert!(backup.voter_set_state.is_none());
+
+		let json = serde_json::to_string(&backup).unwrap();
+		let backup: AuxBackup = serde_json::from_str(&json).unwrap();
+
+		// Rewrite and create a voter state, then restore the original values.
+		let rewrite = AuthoritySetRewrite { set_id: Some(991), ..Default::default() };
+		write_authority_set(&store, &rewrite.apply(stuck_authority_set()), true).unwrap();
+		store
+			.insert_aux(&[(&SET_STATE_KEY[..], b"fake_voter_state" as &[u8])], &[])
+			.unwrap();
+		assert_eq!(backup.decode_authority_set().unwrap().unwrap().set_id, 987);
+		backup.restore(&store).unwrap();
+
+		assert_eq!(read_authority_set(&store).unwrap().unwrap().set_id, 987);
+		assert!(store.get_aux(SET_STATE_KEY).unwrap().is_none());
+		assert_eq!(AuxBackup::read(&store).unwrap(), backup);
+	}
+
+	#[test]
+	fn display_lists_pending_changes() {
+		let shown = stuck_authority_set().to_string();
+		assert!(shown.contains("set_id: 987"));
+		assert!(shown.contains("5DQjEK2cWN2Qnp5sFdJQAoQ5RLaveyCxYpCbc8kWK2mbkrHi"));
+		assert!(shown.contains("at #14206000"));
+		assert!(shown.contains("forced with median #14206099"));
+		assert!(shown.contains("set 987 finalized at #14205952"));
+	}
+}
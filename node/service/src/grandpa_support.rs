@@ -23,7 +23,14 @@ use sp_runtime::traits::{Block as BlockT, Header as _, NumberFor};
 use crate::HeaderProvider;
 
 #[cfg(feature = "full-node")]
-use polkadot_primitives::{Block, Hash};
+use {
+	crate::{
+		chain_spec::{self, GrandpaHardFork},
+		Error, IdentifyVariant,
+	},
+	polkadot_primitives::{Block, Hash},
+	std::collections::HashSet,
+};
 
 /// Returns the block hash of the block at the given `target_number` by walking
 /// backwards from the given `current_header`.
@@ -215,84 +222,75 @@ pub(crate) fn kusama_hard_forks() -> Vec<grandpa::AuthoritySetHardFork<Block>> {
 		.collect()
 }
 
-/// Post-incident GRANDPA authority set for THX Network mainnet.
-/// 10 validators, verified from both archive-001 and archive-002 at block #14,210,000.
+/// GRANDPA hard forks of the chain of `chain_spec`.
+///
+/// The forks of the `grandpaHardForks` chain spec extension are added to the built-in forks of
+/// Kusama. A THX Network mainnet chain spec without the extension, for example one exported
+/// before it existed, falls back to the built-in incident forks, without which the node cannot
+/// follow finality past block #14,206,626.
 #[cfg(feature = "full-node")]
-pub(crate) fn thxnet_post_incident_authorities() -> Vec<(grandpa_primitives::AuthorityId, u64)> {
-	use sp_core::crypto::Ss58Codec;
-
-	let addresses: Vec<&str> = vec![
-		"5DQjEK2cWN2Qnp5sFdJQAoQ5RLaveyCxYpCbc8kWK2mbkrHi",
-		"5CLCUaSjUhmukZEsp9bTgWi6gBDCMEVLXebN79U46q68Qzh1",
-		"5FMYd9YVje234kxfCwZ5UmWoEQ6Zjz78GjjN3hQLM7SH3wDi",
-		"5CNfCS5SZ6zEu9YtW1HKeyBxWibrwedgd6by4y9W1D2R1NbA",
-		"5CKRFQnViKUtpyEmETsG2TxmzbWHDpGt9n9r1NWEVh9CU4RY",
-		"5FW1LVeZKtrJB8RE3uWSEVsXSyFEkJA6PF5oEeKAnwi8cUMq",
-		"5Dn9oyDjpcm6yp3bNRnsHEDgzxnkRgqvinChpt3WfZScjt48",
-		"5FWBTpBSv4vCR4SC5Q5XT4zGvXF3cAT7AHfe7i45yRdUxwAL",
-		"5Fv7rAvMJaKGEWJr1DNxhn5AeaiPot2TuHNvsCzAk9LyPDLR",
-		"5ECrXnTf7R7W5wF8bv4xJiJYYyQUgnZfGy6uce4t36puANrT",
-	];
+pub(crate) fn grandpa_hard_forks(
+	chain_spec: &Box<dyn sc_chain_spec::ChainSpec>,
+) -> Result<Vec<grandpa::AuthoritySetHardFork<Block>>, Error> {
+	let mut forks = if chain_spec.is_kusama() { kusama_hard_forks() } else { Vec::new() };
+
+	match chain_spec::Extensions::try_get(&**chain_spec)
+		.and_then(|extensions| extensions.grandpa_hard_forks.as_ref())
+	{
+		Some(spec_forks) => forks.extend(chain_spec_hard_forks(spec_forks)?),
+		None if chain_spec.is_thxnet_mainnet() =>
+			forks.extend(chain_spec_hard_forks(&chain_spec::thxnet_mainnet_grandpa_hard_forks())?),
+		None => {},
+	}
 
-	addresses
-		.into_iter()
-		.map(|address| {
-			(
-				grandpa_primitives::AuthorityId::from_ss58check(address)
-					.expect("hard fork authority addresses are static and they should be carefully defined; qed."),
-				1,
-			)
-		})
-		.collect()
+	Ok(forks)
 }
 
-/// GRANDPA hard forks for THX Network mainnet.
+/// GRANDPA hard forks listed in the `grandpaHardForks` chain spec extension.
 ///
-/// During the finality deadlock incident (2026-02-20), manual `setStorage`
-/// interventions and forced authority changes created chaotic GRANDPA state
-/// at blocks 14,205,952 through 14,206,626. Only 3 real ForcedChange consensus
-/// logs exist in block headers (at 14,206,555, 14,206,564, 14,206,591).
-/// The migration at block 14,206,625 incremented runtime set_id but did NOT
-/// emit a ForcedChange log, causing a client/runtime set_id divergence.
-///
-/// These 4 hard fork entries use the GRANDPA CLIENT's internal set_id values
-/// (987→988→989→990), each incrementing by exactly 1. After all 4, the client
-/// reaches set_id=991, matching the healthy archive nodes on the network.
-///
-/// This is NOT a chain fork — block hashes and the canonical chain are unchanged.
-/// Only the GRANDPA finality gadget's authority tracking is overridden.
+/// Returns an error for entries that could never be applied correctly, so that a broken chain
+/// spec is noticed at startup and not when the fork block is imported.
 #[cfg(feature = "full-node")]
-pub(crate) fn thxnet_hard_forks() -> Vec<grandpa::AuthoritySetHardFork<Block>> {
-	use std::str::FromStr;
-
-	// (client_set_id, block_hash, block_number)
-	// client_set_id = the GRANDPA client's internal set_id WHEN that block is reached.
-	// After processing all 4 entries: 987 + 4 = 991 (matches archive nodes).
-	let forks: Vec<(u64, &str, u32)> = vec![
-		// Override 1st real ForcedChange log (chaotic incident)
-		(987, "9cd4f37aed551dbb8fc422dea295d832b5efffc0230007c86714cac444bd5cff", 14_206_555),
-		// Override 2nd real ForcedChange log
-		(988, "b24efda871e72649a6512d418e75b5e5e5921307ee04564042f3bd1cdd721d04", 14_206_564),
-		// Override 3rd real ForcedChange log
-		(989, "323b1605b3030e79bae563f64e5c7f5cad9147632a0230764744d6f04e190b9f", 14_206_591),
-		// ADD missing ForcedChange (migration at 14,206,625 failed to emit log)
-		(990, "9db27f4ec24dc50ca5c314a76f55384748ee6d0a1af3f719ec07166238a8200c", 14_206_626),
-	];
-
-	let authorities = thxnet_post_incident_authorities();
+pub(crate) fn chain_spec_hard_forks(
+	forks: &[GrandpaHardFork],
+) -> Result<Vec<grandpa::AuthoritySetHardFork<Block>>, Error> {
+	let mut hashes = HashSet::new();
 
 	forks
-		.into_iter()
-		.map(|(set_id, hash, number)| {
-			let hash = Hash::from_str(hash)
-				.expect("hard fork hashes are static and they should be carefully defined; qed.");
-
-			grandpa::AuthoritySetHardFork {
-				set_id,
-				block: (hash, number),
-				authorities: authorities.clone(),
-				last_finalized: Some(14_205_952),
+		.iter()
+		.map(|fork| {
+			let invalid = |reason| Error::InvalidGrandpaHardFork {
+				set_id: fork.set_id,
+				number: fork.number,
+				reason,
+			};
+
+			if fork.authorities.is_empty() {
+				return Err(invalid("the authority set is empty"))
+			}
+			if fork.authorities.iter().any(|(_, weight)| *weight == 0) {
+				return Err(invalid("an authority has zero weight"))
+			}
+			let mut authorities = HashSet::new();
+			if !fork.authorities.iter().all(|(id, _)| authorities.insert(id)) {
+				return Err(invalid("an authority is listed twice"))
+			}
+			if fork
+				.last_finalized
+				.map_or(false, |last_finalized| last_finalized >= fork.number)
+			{
+				return Err(invalid("`lastFinalized` must be below the hard fork block"))
+			}
+			if !hashes.insert(fork.hash) {
+				return Err(invalid("another hard fork uses the same block hash"))
 			}
+
+			Ok(grandpa::AuthoritySetHardFork {
+				set_id: fork.set_id,
+				block: (fork.hash, fork.number),
+				authorities: fork.authorities.clone(),
+				last_finalized: fork.last_finalized,
+			})
 		})
 		.collect()
 }
@@ -422,4 +420,57 @@ mod tests {
 			None,
 		);
 	}
+
+	#[cfg(feature = "full-node")]
+	#[test]
+	fn chain_spec_hard_forks_are_validated() {
+		use super::chain_spec_hard_forks;
+		use crate::{chain_spec::GrandpaHardFork, Error};
+		use polkadot_primitives::Hash;
+
+		let authority = |byte| {
+			grandpa_primitives::AuthorityId::from(sp_core::ed25519::Public::from_raw([byte; 32]))
+		};
+		let fork: GrandpaHardFork = serde_json::from_value(serde_json::json!({
+			"setId": 5,
+			"hash": Hash::repeat_byte(1),
+			"number": 100,
+			"authorities": [[authority(1), 1], [authority(2), 1]],
+		}))
+		.unwrap();
+		assert_eq!(fork.last_finalized, None);
+
+		let forks = chain_spec_hard_forks(&[fork.clone()]).unwrap();
+		assert_eq!(forks.len(), 1);
+		assert_eq!(forks[0].set_id, 5);
+		assert_eq!(forks[0].block, (Hash::repeat_byte(1), 100));
+		assert_eq!(forks[0].authorities, fork.authorities);
+
+		let assert_invalid = |forks: &[GrandpaHardFork]| {
+			assert!(matches!(
+				chain_spec_hard_forks(forks),
+				Err(Error::InvalidGrandpaHardFork { set_id: 5, number: 100, .. })
+			));
+		};
+
+		assert_invalid(&[GrandpaHardFork { authorities: Vec::new(), ..fork.clone() }]);
+		assert_invalid(&[GrandpaHardFork { authorities: vec![(authority(1), 0)], ..fork.clone() }]);
+		assert_invalid(&[GrandpaHardFork {
+			authorities: vec![(authority(1), 1), (authority(1), 1)],
+			..fork.clone()
+		}]);
+		assert_invalid(&[GrandpaHardFork { last_finalized: Some(100), ..fork.clone() }]);
+		assert_invalid(&[fork.clone(), fork]);
+	}
+
+	#[cfg(feature = "full-node")]
+	#[test]
+	fn thxnet_mainnet_fallback_hard_forks_are_valid() {
+		let forks =
+			super::chain_spec_hard_forks(&crate::chain_spec::thxnet_mainnet_grandpa_hard_forks())
+				.unwrap();
+		let set_ids: Vec<_> = forks.iter().map(|fork| fork.set_id).collect();
+		assert_eq!(set_ids, vec![987, 988, 989, 990]);
+		assert_eq!(forks.last().unwrap().block.1, 14_206_626);
+	}
 }
@@ -19,11 +19,21 @@
 #![deny(unused_results)]
 
 pub mod chain_spec;
+#[cfg(all(feature = "full-node", feature = "polkadot-native"))]
+mod dao_rpc;
+#[cfg(all(feature = "full-node", feature = "polkadot-native"))]
+mod finality_attestation;
+#[cfg(feature = "full-node")]
+mod finality_watchdog;
+#[cfg(feature = "full-node")]
+mod grandpa_metrics;
+#[cfg(feature = "full-node")]
+pub mod grandpa_recovery;
 mod grandpa_support;
 mod parachains_db;
 mod relay_chain_selection;
 #[cfg(feature = "full-node")]
-mod thxnet_grandpa_recovery;
+mod validator_health;
 
 #[cfg(feature = "full-node")]
 pub mod overseer;
@@ -56,10 +66,12 @@ use {
 	sp_trie::PrefixedMemoryDB,
 };
 
-use polkadot_node_subsystem_util::database::Database;
+pub use polkadot_node_subsystem_util::database::Database;
 
 #[cfg(feature = "full-node")]
 pub use {
+	finality_watchdog::FinalityWatchdogConfig,
+	parachains_db::integrity as parachains_db_integrity,
 	polkadot_overseer::{Handle, Overseer, OverseerConnector, OverseerHandle},
 	polkadot_primitives::runtime_api::ParachainHost,
 	relay_chain_selection::SelectRelayChain,
@@ -67,6 +79,7 @@ pub use {
 	sp_authority_discovery::AuthorityDiscoveryApi,
 	sp_blockchain::{HeaderBackend, HeaderMetadata},
 	sp_consensus_babe::BabeApi,
+	validator_health::ValidatorHealthConfig,
 };
 
 #[cfg(feature = "full-node")]
@@ -237,6 +250,10 @@ pub enum Error {
 	#[cfg(feature = "full-node")]
 	#[error("Expected at least one of polkadot, kusama, westend or rococo runtime feature")]
 	NoRuntime,
+
+	#[cfg(feature = "full-node")]
+	#[error("Invalid GRANDPA hard fork to set {set_id} at #{number} in the chain spec: {reason}")]
+	InvalidGrandpaHardFork { set_id: u64, number: BlockNumber, reason: &'static str },
 }
 
 /// Can be called for a `Configuration` to identify which network the configuration targets.
@@ -262,6 +279,9 @@ pub trait IdentifyVariant {
 	/// Returns if this is a configuration for the `THX Network` mainnet.
 	fn is_thxnet_mainnet(&self) -> bool;
 
+	/// Returns if this is a configuration for the `THX Network` testnet.
+	fn is_thxnet_testnet(&self) -> bool;
+
 	/// Returns true if this configuration is for a development network.
 	fn is_dev(&self) -> bool;
 }
@@ -288,6 +308,9 @@ impl IdentifyVariant for Box<dyn ChainSpec> {
 	fn is_thxnet_mainnet(&self) -> bool {
 		self.id().starts_with("thxnet_mainnet")
 	}
+	fn is_thxnet_testnet(&self) -> bool {
+		self.id().starts_with("thxnet_testnet")
+	}
 	fn is_dev(&self) -> bool {
 		self.id().ends_with("dev")
 	}
@@ -502,40 +525,11 @@ where
 		client.clone(),
 	);
 
-	// THX Network stuck-node recovery: detect nodes that already imported blocks through
-	// the chaotic area but have GRANDPA finality stuck. Reset aux storage before GRANDPA init.
-	if config.chain_spec.is_thxnet_mainnet() {
-		let info = client.info();
-		if info.finalized_number > 0 &&
-			info.finalized_number <= 14_206_626 &&
-			info.best_number > 14_210_000
-		{
-			thxnet_grandpa_recovery::reset_grandpa_state(
-				&*backend,
-				991, // target set_id matching healthy archive nodes
-				&grandpa_support::thxnet_post_incident_authorities(),
-				info.finalized_number,
-				info.finalized_hash,
-			)?;
-			log::warn!(
-				"🔧 THX Network: Reset GRANDPA state for stuck node \
-				 (finalized=#{}, best=#{})",
-				info.finalized_number,
-				info.best_number,
-			);
-		}
+	let grandpa_hard_forks = grandpa_support::grandpa_hard_forks(&config.chain_spec)?;
+	if !grandpa_hard_forks.is_empty() {
+		info!("Loaded {} GRANDPA hard forks", grandpa_hard_forks.len());
 	}
 
-	let grandpa_hard_forks = if config.chain_spec.is_kusama() {
-		grandpa_support::kusama_hard_forks()
-	} else if config.chain_spec.is_thxnet_mainnet() {
-		let forks = grandpa_support::thxnet_hard_forks();
-		log::info!("🔧 THX Network: Loading {} GRANDPA hard fork entries", forks.len(),);
-		forks
-	} else {
-		Vec::new()
-	};
-
 	let (grandpa_block_import, grandpa_link) = grandpa::block_import_with_authority_set_hard_forks(
 		client.clone(),
 		&(client.clone() as Arc<_>),
@@ -729,6 +723,9 @@ pub fn new_full<RuntimeApi, ExecutorDispatch, OverseerGenerator>(
 	overseer_message_channel_capacity_override: Option<usize>,
 	_malus_finality_delay: Option<u32>,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	validator_health: ValidatorHealthConfig,
+	finality_watchdog: FinalityWatchdogConfig,
+	longest_chain_fallback: bool,
 ) -> Result<NewFull<Arc<FullClient<RuntimeApi, ExecutorDispatch>>>, Error>
 where
 	RuntimeApi: ConstructRuntimeApi<Block, FullClient<RuntimeApi, ExecutorDispatch>>
@@ -745,6 +742,8 @@ where
 
 	let is_offchain_indexing_enabled = config.offchain_worker.indexing_enabled;
 	let role = config.role.clone();
+	#[cfg(feature = "polkadot-native")]
+	let finality_attestation = finality_attestation::builder_for(&config.chain_spec);
 	let force_authoring = config.force_authoring;
 	let backoff_authoring_blocks = {
 		let mut backoff = sc_consensus_slots::BackoffAuthoringOnFinalizedHeadLagging::default();
@@ -797,13 +796,20 @@ where
 		let metrics =
 			polkadot_node_subsystem_util::metrics::Metrics::register(prometheus_registry.as_ref())?;
 
-		if config.chain_spec.is_thxnet_mainnet() {
-			// HOTFIX (thxnet-only): Use LongestChain to bypass broken chain-selection/
-			// approval-voting/dispute-coordinator subsystem state after parachain DB cleanup.
-			// Safe because no active parachain candidates require approval voting.
-			// TODO: Revert to new_with_overseer once finalization catches up.
-			let _ = metrics;
-			SelectRelayChain::new_longest_chain(basics.backend.clone())
+		// Mainnet validators selected the longest chain until the hybrid mode existed, so they
+		// keep falling back to it without `--chain-selection-fallback`.
+		// TODO: Drop the default once the parachains DB of the mainnet validators was checked.
+		if longest_chain_fallback || config.chain_spec.is_thxnet_mainnet() {
+			log::warn!(
+				"⚠️  Chain selection falls back to the longest chain while the chain selection, \
+				approval voting or dispute coordinator subsystems fail"
+			);
+			SelectRelayChain::new_hybrid(
+				basics.backend.clone(),
+				overseer_handle.clone(),
+				metrics,
+				relay_chain_selection::LONGEST_CHAIN_FALLBACK_THRESHOLD,
+			)
 		} else {
 			SelectRelayChain::new_with_overseer(
 				basics.backend.clone(),
@@ -891,18 +897,7 @@ where
 	let (dispute_req_receiver, cfg) = IncomingRequest::get_config_receiver(&req_protocol_names);
 	config.network.request_response_protocols.push(cfg);
 
-	let grandpa_hard_forks = if config.chain_spec.is_kusama() {
-		grandpa_support::kusama_hard_forks()
-	} else if config.chain_spec.is_thxnet_mainnet() {
-		let forks = grandpa_support::thxnet_hard_forks();
-		log::info!(
-			"🔧 THX Network: Loading {} GRANDPA hard fork entries for warp sync provider",
-			forks.len(),
-		);
-		forks
-	} else {
-		Vec::new()
-	};
+	let grandpa_hard_forks = grandpa_support::grandpa_hard_forks(&config.chain_spec)?;
 
 	if config.chain_spec.is_thxnet_mainnet() {
 		let shared = import_setup.1.shared_authority_set();
@@ -981,45 +976,6 @@ where
 		col_session_data: parachains_db::REAL_COLUMNS.col_session_window_data,
 	};
 
-	let rpc_handlers = service::spawn_tasks(service::SpawnTasksParams {
-		config,
-		backend: backend.clone(),
-		client: client.clone(),
-		keystore: keystore_container.sync_keystore(),
-		network: network.clone(),
-		sync_service: sync_service.clone(),
-		rpc_builder: Box::new(rpc_extensions_builder),
-		transaction_pool: transaction_pool.clone(),
-		task_manager: &mut task_manager,
-		system_rpc_tx,
-		tx_handler_controller,
-		telemetry: telemetry.as_mut(),
-	})?;
-
-	if let Some(hwbench) = hwbench {
-		sc_sysinfo::print_hwbench(&hwbench);
-		if !SUBSTRATE_REFERENCE_HARDWARE.check_hardware(&hwbench) && role.is_authority() {
-			log::warn!(
-				"⚠️  The hardware does not meet the minimal requirements for role 'Authority' find out more at:\n\
-				https://wiki.polkadot.network/docs/maintain-guides-how-to-validate-polkadot#reference-hardware"
-			);
-		}
-
-		if let Some(ref mut telemetry) = telemetry {
-			let telemetry_handle = telemetry.handle();
-			task_manager.spawn_handle().spawn(
-				"telemetry_hwbench",
-				None,
-				sc_sysinfo::initialize_hwbench_telemetry(telemetry_handle, hwbench),
-			);
-		}
-	}
-
-	let (block_import, link_half, babe_link, beefy_links) = import_setup;
-
-	let overseer_client = client.clone();
-	let spawner = task_manager.spawn_handle();
-
 	let authority_discovery_service = if auth_or_collator || overseer_enable_anyways {
 		use futures::StreamExt;
 		use sc_network::{Event, NetworkEventStream};
@@ -1061,6 +1017,99 @@ where
 		None
 	};
 
+	let (validator_health, grandpa_activity_tracker) =
+		validator_health::ValidatorHealthChecker::new(
+			validator_health,
+			client.clone(),
+			sync_service.clone(),
+			authority_discovery_service.clone(),
+			keystore_container.sync_keystore(),
+			import_setup.1.shared_authority_set().clone(),
+			shared_voter_state.clone(),
+		);
+	task_manager
+		.spawn_handle()
+		.spawn("validator-health-grandpa", None, grandpa_activity_tracker);
+	let validator_health =
+		Arc::new(polkadot_rpc::health::CachedValidatorHealth::new(validator_health));
+	// Substrate's Prometheus endpoint only serves `/metrics`, so on validators it moves to an
+	// ephemeral loopback port and the configured one also serves the validator health.
+	let health_endpoint = match config.prometheus_config.as_mut() {
+		Some(prometheus) if role.is_authority() => {
+			let address = prometheus.port;
+			prometheus.port = std::net::SocketAddr::from(([127, 0, 0, 1], 0));
+			Some((address, prometheus.registry.clone(), validator_health.clone()))
+		},
+		_ => None,
+	};
+	// Only the thxnet runtimes have the DAO pallet.
+	#[cfg(feature = "polkadot-native")]
+	let dao_client =
+		(chain_spec.is_thxnet_mainnet() || chain_spec.is_thxnet_testnet()).then(|| client.clone());
+	let rpc_builder = move |deny_unsafe: polkadot_rpc::DenyUnsafe,
+	                        subscription_executor: polkadot_rpc::SubscriptionTaskExecutor|
+	      -> Result<polkadot_rpc::RpcExtension, service::Error> {
+		use polkadot_rpc::health::{ValidatorHealth, ValidatorHealthApiServer};
+
+		let mut io = rpc_extensions_builder(deny_unsafe, subscription_executor)?;
+		io.merge(ValidatorHealth::new(validator_health.clone(), deny_unsafe).into_rpc())
+			.map_err(|e| service::Error::Application(e.into()))?;
+		#[cfg(feature = "polkadot-native")]
+		if let Some(client) = &dao_client {
+			dao_rpc::extend_rpc(&mut io, client.clone())?;
+		}
+		Ok(io)
+	};
+
+	let rpc_handlers = service::spawn_tasks(service::SpawnTasksParams {
+		config,
+		backend: backend.clone(),
+		client: client.clone(),
+		keystore: keystore_container.sync_keystore(),
+		network: network.clone(),
+		sync_service: sync_service.clone(),
+		rpc_builder: Box::new(rpc_builder),
+		transaction_pool: transaction_pool.clone(),
+		task_manager: &mut task_manager,
+		system_rpc_tx,
+		tx_handler_controller,
+		telemetry: telemetry.as_mut(),
+	})?;
+
+	if let Some((address, registry, provider)) = health_endpoint {
+		task_manager
+			.spawn_handle()
+			.spawn("validator-health-endpoint", None, async move {
+				if let Err(err) = validator_health::serve(address, registry, provider).await {
+					log::error!("Cannot serve the validator health at {}: {}", address, err);
+				}
+			});
+	}
+
+	if let Some(hwbench) = hwbench {
+		sc_sysinfo::print_hwbench(&hwbench);
+		if !SUBSTRATE_REFERENCE_HARDWARE.check_hardware(&hwbench) && role.is_authority() {
+			log::warn!(
+				"⚠️  The hardware does not meet the minimal requirements for role 'Authority' find out more at:\n\
+				https://wiki.polkadot.network/docs/maintain-guides-how-to-validate-polkadot#reference-hardware"
+			);
+		}
+
+		if let Some(ref mut telemetry) = telemetry {
+			let telemetry_handle = telemetry.handle();
+			task_manager.spawn_handle().spawn(
+				"telemetry_hwbench",
+				None,
+				sc_sysinfo::initialize_hwbench_telemetry(telemetry_handle, hwbench),
+			);
+		}
+	}
+
+	let (block_import, link_half, babe_link, beefy_links) = import_setup;
+
+	let overseer_client = client.clone();
+	let spawner = task_manager.spawn_handle();
+
 	if local_keystore.is_none() {
 		gum::info!("Cannot run as validator without local keystore.");
 	}
@@ -1138,6 +1187,46 @@ where
 		None
 	};
 
+	let finality_watchdog = finality_watchdog::FinalityWatchdog::new(
+		finality_watchdog,
+		backend.clone(),
+		sync_service.clone(),
+		overseer_handle.clone(),
+		shared_voter_state.clone(),
+		link_half.shared_authority_set().clone(),
+		telemetry.as_ref().map(|x| x.handle()),
+		prometheus_registry.as_ref(),
+	)?;
+	task_manager
+		.spawn_handle()
+		.spawn("finality-watchdog", None, finality_watchdog.run());
+
+	#[cfg(feature = "polkadot-native")]
+	if let Some(build) = finality_attestation.filter(|_| role.is_authority()) {
+		task_manager.spawn_handle().spawn(
+			"finality-attestation",
+			None,
+			finality_attestation::run(
+				client.clone(),
+				transaction_pool.clone(),
+				keystore_container.sync_keystore(),
+				build,
+			),
+		);
+	}
+
+	if let Some(registry) = prometheus_registry.as_ref() {
+		let grandpa_metrics = grandpa_metrics::run(
+			backend.clone(),
+			shared_voter_state.clone(),
+			link_half.shared_authority_set().clone(),
+			registry,
+		)?;
+		task_manager
+			.spawn_handle()
+			.spawn("grandpa-round-metrics", None, grandpa_metrics);
+	}
+
 	if role.is_authority() {
 		let proposer = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
@@ -1424,6 +1513,9 @@ pub fn build_full(
 	overseer_message_channel_override: Option<usize>,
 	malus_finality_delay: Option<u32>,
 	hwbench: Option<sc_sysinfo::HwBench>,
+	validator_health: ValidatorHealthConfig,
+	finality_watchdog: FinalityWatchdogConfig,
+	longest_chain_fallback: bool,
 ) -> Result<NewFull<Client>, Error> {
 	#[cfg(feature = "rococo-native")]
 	if config.chain_spec.is_rococo() ||
@@ -1443,6 +1535,9 @@ pub fn build_full(
 			overseer_message_channel_override,
 			malus_finality_delay,
 			hwbench,
+			validator_health,
+			finality_watchdog,
+			longest_chain_fallback,
 		)
 		.map(|full| full.with_client(Client::Rococo))
 	}
@@ -1462,6 +1557,9 @@ pub fn build_full(
 			overseer_message_channel_override,
 			malus_finality_delay,
 			hwbench,
+			validator_health,
+			finality_watchdog,
+			longest_chain_fallback,
 		)
 		.map(|full| full.with_client(Client::Kusama))
 	}
@@ -1481,6 +1579,9 @@ pub fn build_full(
 			overseer_message_channel_override,
 			malus_finality_delay,
 			hwbench,
+			validator_health,
+			finality_watchdog,
+			longest_chain_fallback,
 		)
 		.map(|full| full.with_client(Client::Westend))
 	}
@@ -1503,6 +1604,9 @@ pub fn build_full(
 			}),
 			malus_finality_delay,
 			hwbench,
+			validator_health,
+			finality_watchdog,
+			longest_chain_fallback,
 		)
 		.map(|full| full.with_client(Client::Polkadot))
 	}
@@ -1520,6 +1624,9 @@ pub fn build_full(
 		let _ = overseer_message_channel_override;
 		let _ = malus_finality_delay;
 		let _ = hwbench;
+		let _ = validator_health;
+		let _ = finality_watchdog;
+		let _ = longest_chain_fallback;
 
 		Err(Error::NoRuntime)
 	}
@@ -0,0 +1,398 @@
+// Copyright 2026 THX Network Contributors.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Consistency checks and repairs of the parachains DB against the block backend.
+//!
+//! The chain selection and approval voting subsystems keep an entry for every unfinalized
+//! block. If entries go missing, e.g. after a partially lost parachains DB, chain selection
+//! no longer sees viable leaves and finality stalls. These functions find such gaps, rebuild
+//! chain selection entries from the imported headers and wipe single subsystem columns.
+//!
+//! The node must not be running while they are used.
+
+use super::{other_io_error, REAL_COLUMNS};
+use polkadot_node_core_approval_voting as approval_voting_subsystem;
+use polkadot_node_core_chain_selection as chain_selection_subsystem;
+use polkadot_node_core_dispute_coordinator as dispute_coordinator_subsystem;
+use polkadot_node_subsystem_util::database::{DBTransaction, Database};
+use polkadot_primitives::{Block, BlockNumber, Hash, Header, SessionIndex};
+use sc_client_api::{blockchain::Backend as _, Backend};
+use sp_blockchain::HeaderBackend;
+use std::{collections::HashSet, fmt, io, sync::Arc};
+
+/// A subsystem column of the parachains DB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubsystemColumn {
+	/// The chain selection data.
+	ChainSelection,
+	/// The approval voting data.
+	ApprovalVoting,
+	/// The dispute coordinator data. Wiping it forgets all dispute votes.
+	DisputeCoordinator,
+}
+
+impl SubsystemColumn {
+	fn column(self) -> u32 {
+		match self {
+			SubsystemColumn::ChainSelection => REAL_COLUMNS.col_chain_selection_data,
+			SubsystemColumn::ApprovalVoting => REAL_COLUMNS.col_approval_data,
+			SubsystemColumn::DisputeCoordinator => REAL_COLUMNS.col_dispute_coordinator_data,
+		}
+	}
+}
+
+/// The result of [`check`].
+#[derive(Debug, Clone)]
+pub struct IntegrityReport {
+	/// The last finalized block number.
+	pub finalized_number: BlockNumber,
+	/// The number of unfinalized blocks in the block backend.
+	pub unfinalized_blocks: usize,
+	/// Unfinalized blocks without a chain selection entry, ascending.
+	pub missing_chain_selection: Vec<(BlockNumber, Hash)>,
+	/// Unfinalized blocks without an approval voting entry, ascending.
+	pub missing_approval_voting: Vec<(BlockNumber, Hash)>,
+	/// The earliest session kept by the dispute coordinator.
+	pub earliest_dispute_session: Option<SessionIndex>,
+	/// The number of recent disputes kept by the dispute coordinator.
+	pub recent_disputes: usize,
+}
+
+impl IntegrityReport {
+	/// Whether chain selection has an entry for every unfinalized block.
+	///
+	/// Gaps in the approval voting data are expected after a major sync and do not count.
+	pub fn is_consistent(&self) -> bool {
+		self.missing_chain_selection.is_empty()
+	}
+}
+
+impl fmt::Display for IntegrityReport {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fn fmt_gaps(
+			f: &mut fmt::Formatter,
+			name: &str,
+			missing: &[(BlockNumber, Hash)],
+		) -> fmt::Result {
+			writeln!(f, "{}: {} unfinalized blocks missing", name, missing.len())?;
+			let mut ranges: Vec<(BlockNumber, BlockNumber)> = Vec::new();
+			for (number, _) in missing {
+				match ranges.last_mut() {
+					Some((_, end)) if *number <= *end + 1 => *end = *number,
+					_ => ranges.push((*number, *number)),
+				}
+			}
+			for (start, end) in ranges {
+				if start == end {
+					writeln!(f, "  - #{}", start)?;
+				} else {
+					writeln!(f, "  - #{}..=#{}", start, end)?;
+				}
+			}
+			Ok(())
+		}
+
+		writeln!(
+			f,
+			"finalized #{}, {} unfinalized blocks",
+			self.finalized_number, self.unfinalized_blocks
+		)?;
+		fmt_gaps(f, "chain selection", &self.missing_chain_selection)?;
+		fmt_gaps(f, "approval voting", &self.missing_approval_voting)?;
+		match self.earliest_dispute_session {
+			Some(session) => writeln!(
+				f,
+				"dispute coordinator: {} recent disputes, earliest session {}",
+				self.recent_disputes, session
+			),
+			None => writeln!(f, "dispute coordinator: empty"),
+		}
+	}
+}
+
+fn backend_error(err: impl ToString) -> sp_blockchain::Error {
+	sp_blockchain::Error::Backend(err.to_string())
+}
+
+/// All unfinalized headers of the block backend, ascending by number.
+fn unfinalized_headers<B: Backend<Block>>(backend: &B) -> sp_blockchain::Result<Vec<Header>> {
+	let blockchain = backend.blockchain();
+	let finalized_number = blockchain.info().finalized_number;
+
+	let mut seen = HashSet::new();
+	let mut headers = Vec::new();
+	for leaf in blockchain.leaves()? {
+		let mut hash = leaf;
+		while seen.insert(hash) {
+			let header = blockchain.expect_header(hash)?;
+			if header.number <= finalized_number {
+				break
+			}
+			hash = header.parent_hash;
+			headers.push(header);
+		}
+	}
+	headers.sort_by_key(|header| header.number);
+
+	Ok(headers)
+}
+
+fn chain_selection(db: Arc<dyn Database>) -> chain_selection_subsystem::ChainSelectionSubsystem {
+	let config = chain_selection_subsystem::Config {
+		col_data: REAL_COLUMNS.col_chain_selection_data,
+		stagnant_check_interval: chain_selection_subsystem::StagnantCheckInterval::never(),
+		stagnant_check_mode: chain_selection_subsystem::StagnantCheckMode::PruneOnly,
+	};
+
+	chain_selection_subsystem::ChainSelectionSubsystem::new(config, db)
+}
+
+fn approval_voting(db: Arc<dyn Database>) -> approval_voting_subsystem::ApprovalVotingSubsystem {
+	approval_voting_subsystem::ApprovalVotingSubsystem::with_config(
+		approval_voting_subsystem::Config {
+			col_approval_data: REAL_COLUMNS.col_approval_data,
+			col_session_data: REAL_COLUMNS.col_session_window_data,
+			slot_duration_millis: Default::default(),
+		},
+		db,
+		Arc::new(sc_keystore::LocalKeystore::in_memory()),
+		Box::new(consensus_common::NoNetwork),
+		approval_voting_subsystem::Metrics::default(),
+	)
+}
+
+/// Cross-check the chain selection, approval voting and dispute coordinator data against the
+/// unfinalized blocks of the block backend.
+///
+/// Approval voting does not import blocks while the node is doing a major sync, so gaps in its
+/// data are expected right after syncing and are only reported.
+pub fn check<B: Backend<Block>>(
+	backend: &B,
+	db: Arc<dyn Database>,
+) -> sp_blockchain::Result<IntegrityReport> {
+	let headers = unfinalized_headers(backend)?;
+	let missing = |missing: Vec<Hash>| {
+		let missing: HashSet<_> = missing.into_iter().collect();
+		headers
+			.iter()
+			.filter(|header| missing.contains(&header.hash()))
+			.map(|header| (header.number, header.hash()))
+			.collect::<Vec<_>>()
+	};
+
+	let missing_chain_selection = chain_selection(db.clone())
+		.missing_blocks(headers.iter().map(|header| header.hash()))
+		.map_err(backend_error)?;
+
+	let missing_approval_voting = approval_voting(db.clone())
+		.missing_blocks(headers.iter().map(|header| header.hash()))
+		.map_err(backend_error)?;
+
+	let dispute_coordinator = dispute_coordinator_subsystem::DisputeCoordinatorSubsystem::new(
+		db,
+		dispute_coordinator_subsystem::Config {
+			col_dispute_data: REAL_COLUMNS.col_dispute_coordinator_data,
+			col_session_data: REAL_COLUMNS.col_session_window_data,
+		},
+		Arc::new(sc_keystore::LocalKeystore::in_memory()),
+		Default::default(),
+	);
+	let (earliest_dispute_session, recent_disputes) =
+		dispute_coordinator.load_db_overview().map_err(backend_error)?;
+
+	Ok(IntegrityReport {
+		finalized_number: backend.blockchain().info().finalized_number,
+		unfinalized_blocks: headers.len(),
+		missing_chain_selection: missing(missing_chain_selection),
+		missing_approval_voting: missing(missing_approval_voting),
+		earliest_dispute_session,
+		recent_disputes,
+	})
+}
+
+/// Rebuild missing chain selection entries from the unfinalized headers of the block backend.
+///
+/// Blocks without a known BABE weight are skipped together with their descendants, like the
+/// chain selection subsystem does on import. Returns the number of rebuilt entries.
+pub fn repair_chain_selection<B: Backend<Block>>(
+	backend: &B,
+	db: Arc<dyn Database>,
+) -> sp_blockchain::Result<usize> {
+	let mut skipped = HashSet::new();
+	let mut blocks = Vec::new();
+	for header in unfinalized_headers(backend)? {
+		let hash = header.hash();
+		if skipped.contains(&header.parent_hash) {
+			let _ = skipped.insert(hash);
+			continue
+		}
+		match babe::aux_schema::load_block_weight(backend, hash)? {
+			Some(weight) => blocks.push((header, weight)),
+			None => {
+				gum::warn!(
+					target: super::LOG_TARGET,
+					?hash,
+					"Missing block weight, skipping the block and its descendants",
+				);
+				let _ = skipped.insert(hash);
+			},
+		}
+	}
+
+	chain_selection(db).import_missing_blocks(blocks).map_err(backend_error)
+}
+
+/// Delete all data of one subsystem column. Returns the number of deleted entries.
+///
+/// The subsystem starts over from the unfinalized blocks on the next start of the node. The
+/// approval voting column is not ordered with ParityDB and cannot be iterated over, so its
+/// entries are found from the blocks approval voting keeps track of.
+pub fn reset_column(db: Arc<dyn Database>, column: SubsystemColumn) -> io::Result<usize> {
+	if column == SubsystemColumn::ApprovalVoting {
+		return approval_voting(db)
+			.clear_db()
+			.map_err(|err| other_io_error(format!("Cannot clear the {:?} column: {}", column, err)))
+	}
+
+	let col = column.column();
+	let keys = db
+		.iter(col)
+		.map(|entry| entry.map(|(key, _)| key))
+		.collect::<io::Result<Vec<_>>>()
+		.map_err(|err| {
+			other_io_error(format!("Cannot iterate over the {:?} column: {}", column, err))
+		})?;
+
+	let mut tx = DBTransaction::new();
+	for key in &keys {
+		tx.delete(col, key);
+	}
+	db.write(tx)?;
+
+	Ok(keys.len())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::parachains_db::{open_creating_paritydb, open_creating_rocksdb, CacheSizes};
+	use codec::Encode;
+	use consensus_common::BlockOrigin;
+	use polkadot_test_client::{
+		ClientBlockImportExt, DefaultTestClientBuilderExt, InitPolkadotBlockBuilder,
+		TestClientBuilder, TestClientBuilderExt,
+	};
+	use sc_client_api::AuxStore;
+
+	#[test]
+	fn reset_column_only_wipes_the_column() {
+		let db_dir = tempfile::tempdir().unwrap();
+		let db = open_creating_rocksdb(db_dir.path().to_owned(), CacheSizes::default()).unwrap();
+
+		let mut tx = DBTransaction::new();
+		tx.put(REAL_COLUMNS.col_chain_selection_data, b"CS_block_entry1", b"1");
+		tx.put(REAL_COLUMNS.col_chain_selection_data, b"CS_leaves", b"2");
+		tx.put(REAL_COLUMNS.col_approval_data, b"Approvals_StoredBlocks", b"3");
+		db.write(tx).unwrap();
+
+		assert_eq!(reset_column(db.clone(), SubsystemColumn::ChainSelection).unwrap(), 2);
+		assert_eq!(db.iter(REAL_COLUMNS.col_chain_selection_data).count(), 0);
+		assert!(db
+			.get(REAL_COLUMNS.col_approval_data, b"Approvals_StoredBlocks")
+			.unwrap()
+			.is_some());
+	}
+
+	#[test]
+	fn reset_approval_voting_works_with_paritydb() {
+		let db_dir = tempfile::tempdir().unwrap();
+		let db = open_creating_paritydb(db_dir.path().to_owned(), CacheSizes::default()).unwrap();
+
+		let blocks_at_height_key = [&b"Approvals_at"[..], &1u32.encode()].concat();
+		let mut tx = DBTransaction::new();
+		tx.put_vec(
+			REAL_COLUMNS.col_approval_data,
+			b"Approvals_StoredBlocks",
+			(1u32, 2u32).encode(),
+		);
+		tx.put_vec(
+			REAL_COLUMNS.col_approval_data,
+			&blocks_at_height_key,
+			Vec::<Hash>::new().encode(),
+		);
+		db.write(tx).unwrap();
+
+		assert_eq!(reset_column(db.clone(), SubsystemColumn::ApprovalVoting).unwrap(), 2);
+		assert!(db
+			.get(REAL_COLUMNS.col_approval_data, b"Approvals_StoredBlocks")
+			.unwrap()
+			.is_none());
+		assert!(db.get(REAL_COLUMNS.col_approval_data, &blocks_at_height_key).unwrap().is_none());
+	}
+
+	#[test]
+	fn repair_rebuilds_chain_selection_from_headers() {
+		let builder = TestClientBuilder::new();
+		let backend = builder.backend();
+		let mut client = builder.build();
+
+		for weight in 1..=3u32 {
+			let block = client.init_polkadot_block_builder().build().unwrap().block;
+			let hash = block.header.hash();
+			futures::executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+			let key = babe::aux_schema::block_weight_key(hash);
+			backend.insert_aux(&[(&key[..], &weight.encode()[..])], &[]).unwrap();
+		}
+
+		let db_dir = tempfile::tempdir().unwrap();
+		let db = open_creating_rocksdb(db_dir.path().to_owned(), CacheSizes::default()).unwrap();
+
+		let report = check(&*backend, db.clone()).unwrap();
+		assert_eq!(report.unfinalized_blocks, 3);
+		assert_eq!(
+			report
+				.missing_chain_selection
+				.iter()
+				.map(|(number, _)| *number)
+				.collect::<Vec<_>>(),
+			vec![1, 2, 3],
+		);
+		assert_eq!(report.missing_approval_voting.len(), 3);
+		assert!(!report.is_consistent());
+
+		assert_eq!(repair_chain_selection(&*backend, db.clone()).unwrap(), 3);
+		assert_eq!(repair_chain_selection(&*backend, db.clone()).unwrap(), 0);
+
+		let report = check(&*backend, db).unwrap();
+		assert!(report.missing_chain_selection.is_empty());
+		// Approval voting gaps are reported, but do not make the DB inconsistent.
+		assert_eq!(report.missing_approval_voting.len(), 3);
+		assert!(report.is_consistent());
+	}
+
+	#[test]
+	fn report_lists_gaps_as_ranges() {
+		let report = IntegrityReport {
+			finalized_number: 99,
+			unfinalized_blocks: 10,
+			missing_chain_selection: [100, 101, 102, 105]
+				.into_iter()
+				.map(|number| (number, Hash::repeat_byte(number as u8)))
+				.collect(),
+			missing_approval_voting: Vec::new(),
+			earliest_dispute_session: None,
+			recent_disputes: 0,
+		};
+
+		assert!(!report.is_consistent());
+		assert!(IntegrityReport { missing_chain_selection: Vec::new(), ..report.clone() }
+			.is_consistent());
+		assert_eq!(
+			report.to_string(),
+			"finalized #99, 10 unfinalized blocks\n\
+			 chain selection: 4 unfinalized blocks missing\n  - #100..=#102\n  - #105\n\
+			 approval voting: 0 unfinalized blocks missing\n\
+			 dispute coordinator: empty\n",
+		);
+	}
+}
@@ -18,6 +18,8 @@ use {
 	polkadot_node_subsystem_util::database::Database, std::io, std::path::PathBuf, std::sync::Arc,
 };
 
+#[cfg(feature = "full-node")]
+pub mod integrity;
 #[cfg(feature = "full-node")]
 mod upgrade;
 
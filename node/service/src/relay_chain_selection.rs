@@ -37,7 +37,12 @@
 
 use super::{HeaderProvider, HeaderProviderProvider};
 use consensus_common::{Error as ConsensusError, SelectChain};
-use futures::channel::oneshot;
+use futures::{
+	channel::oneshot,
+	future::{self, Either},
+	Future,
+};
+use futures_timer::Delay;
 use polkadot_node_primitives::MAX_FINALITY_LAG as PRIMITIVES_MAX_FINALITY_LAG;
 use polkadot_node_subsystem::messages::{
 	ApprovalVotingMessage, ChainSelectionMessage, DisputeCoordinatorMessage,
@@ -46,7 +51,14 @@ use polkadot_node_subsystem::messages::{
 use polkadot_node_subsystem_util::metrics::{self, prometheus};
 use polkadot_overseer::{AllMessages, Handle};
 use polkadot_primitives::{Block as PolkadotBlock, BlockNumber, Hash, Header as PolkadotHeader};
-use std::sync::Arc;
+use sp_blockchain::HeaderBackend;
+use std::{
+	sync::{
+		atomic::{AtomicBool, AtomicU32, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
 
 /// The maximum amount of unfinalized blocks we are willing to allow due to approval checking
 /// or disputes.
@@ -58,6 +70,14 @@ const MAX_FINALITY_LAG: polkadot_primitives::BlockNumber = PRIMITIVES_MAX_FINALI
 
 const LOG_TARGET: &str = "parachain::chain-selection";
 
+/// The number of consecutive failures of the chain selection subsystems after which
+/// [`SelectRelayChain::new_hybrid`] falls back to the longest chain.
+pub const LONGEST_CHAIN_FALLBACK_THRESHOLD: u32 = 10;
+
+/// How long [`SelectRelayChain::new_hybrid`] waits for the chain selection subsystems before
+/// counting a failure.
+const SUBSYSTEM_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Prometheus metrics for chain-selection.
 #[derive(Debug, Default, Clone)]
 pub struct Metrics(Option<MetricsInner>);
@@ -66,6 +86,8 @@ pub struct Metrics(Option<MetricsInner>);
 struct MetricsInner {
 	approval_checking_finality_lag: prometheus::Gauge<prometheus::U64>,
 	disputes_finality_lag: prometheus::Gauge<prometheus::U64>,
+	longest_chain_fallback: prometheus::GaugeVec<prometheus::U64>,
+	longest_chain_fallbacks_total: prometheus::CounterVec<prometheus::U64>,
 }
 
 impl metrics::Metrics for Metrics {
@@ -89,6 +111,26 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			longest_chain_fallback: prometheus::register(
+				prometheus::GaugeVec::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_chain_selection_longest_chain_fallback",
+						"Whether chain selection currently falls back to the longest chain (0 or 1)",
+					),
+					&["query"],
+				)?,
+				registry,
+			)?,
+			longest_chain_fallbacks_total: prometheus::register(
+				prometheus::CounterVec::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_chain_selection_longest_chain_fallbacks_total",
+						"Number of times chain selection fell back to the longest chain",
+					),
+					&["query"],
+				)?,
+				registry,
+			)?,
 		};
 
 		Ok(Metrics(Some(metrics)))
@@ -107,6 +149,166 @@ impl Metrics {
 			metrics.disputes_finality_lag.set(lag as _);
 		}
 	}
+
+	fn note_longest_chain_fallback(&self, query: &str, active: bool) {
+		if let Some(ref metrics) = self.0 {
+			metrics.longest_chain_fallback.with_label_values(&[query]).set(active as _);
+			if active {
+				metrics.longest_chain_fallbacks_total.with_label_values(&[query]).inc();
+			}
+		}
+	}
+}
+
+/// Tracks whether the chain selection subsystems can be relied upon for one kind of query.
+///
+/// After `threshold` consecutive failures the longest chain is used instead, until the
+/// subsystems answer again.
+pub(crate) struct LongestChainFallback {
+	query: &'static str,
+	threshold: u32,
+	failures: AtomicU32,
+	active: AtomicBool,
+	metrics: Metrics,
+}
+
+impl LongestChainFallback {
+	pub(crate) fn new(query: &'static str, threshold: u32, metrics: Metrics) -> Self {
+		LongestChainFallback {
+			query,
+			threshold,
+			failures: AtomicU32::new(0),
+			active: AtomicBool::new(false),
+			metrics,
+		}
+	}
+
+	/// Whether the longest chain is currently used.
+	pub(crate) fn is_active(&self) -> bool {
+		self.active.load(Ordering::Relaxed)
+	}
+
+	/// Note a failure of the subsystems. Returns whether the longest chain should be used.
+	pub(crate) fn note_failure(&self, reason: &str) -> bool {
+		let failures = self.failures.fetch_add(1, Ordering::Relaxed).saturating_add(1);
+		if failures >= self.threshold && !self.active.swap(true, Ordering::Relaxed) {
+			gum::error!(
+				target: LOG_TARGET,
+				query = self.query,
+				failures,
+				reason,
+				"Chain selection subsystems keep failing, falling back to the longest chain. \
+				 Approvals and disputes are not taken into account until they recover.",
+			);
+			self.metrics.note_longest_chain_fallback(self.query, true);
+		}
+		self.is_active()
+	}
+
+	/// Note an answer of the subsystems, leaving the fallback if it was active.
+	pub(crate) fn note_success(&self) {
+		self.failures.store(0, Ordering::Relaxed);
+		if self.active.swap(false, Ordering::Relaxed) {
+			gum::info!(
+				target: LOG_TARGET,
+				query = self.query,
+				"Chain selection subsystems recovered"
+			);
+			self.metrics.note_longest_chain_fallback(self.query, false);
+		}
+	}
+}
+
+/// The dispute aware selection of [`SelectRelayChain::new_hybrid`], giving up on the chain
+/// selection subsystems while they keep failing.
+///
+/// Only failures of the subsystems count: a request they drop, a request they do not answer
+/// within [`SUBSYSTEM_TIMEOUT`], and no leaves at all, which means chain selection lacks even
+/// the finalized block. The finalized block as best block, or no viable leaf containing the
+/// finality target, are legitimate answers once disputes reverted the unfinalized blocks and
+/// never cause a fallback. Block authoring and finality count their failures separately.
+///
+/// The methods return `None` when the longest chain should be used instead.
+pub(crate) struct HybridSelection<B, OH> {
+	inner: SelectRelayChainInner<B, OH>,
+	best_chain: LongestChainFallback,
+	finality_target: LongestChainFallback,
+}
+
+impl<B, OH> HybridSelection<B, OH>
+where
+	B: HeaderProviderProvider<PolkadotBlock>,
+	OH: OverseerHandleT,
+{
+	pub(crate) fn new(inner: SelectRelayChainInner<B, OH>, threshold: u32) -> Self {
+		let metrics = inner.metrics.clone();
+		HybridSelection {
+			inner,
+			best_chain: LongestChainFallback::new("best_chain", threshold, metrics.clone()),
+			finality_target: LongestChainFallback::new("finality_target", threshold, metrics),
+		}
+	}
+
+	/// The leaves of the chain, if block authoring does not fall back to the longest chain.
+	pub(crate) async fn leaves(&self) -> Option<Result<Vec<Hash>, ConsensusError>> {
+		if self.best_chain.is_active() {
+			None
+		} else {
+			Some(self.inner.leaves().await)
+		}
+	}
+
+	/// The best block to build on, see [`SelectRelayChainInner::best_chain`].
+	pub(crate) async fn best_chain(&self) -> Option<Result<PolkadotHeader, ConsensusError>> {
+		let best = with_timeout(self.inner.best_chain()).await;
+		match best {
+			Ok(_) => self.best_chain.note_success(),
+			Err(ref e) if is_subsystem_failure(e) =>
+				if self.best_chain.note_failure(&e.to_string()) {
+					return None
+				},
+			Err(_) => {},
+		}
+		Some(best)
+	}
+
+	/// The block to finalize next, see
+	/// [`SelectRelayChainInner::finality_target_with_longest_chain`].
+	pub(crate) async fn finality_target(
+		&self,
+		target_hash: Hash,
+		maybe_max_number: Option<BlockNumber>,
+	) -> Option<Result<Hash, ConsensusError>> {
+		let target = with_timeout(self.inner.finality_target(target_hash, maybe_max_number)).await;
+		match target {
+			Ok(_) => self.finality_target.note_success(),
+			Err(ref e) if is_subsystem_failure(e) => {
+				if self.finality_target.note_failure(&e.to_string()) {
+					return None
+				}
+				// Vote on the target itself, like the dispute aware selection does.
+				return Some(Ok(target_hash))
+			},
+			Err(_) => {},
+		}
+		Some(target)
+	}
+}
+
+/// Whether `error` comes from the chain selection subsystems rather than the block backend.
+fn is_subsystem_failure(error: &ConsensusError) -> bool {
+	matches!(error, ConsensusError::Other(e) if e.downcast_ref::<Error>().is_some())
+}
+
+/// Fail with [`Error::Timeout`] if `request` takes longer than [`SUBSYSTEM_TIMEOUT`].
+async fn with_timeout<T>(
+	request: impl Future<Output = Result<T, ConsensusError>>,
+) -> Result<T, ConsensusError> {
+	futures::pin_mut!(request);
+	match future::select(request, Delay::new(SUBSYSTEM_TIMEOUT)).await {
+		Either::Left((result, _)) => result,
+		Either::Right(_) => Err(ConsensusError::Other(Box::new(Error::Timeout))),
+	}
 }
 
 /// Determines whether the chain is a relay chain
@@ -114,6 +316,8 @@ impl Metrics {
 /// into account.
 enum IsDisputesAwareWithOverseer<B: sc_client_api::Backend<PolkadotBlock>> {
 	Yes(SelectRelayChainInner<B, Handle>),
+	/// Like `Yes`, but falls back to the longest chain while the subsystems fail.
+	Hybrid(Arc<HybridSelection<B, Handle>>),
 	No,
 }
 
@@ -125,6 +329,7 @@ where
 	fn clone(&self) -> Self {
 		match self {
 			Self::Yes(ref inner) => Self::Yes(inner.clone()),
+			Self::Hybrid(ref hybrid) => Self::Hybrid(hybrid.clone()),
 			Self::No => Self::No,
 		}
 	}
@@ -173,6 +378,29 @@ where
 		}
 	}
 
+	/// Create a new [`SelectRelayChain`] which uses the dispute aware selection algorithm, but
+	/// falls back to the longest chain after `threshold` consecutive failures of the
+	/// subsystems, e.g. because the overseer is gone or their database lacks entries. It
+	/// switches back as soon as the subsystems answer again.
+	///
+	/// While the fallback is active, blocks which are not approved or are disputed can be
+	/// finalized, so this is only enabled on request.
+	pub fn new_hybrid(backend: Arc<B>, overseer: Handle, metrics: Metrics, threshold: u32) -> Self {
+		gum::debug!(
+			target: LOG_TARGET,
+			threshold,
+			"Using dispute aware relay-chain selection algorithm with longest chain fallback",
+		);
+
+		SelectRelayChain {
+			longest_chain: sc_consensus::LongestChain::new(backend.clone()),
+			selection: IsDisputesAwareWithOverseer::Hybrid(Arc::new(HybridSelection::new(
+				SelectRelayChainInner::new(backend, overseer, metrics),
+				threshold,
+			))),
+		}
+	}
+
 	/// Allow access to the inner chain, for usage during the node setup.
 	pub fn as_longest_chain(&self) -> &sc_consensus::LongestChain<B, PolkadotBlock> {
 		&self.longest_chain
@@ -187,6 +415,10 @@ where
 	async fn leaves(&self) -> Result<Vec<Hash>, ConsensusError> {
 		match self.selection {
 			IsDisputesAwareWithOverseer::Yes(ref selection) => selection.leaves().await,
+			IsDisputesAwareWithOverseer::Hybrid(ref hybrid) => match hybrid.leaves().await {
+				Some(leaves) => leaves,
+				None => self.longest_chain.leaves().await,
+			},
 			IsDisputesAwareWithOverseer::No => self.longest_chain.leaves().await,
 		}
 	}
@@ -194,6 +426,10 @@ where
 	async fn best_chain(&self) -> Result<PolkadotHeader, ConsensusError> {
 		match self.selection {
 			IsDisputesAwareWithOverseer::Yes(ref selection) => selection.best_chain().await,
+			IsDisputesAwareWithOverseer::Hybrid(ref hybrid) => match hybrid.best_chain().await {
+				Some(best) => best,
+				None => self.longest_chain.best_chain().await,
+			},
 			IsDisputesAwareWithOverseer::No => self.longest_chain.best_chain().await,
 		}
 	}
@@ -203,12 +439,18 @@ where
 		target_hash: Hash,
 		maybe_max_number: Option<BlockNumber>,
 	) -> Result<Hash, ConsensusError> {
-		if let IsDisputesAwareWithOverseer::Yes(ref selection) = self.selection {
-			selection
-				.finality_target_with_longest_chain(target_hash, maybe_max_number)
-				.await
-		} else {
-			self.longest_chain.finality_target(target_hash, maybe_max_number).await
+		match self.selection {
+			IsDisputesAwareWithOverseer::Yes(ref selection) =>
+				selection
+					.finality_target_with_longest_chain(target_hash, maybe_max_number)
+					.await,
+			IsDisputesAwareWithOverseer::Hybrid(ref hybrid) =>
+				match hybrid.finality_target(target_hash, maybe_max_number).await {
+					Some(target) => target,
+					None => self.longest_chain.finality_target(target_hash, maybe_max_number).await,
+				},
+			IsDisputesAwareWithOverseer::No =>
+				self.longest_chain.finality_target(target_hash, maybe_max_number).await,
 		}
 	}
 }
@@ -287,6 +529,9 @@ enum Error {
 	/// Chain selection returned empty leaves.
 	#[error("ChainSelection returned no leaves")]
 	EmptyLeaves,
+	/// The subsystems did not answer in time.
+	#[error("Chain selection subsystems did not answer within {:?}", SUBSYSTEM_TIMEOUT)]
+	Timeout,
 }
 
 /// Decoupling trait for the overseer handle.
@@ -359,30 +604,74 @@ where
 		target_hash: Hash,
 		maybe_max_number: Option<BlockNumber>,
 	) -> Result<Hash, ConsensusError> {
-		let mut overseer = self.overseer.clone();
+		match self.finality_target(target_hash, maybe_max_number).await {
+			Err(ConsensusError::Other(ref e))
+				if matches!(
+					e.downcast_ref::<Error>(),
+					Some(Error::DetermineUndisputedChainCanceled(_))
+				) =>
+			{
+				gum::error!(
+					target: LOG_TARGET,
+					error = ?e,
+					"Call to `DetermineUndisputedChain` failed",
+				);
+				// We need to return a sane finality target. But, we are unable to ensure we are not
+				// finalizing something that is being disputed or has been concluded as invalid. We will be
+				// conservative here and not vote for finality above the ancestor passed in.
+				Ok(target_hash)
+			},
+			target => target,
+		}
+	}
 
-		let subchain_head = {
-			let (tx, rx) = oneshot::channel();
-			overseer
-				.send_msg(
-					ChainSelectionMessage::BestLeafContaining(target_hash, tx),
-					std::any::type_name::<Self>(),
-				)
-				.await;
+	/// Like [`Self::finality_target_with_longest_chain`], but fails when the dispute
+	/// coordinator does not answer.
+	async fn finality_target(
+		&self,
+		target_hash: Hash,
+		maybe_max_number: Option<BlockNumber>,
+	) -> Result<Hash, ConsensusError> {
+		match self.best_leaf_containing(target_hash).await? {
+			// No viable leaves containing the block.
+			None => Ok(target_hash),
+			Some(best) => self.finality_target_from_leaf(target_hash, best, maybe_max_number).await,
+		}
+	}
 
-			let best = rx
-				.await
-				.map_err(Error::BestLeafContainingCanceled)
-				.map_err(|e| ConsensusError::Other(Box::new(e)))?;
+	/// Get the best viable leaf containing `target_hash`, if any.
+	async fn best_leaf_containing(
+		&self,
+		target_hash: Hash,
+	) -> Result<Option<Hash>, ConsensusError> {
+		let (tx, rx) = oneshot::channel();
+		self.overseer
+			.clone()
+			.send_msg(
+				ChainSelectionMessage::BestLeafContaining(target_hash, tx),
+				std::any::type_name::<Self>(),
+			)
+			.await;
+
+		let best = rx
+			.await
+			.map_err(Error::BestLeafContainingCanceled)
+			.map_err(|e| ConsensusError::Other(Box::new(e)))?;
 
-			gum::trace!(target: LOG_TARGET, ?best, "Best leaf containing");
+		gum::trace!(target: LOG_TARGET, ?best, "Best leaf containing");
 
-			match best {
-				// No viable leaves containing the block.
-				None => return Ok(target_hash),
-				Some(best) => best,
-			}
-		};
+		Ok(best)
+	}
+
+	/// Get the best descendant of `target_hash` to finalize on the chain ending in
+	/// `subchain_head`, a leaf containing `target_hash`.
+	async fn finality_target_from_leaf(
+		&self,
+		target_hash: Hash,
+		subchain_head: Hash,
+		maybe_max_number: Option<BlockNumber>,
+	) -> Result<Hash, ConsensusError> {
+		let mut overseer = self.overseer.clone();
 
 		let target_number = self.block_number(target_hash)?;
 
@@ -482,31 +771,17 @@ where
 				)
 				.await;
 
-			// Try to fetch response from `dispute-coordinator`. If an error occurs we just log it
-			// and return `target_hash` as maximal vote. It is safer to contain this error here
+			// If the `dispute-coordinator` does not answer, `finality_target_with_longest_chain`
+			// returns `target_hash` as maximal vote. It is safer to contain this error there
 			// and not push it up the stack to cause additional issues in GRANDPA/BABE.
-			let (lag, subchain_head) =
-				match rx.await.map_err(Error::DetermineUndisputedChainCanceled) {
-					// If request succeded we will receive (block number, block hash).
-					Ok((subchain_number, subchain_head)) => {
-						// The total lag accounting for disputes.
-						let lag_disputes = initial_leaf_number.saturating_sub(subchain_number);
-						self.metrics.note_disputes_finality_lag(lag_disputes);
-						(lag_disputes, subchain_head)
-					},
-					Err(e) => {
-						gum::error!(
-							target: LOG_TARGET,
-							error = ?e,
-							"Call to `DetermineUndisputedChain` failed",
-						);
-						// We need to return a sane finality target. But, we are unable to ensure we are not
-						// finalizing something that is being disputed or has been concluded as invalid. We will be
-						// conservative here and not vote for finality above the ancestor passed in.
-						return Ok(target_hash)
-					},
-				};
-			(lag, subchain_head)
+			let (subchain_number, subchain_head) = rx
+				.await
+				.map_err(Error::DetermineUndisputedChainCanceled)
+				.map_err(|e| ConsensusError::Other(Box::new(e)))?;
+			// The total lag accounting for disputes.
+			let lag_disputes = initial_leaf_number.saturating_sub(subchain_number);
+			self.metrics.note_disputes_finality_lag(lag_disputes);
+			(lag_disputes, subchain_head)
 		};
 
 		gum::trace!(
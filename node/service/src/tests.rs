@@ -774,3 +774,182 @@ fn chain_sel_5_best_is_target_hash() {
 fn chain_sel_6_approval_lag() {
 	run_specialized_test_w_harness(chain_6);
 }
+
+#[test]
+fn longest_chain_fallback_needs_consecutive_failures() {
+	let fallback = LongestChainFallback::new("finality_target", 3, Default::default());
+
+	assert!(!fallback.note_failure("no viable leaf"));
+	assert!(!fallback.note_failure("no viable leaf"));
+	fallback.note_success();
+
+	// The count starts over after a usable answer.
+	assert!(!fallback.note_failure("no viable leaf"));
+	assert!(!fallback.note_failure("no viable leaf"));
+	assert!(fallback.note_failure("no viable leaf"));
+	assert!(fallback.is_active());
+	assert!(fallback.note_failure("no viable leaf"));
+
+	fallback.note_success();
+	assert!(!fallback.is_active());
+	assert!(!fallback.note_failure("no viable leaf"));
+}
+
+type TestHybridSelection = HybridSelection<TestChainStorage, TestSubsystemSender>;
+
+/// Run `selection` against a [`HybridSelection`] falling back after `threshold` failures,
+/// while `overseer` answers its requests.
+fn hybrid_test_harness<S, O>(
+	chain: TestChainStorage,
+	threshold: u32,
+	selection: impl FnOnce(TestHybridSelection) -> S,
+	overseer: impl FnOnce(VirtualOverseer) -> O,
+) where
+	S: Future<Output = ()>,
+	O: Future<Output = VirtualOverseer>,
+{
+	let pool = sp_core::testing::TaskExecutor::new();
+	let (mut context, virtual_overseer) = test_helpers::make_subsystem_context(pool);
+
+	let hybrid = HybridSelection::new(
+		SelectRelayChainInner::new(Arc::new(chain), context.sender().clone(), Default::default()),
+		threshold,
+	);
+
+	futures::executor::block_on(future::join(selection(hybrid), async move {
+		let _overseer: VirtualOverseer = overseer(virtual_overseer).await;
+	}));
+}
+
+/// Genesis with two approved descendants.
+fn hybrid_chain() -> (TestChainStorage, Hash, Hash) {
+	let mut builder = ChainBuilder::new();
+	let a1 = builder.fast_forward_approved(0xA0, ChainBuilder::GENESIS_HASH, 1);
+	let a2 = builder.fast_forward_approved(0xA0, a1, 2);
+	builder.set_heads(vec![a2]);
+	(builder.init(), a1, a2)
+}
+
+#[test]
+fn hybrid_selection_falls_back_and_recovers() {
+	let (chain, a1, a2) = hybrid_chain();
+
+	hybrid_test_harness(
+		chain.clone(),
+		2,
+		|hybrid| async move {
+			// The first failure votes on the target, like the dispute aware selection does.
+			assert_matches!(hybrid.finality_target(a1, None).await, Some(Ok(target)) => {
+				assert_eq!(target, a1);
+			});
+			assert_matches!(hybrid.finality_target(a1, None).await, None);
+
+			// Block authoring counts its failures separately.
+			assert_matches!(hybrid.best_chain().await, Some(Ok(header)) => {
+				assert_eq!(header.number, 2);
+			});
+			assert_matches!(hybrid.leaves().await, Some(Ok(leaves)) => {
+				assert_eq!(leaves, vec![a2]);
+			});
+
+			// The subsystems are queried again and used as soon as they answer.
+			assert_matches!(hybrid.finality_target(a1, None).await, Some(Ok(target)) => {
+				assert_eq!(target, a2);
+			});
+		},
+		|mut virtual_overseer| async move {
+			for _ in 0..2 {
+				assert_matches!(
+					overseer_recv(&mut virtual_overseer).await,
+					AllMessages::ChainSelection(ChainSelectionMessage::BestLeafContaining(_, tx)) => {
+						drop(tx);
+					}
+				);
+			}
+			for _ in 0..2 {
+				assert_matches!(
+					overseer_recv(&mut virtual_overseer).await,
+					AllMessages::ChainSelection(ChainSelectionMessage::Leaves(tx)) => {
+						tx.send(vec![a2]).unwrap();
+					}
+				);
+			}
+			test_skeleton(
+				&chain,
+				&mut virtual_overseer,
+				a1,
+				Some(a2),
+				chain.highest_approved_ancestors(1, a2),
+				Some(a2),
+			)
+			.await;
+			virtual_overseer
+		},
+	);
+}
+
+#[test]
+fn hybrid_selection_falls_back_without_leaves() {
+	let (chain, _, _) = hybrid_chain();
+
+	hybrid_test_harness(
+		chain,
+		2,
+		|hybrid| async move {
+			assert_matches!(hybrid.best_chain().await, Some(Err(_)));
+			assert_matches!(hybrid.best_chain().await, None);
+			assert_matches!(hybrid.leaves().await, None);
+		},
+		|mut virtual_overseer| async move {
+			for _ in 0..2 {
+				assert_matches!(
+					overseer_recv(&mut virtual_overseer).await,
+					AllMessages::ChainSelection(ChainSelectionMessage::Leaves(tx)) => {
+						tx.send(Vec::new()).unwrap();
+					}
+				);
+			}
+			virtual_overseer
+		},
+	);
+}
+
+#[test]
+fn hybrid_selection_does_not_fall_back_on_dispute_reverted_chain() {
+	let (chain, a1, _) = hybrid_chain();
+	const QUERIES: usize = 3;
+
+	hybrid_test_harness(
+		chain,
+		2,
+		|hybrid| async move {
+			for _ in 0..QUERIES {
+				// All unfinalized blocks are reverted, so the finalized block is the best one.
+				assert_matches!(hybrid.best_chain().await, Some(Ok(header)) => {
+					assert_eq!(header.number, 0);
+				});
+				// And no viable leaf contains the target.
+				assert_matches!(hybrid.finality_target(a1, None).await, Some(Ok(target)) => {
+					assert_eq!(target, a1);
+				});
+			}
+		},
+		|mut virtual_overseer| async move {
+			for _ in 0..QUERIES {
+				assert_matches!(
+					overseer_recv(&mut virtual_overseer).await,
+					AllMessages::ChainSelection(ChainSelectionMessage::Leaves(tx)) => {
+						tx.send(vec![ChainBuilder::GENESIS_HASH]).unwrap();
+					}
+				);
+				assert_matches!(
+					overseer_recv(&mut virtual_overseer).await,
+					AllMessages::ChainSelection(ChainSelectionMessage::BestLeafContaining(_, tx)) => {
+						tx.send(None).unwrap();
+					}
+				);
+			}
+			virtual_overseer
+		},
+	);
+}
@@ -0,0 +1,421 @@
+// Copyright 2026 THX Network Contributors.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Readiness conditions of a validator, served by the `system_validatorHealth` RPC and at
+//! `/health/validator` on the Prometheus port.
+//!
+//! A validator is ready once it is synced, connected, able to reach the other validators and
+//! actively taking part in BABE and GRANDPA.
+
+use crate::{BabeApi, HeaderBackend, ParachainHost};
+use futures::future::Future;
+use hyper::{
+	header::{HeaderValue, CONTENT_TYPE},
+	service::{make_service_fn, service_fn},
+	Body, Method, Request, Response, Server, StatusCode,
+};
+use parking_lot::Mutex;
+use polkadot_primitives::{Block, BlockNumber, Hash};
+use polkadot_rpc::health::{ValidatorHealthProvider, ValidatorHealthReport};
+use prometheus_endpoint::{
+	prometheus::{Encoder, TextEncoder, TEXT_FORMAT},
+	Registry,
+};
+use sc_network_sync::SyncingService;
+use sp_api::ProvideRuntimeApi;
+use sp_core::crypto::{key_types, ByteArray};
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+/// Path of the HTTP endpoint answering with the report, `200 OK` if the validator is ready and
+/// `503 Service Unavailable` otherwise.
+pub(crate) const HEALTH_PATH: &str = "/health/validator";
+
+/// How often the GRANDPA voter state is sampled.
+const GRANDPA_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Thresholds of the readiness conditions.
+#[derive(Debug, Clone)]
+pub struct ValidatorHealthConfig {
+	/// Minimum number of connected peers.
+	pub min_peers: usize,
+	/// Minimum share of the other validators of the current session, in percent, whose
+	/// addresses authority discovery has resolved.
+	pub min_authority_discovery_percent: u8,
+	/// Maximum number of GRANDPA rounds since the last vote of the local authority.
+	pub max_rounds_without_vote: u64,
+}
+
+impl Default for ValidatorHealthConfig {
+	fn default() -> Self {
+		Self { min_peers: 3, min_authority_discovery_percent: 66, max_rounds_without_vote: 5 }
+	}
+}
+
+/// GRANDPA voting activity of the local authority, as seen by the running voter.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct GrandpaActivity {
+	set_id: u64,
+	best_round: Option<u64>,
+	last_voted_round: Option<u64>,
+}
+
+impl GrandpaActivity {
+	/// Record that the voter of `set_id` is in `best_round` and the local authority last voted in
+	/// `voted_round`, if any of the rounds kept by the voter.
+	fn note(&mut self, set_id: u64, best_round: u64, voted_round: Option<u64>) {
+		// A new set or a restarted voter starts counting rounds from scratch.
+		if set_id != self.set_id || self.best_round.map_or(false, |round| best_round < round) {
+			*self = GrandpaActivity { set_id, ..Default::default() };
+		}
+		self.best_round = Some(best_round);
+		self.last_voted_round = self.last_voted_round.max(voted_round);
+	}
+
+	/// The number of rounds since the last vote, `None` if there was no vote in the set.
+	fn rounds_without_vote(&self) -> Option<u64> {
+		let last_voted_round = self.last_voted_round?;
+		Some(self.best_round.unwrap_or(last_voted_round).saturating_sub(last_voted_round))
+	}
+}
+
+fn local_grandpa_id(
+	shared_authority_set: &grandpa::SharedAuthoritySet<Hash, BlockNumber>,
+	keystore: &dyn SyncCryptoStore,
+) -> Option<grandpa_primitives::AuthorityId> {
+	shared_authority_set
+		.current_authorities()
+		.iter()
+		.map(|(id, _)| id)
+		.find(|id| keystore.has_keys(&[(id.to_raw_vec(), grandpa_primitives::KEY_TYPE)]))
+		.cloned()
+}
+
+/// Sample the GRANDPA voter state and record the rounds in which the local authority voted.
+fn track_grandpa_activity(
+	shared_voter_state: grandpa::SharedVoterState,
+	shared_authority_set: grandpa::SharedAuthoritySet<Hash, BlockNumber>,
+	keystore: SyncCryptoStorePtr,
+	activity: Arc<Mutex<GrandpaActivity>>,
+) -> impl Future<Output = ()> {
+	async move {
+		loop {
+			futures_timer::Delay::new(GRANDPA_SAMPLE_INTERVAL).await;
+
+			let voter_state = match shared_voter_state.voter_state() {
+				Some(voter_state) => voter_state,
+				None => continue,
+			};
+			let set_id = shared_authority_set.set_id();
+			let local_id = local_grandpa_id(&shared_authority_set, &*keystore);
+
+			let (best_round, best_round_state) = &voter_state.best_round;
+			let voted_round = voter_state
+				.background_rounds
+				.iter()
+				.chain(std::iter::once((best_round, best_round_state)))
+				.filter(|(_, state)| {
+					local_id.as_ref().map_or(false, |id| {
+						state.prevote_ids.contains(id) || state.precommit_ids.contains(id)
+					})
+				})
+				.map(|(round, _)| *round)
+				.max();
+
+			activity.lock().note(set_id, *best_round, voted_round);
+		}
+	}
+}
+
+/// Checks the readiness conditions of the node.
+pub(crate) struct ValidatorHealthChecker<C> {
+	config: ValidatorHealthConfig,
+	client: Arc<C>,
+	sync_service: Arc<SyncingService<Block>>,
+	authority_discovery_service: Option<sc_authority_discovery::Service>,
+	keystore: SyncCryptoStorePtr,
+	shared_authority_set: grandpa::SharedAuthoritySet<Hash, BlockNumber>,
+	grandpa_activity: Arc<Mutex<GrandpaActivity>>,
+}
+
+impl<C> ValidatorHealthChecker<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BabeApi<Block> + ParachainHost<Block>,
+{
+	/// Create the checker together with the task sampling the GRANDPA voter state, which must be
+	/// spawned for the GRANDPA condition to ever hold.
+	pub(crate) fn new(
+		config: ValidatorHealthConfig,
+		client: Arc<C>,
+		sync_service: Arc<SyncingService<Block>>,
+		authority_discovery_service: Option<sc_authority_discovery::Service>,
+		keystore: SyncCryptoStorePtr,
+		shared_authority_set: grandpa::SharedAuthoritySet<Hash, BlockNumber>,
+		shared_voter_state: grandpa::SharedVoterState,
+	) -> (Self, impl Future<Output = ()>) {
+		let grandpa_activity = Arc::new(Mutex::new(GrandpaActivity::default()));
+		let tracker = track_grandpa_activity(
+			shared_voter_state,
+			shared_authority_set.clone(),
+			keystore.clone(),
+			grandpa_activity.clone(),
+		);
+
+		let checker = Self {
+			config,
+			client,
+			sync_service,
+			authority_discovery_service,
+			keystore,
+			shared_authority_set,
+			grandpa_activity,
+		};
+
+		(checker, tracker)
+	}
+
+	async fn check_authority_discovery(&self, best_hash: Hash) -> (bool, String) {
+		let mut service = match &self.authority_discovery_service {
+			Some(service) => service.clone(),
+			None => return (false, "authority discovery is not running".into()),
+		};
+		let session_info = {
+			let api = self.client.runtime_api();
+			api.session_index_for_child(best_hash)
+				.and_then(|session| api.session_info(best_hash, session))
+		};
+		let authorities = match session_info {
+			Ok(Some(session_info)) => session_info.discovery_keys,
+			Ok(None) => return (false, "no session info for the current session".into()),
+			Err(err) => return (false, format!("cannot fetch the current session info: {}", err)),
+		};
+
+		let mut total = 0;
+		let mut resolved = 0;
+		for authority in authorities {
+			if self
+				.keystore
+				.has_keys(&[(authority.to_raw_vec(), key_types::AUTHORITY_DISCOVERY)])
+			{
+				continue
+			}
+			total += 1;
+			if service
+				.get_addresses_by_authority_id(authority)
+				.await
+				.map_or(false, |addresses| !addresses.is_empty())
+			{
+				resolved += 1;
+			}
+		}
+
+		let min_percent = self.config.min_authority_discovery_percent as usize;
+		(
+			resolved * 100 >= total * min_percent,
+			format!(
+				"resolved {} of {} validators, at least {}% required",
+				resolved, total, min_percent
+			),
+		)
+	}
+
+	fn check_grandpa(&self) -> (bool, String) {
+		let set_id = self.shared_authority_set.set_id();
+		if local_grandpa_id(&self.shared_authority_set, &*self.keystore).is_none() {
+			return (false, format!("no key of GRANDPA set {} in the keystore", set_id))
+		}
+
+		let activity = self.grandpa_activity.lock().clone();
+		match activity.rounds_without_vote() {
+			Some(rounds) if activity.set_id == set_id => (
+				rounds <= self.config.max_rounds_without_vote,
+				format!(
+					"last voted {} rounds ago in set {}, at most {} allowed",
+					rounds, set_id, self.config.max_rounds_without_vote
+				),
+			),
+			_ => (false, format!("no vote in GRANDPA set {} yet", set_id)),
+		}
+	}
+
+	fn check_babe(&self, best_hash: Hash) -> (bool, String) {
+		match self.client.runtime_api().current_epoch(best_hash) {
+			Ok(epoch) => {
+				let has_key = epoch.authorities.iter().any(|(id, _)| {
+					self.keystore.has_keys(&[(id.to_raw_vec(), sp_consensus_babe::KEY_TYPE)])
+				});
+				let detail = if has_key { "a" } else { "no" };
+				(
+					has_key,
+					format!("{} key of BABE epoch {} in the keystore", detail, epoch.epoch_index),
+				)
+			},
+			Err(err) => (false, format!("cannot fetch the current BABE epoch: {}", err)),
+		}
+	}
+}
+
+#[async_trait::async_trait]
+impl<C> ValidatorHealthProvider for ValidatorHealthChecker<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: BabeApi<Block> + ParachainHost<Block>,
+{
+	async fn report(&self) -> ValidatorHealthReport {
+		use consensus_common::SyncOracle;
+
+		let mut report = ValidatorHealthReport::default();
+		let best_hash = self.client.info().best_hash;
+
+		let major_syncing = self.sync_service.is_major_syncing();
+		let detail = if major_syncing { "major syncing" } else { "synced" };
+		report.push("sync", !major_syncing, detail.into());
+
+		let peers = self.sync_service.num_connected_peers();
+		report.push(
+			"peers",
+			peers >= self.config.min_peers,
+			format!("{} peers, at least {} required", peers, self.config.min_peers),
+		);
+
+		let (ok, detail) = self.check_authority_discovery(best_hash).await;
+		report.push("authorityDiscovery", ok, detail);
+
+		let (ok, detail) = self.check_grandpa();
+		report.push("grandpa", ok, detail);
+
+		let (ok, detail) = self.check_babe(best_hash);
+		report.push("babe", ok, detail);
+
+		report
+	}
+}
+
+fn response(status: StatusCode, content_type: &'static str, body: Vec<u8>) -> Response<Body> {
+	let mut response = Response::new(Body::from(body));
+	*response.status_mut() = status;
+	response
+		.headers_mut()
+		.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+	response
+}
+
+async fn respond<P: ValidatorHealthProvider>(
+	request: Request<Body>,
+	registry: &Registry,
+	provider: &P,
+) -> Response<Body> {
+	const TEXT_PLAIN: &str = "text/plain; charset=utf-8";
+
+	if request.method() != Method::GET {
+		return response(StatusCode::METHOD_NOT_ALLOWED, TEXT_PLAIN, b"Method not allowed.".to_vec())
+	}
+
+	match request.uri().path() {
+		"/metrics" => {
+			let mut buffer = Vec::new();
+			match TextEncoder::new().encode(&registry.gather(), &mut buffer) {
+				Ok(()) => response(StatusCode::OK, TEXT_FORMAT, buffer),
+				Err(err) => response(
+					StatusCode::INTERNAL_SERVER_ERROR,
+					TEXT_PLAIN,
+					format!("Cannot encode the metrics: {}", err).into_bytes(),
+				),
+			}
+		},
+		HEALTH_PATH => {
+			let report = provider.report().await;
+			let status =
+				if report.is_ready() { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+			let body = serde_json::to_vec(&report).expect("the report only holds strings; qed");
+			response(status, "application/json", body)
+		},
+		_ => response(StatusCode::NOT_FOUND, TEXT_PLAIN, b"Not found.".to_vec()),
+	}
+}
+
+/// Serves the metrics of `registry` at `/metrics` and the report of `provider` at
+/// [`HEALTH_PATH`] on `address`.
+///
+/// It replaces the Prometheus endpoint of Substrate on validators, because orchestrators probe
+/// their readiness over plain HTTP and the status code of a JSON-RPC error is `200 OK`.
+pub(crate) async fn serve<P: ValidatorHealthProvider>(
+	address: SocketAddr,
+	registry: Registry,
+	provider: Arc<P>,
+) -> Result<(), hyper::Error> {
+	let service = make_service_fn(move |_| {
+		let registry = registry.clone();
+		let provider = provider.clone();
+		async move {
+			Ok::<_, hyper::Error>(service_fn(move |request: Request<Body>| {
+				let registry = registry.clone();
+				let provider = provider.clone();
+				async move { Ok::<_, hyper::Error>(respond(request, &registry, &*provider).await) }
+			}))
+		}
+	});
+
+	let server = Server::try_bind(&address)?.serve(service);
+	log::info!("〽️ Prometheus exporter and validator health started at {}", address);
+	server.await
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn grandpa_activity_counts_rounds_within_a_set() {
+		let mut activity = GrandpaActivity::default();
+		assert_eq!(activity.rounds_without_vote(), None);
+
+		activity.note(3, 10, Some(10));
+		activity.note(3, 14, None);
+		assert_eq!(activity.rounds_without_vote(), Some(4));
+
+		activity.note(3, 15, Some(15));
+		assert_eq!(activity.rounds_without_vote(), Some(0));
+
+		// A new set forgets the votes of the previous one.
+		activity.note(4, 1, None);
+		assert_eq!(activity.set_id, 4);
+		assert_eq!(activity.rounds_without_vote(), None);
+
+		// So does a restarted voter.
+		activity.note(4, 5, Some(5));
+		activity.note(4, 2, None);
+		assert_eq!(activity.rounds_without_vote(), None);
+	}
+
+	struct Fixed(bool);
+
+	#[async_trait::async_trait]
+	impl ValidatorHealthProvider for Fixed {
+		async fn report(&self) -> ValidatorHealthReport {
+			let mut report = ValidatorHealthReport::default();
+			report.push("peers", self.0, "checked".into());
+			report
+		}
+	}
+
+	fn get(path: &str, ready: bool) -> Response<Body> {
+		let request = Request::get(path).body(Body::empty()).unwrap();
+		futures::executor::block_on(respond(request, &Registry::new(), &Fixed(ready)))
+	}
+
+	#[test]
+	fn health_endpoint_answers_with_the_readiness_as_status() {
+		assert_eq!(get(HEALTH_PATH, true).status(), StatusCode::OK);
+		assert_eq!(get(HEALTH_PATH, false).status(), StatusCode::SERVICE_UNAVAILABLE);
+		assert_eq!(get("/metrics", false).status(), StatusCode::OK);
+		assert_eq!(get("/", true).status(), StatusCode::NOT_FOUND);
+
+		let body =
+			futures::executor::block_on(hyper::body::to_bytes(get(HEALTH_PATH, false).into_body()))
+				.unwrap();
+		let report: ValidatorHealthReport = serde_json::from_slice(&body).unwrap();
+		assert!(!report.is_ready());
+	}
+}
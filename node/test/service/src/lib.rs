@@ -103,6 +103,9 @@ pub fn new_full(
 		None,
 		None,
 		None,
+		Default::default(),
+		Default::default(),
+		false,
 	)
 }
 
@@ -0,0 +1,32 @@
+//! Runtime API definition for the DAO pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_dao::{TopicInfo, TopicResults, VoterStatus};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait DaoApi<AccountId, TopicId, OptionIndex, VoteWeight>
+	where
+		AccountId: Codec,
+		TopicId: Codec,
+		OptionIndex: Codec,
+		VoteWeight: Codec,
+	{
+		/// Returns at most `limit` topics, starting after the topic `start_after`.
+		fn topics(start_after: Option<TopicId>, limit: u32) -> Vec<TopicInfo<TopicId, AccountId>>;
+
+		/// Returns the topic `topic_id`, if any.
+		fn topic(topic_id: TopicId) -> Option<TopicInfo<TopicId, AccountId>>;
+
+		/// Returns the ballots cast on the topic `topic_id` and its outcome, if any.
+		fn topic_results(topic_id: TopicId) -> Option<TopicResults<OptionIndex, VoteWeight>>;
+
+		/// Returns where `who` stands on the topic `topic_id`.
+		fn voter_status(topic_id: TopicId, who: AccountId) -> VoterStatus<VoteWeight>;
+
+		/// Returns whether `who` can vote on the topic `topic_id` now.
+		fn can_vote(topic_id: TopicId, who: AccountId) -> bool;
+	}
+}
@@ -0,0 +1,656 @@
+//! DAO pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use frame_benchmarking::{account, benchmarks_instance_pallet, whitelisted_caller, BenchmarkError};
+use frame_support::{
+	assert_ok,
+	dispatch::UnfilteredDispatchable,
+	traits::{
+		Currency, EnsureOrigin, Get, Hooks, LockableCurrency, ReservableCurrency, StorePreimage,
+		WithdrawReasons,
+	},
+	BoundedVec,
+};
+use frame_system::RawOrigin as SystemOrigin;
+use sp_runtime::{
+	traits::{Hash, SaturatedConversion, Zero},
+	Perbill,
+};
+use sp_std::{prelude::*, vec};
+
+use super::*;
+use crate::Pallet as Dao;
+
+const SEED: u32 = 0;
+const NOW: u64 = 1_000_000;
+const VOTING_PERIOD_START: u64 = NOW + 1_000_000;
+const VOTING_PERIOD_END: u64 = VOTING_PERIOD_START + 1_000_000;
+
+fn assert_last_event<T: Config<I>, I: 'static>(generic_event: <T as Config<I>>::RuntimeEvent) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+/// Move both the timestamp and the block number to `now`, so that the benchmarks hold in either
+/// period mode.
+fn set_now<T: pallet_timestamp::Config>(now: u64) {
+	pallet_timestamp::Now::<T>::put((now * 1000).saturated_into::<T::Moment>());
+	frame_system::Pallet::<T>::set_block_number(now.saturated_into());
+}
+
+fn funded_account<T: Config<I>, I: 'static>(name: &'static str, index: u32) -> T::AccountId {
+	let who = account(name, index, SEED);
+	let balance = T::TopicRaiserBalanceLowerBound::get()
+		.max(T::CurrencyUnits::get())
+		.saturating_mul(10);
+	T::Currency::make_free_balance_be(&who, balance.saturated_into());
+	who
+}
+
+/// A valid option name of maximum length, distinct for every `index`.
+fn option_name<T: Config<I>, I: 'static>(index: u32) -> Vec<u8> {
+	let mut option = vec![b'a'; T::TopicOptionMaximumLength::get() as usize];
+	for (nibble, byte) in option.iter_mut().take(8).enumerate() {
+		*byte = b'a' + ((index >> (4 * nibble)) & 0xf) as u8;
+	}
+	option
+}
+
+fn raise<T: Config<I>, I: 'static>(
+	raiser: T::AccountId,
+	options: u32,
+	required_answer_number: u32,
+) -> T::TopicId {
+	let topic_id = TopicCount::<T, I>::get().unwrap_or(T::TopicId::initial_value());
+	assert_ok!(Dao::<T, I>::do_raise_topic(
+		raiser,
+		vec![b'a'; T::TopicTitleMaximumLength::get() as usize],
+		vec![b'a'; T::TopicDescriptionMaximumLength::get() as usize],
+		VOTING_PERIOD_START,
+		VOTING_PERIOD_END,
+		(0..options).map(option_name::<T, I>).collect(),
+		required_answer_number,
+		Zero::zero(),
+		false,
+	));
+	topic_id
+}
+
+fn remark_call<T: Config<I>, I: 'static>() -> CallOf<T> {
+	frame_system::Call::<T>::remark { remark: vec![] }.into()
+}
+
+benchmarks_instance_pallet! {
+	where_clause { where T: pallet_timestamp::Config }
+
+	raise_topic {
+		let t in (T::TopicTitleMinimumLength::get()) .. T::TopicTitleMaximumLength::get();
+		let d in
+			(T::TopicDescriptionMinimumLength::get()) .. T::TopicDescriptionMaximumLength::get();
+		let o in 2 .. T::TopicOptionMaximumNumber::get();
+		set_now::<T>(NOW);
+		let caller = funded_account::<T, I>("caller", 0);
+		let title = vec![b'a'; t as usize];
+		let description = vec![b'a'; d as usize];
+		let options = (0..o).map(option_name::<T, I>).collect::<Vec<_>>();
+		T::Currency::deposit_creating(
+			&caller,
+			Dao::<T, I>::topic_deposit(&title, &description, &options),
+		);
+	}: _(
+		SystemOrigin::Signed(caller.clone()),
+		title,
+		description,
+		VOTING_PERIOD_START,
+		VOTING_PERIOD_END,
+		options,
+		1
+	)
+	verify {
+		assert_last_event::<T, I>(
+			Event::TopicRaised { id: T::TopicId::initial_value(), raiser: caller }.into(),
+		);
+	}
+
+	force_raise_topic {
+		let t in (T::TopicTitleMinimumLength::get()) .. T::TopicTitleMaximumLength::get();
+		let d in
+			(T::TopicDescriptionMinimumLength::get()) .. T::TopicDescriptionMaximumLength::get();
+		let o in 2 .. T::TopicOptionMaximumNumber::get();
+		set_now::<T>(NOW);
+		let raiser: T::AccountId = account("raiser", 0, SEED);
+		let origin = T::ForceOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let call = Call::<T, I>::force_raise_topic {
+			raiser: raiser.clone(),
+			title: vec![b'a'; t as usize],
+			description: vec![b'a'; d as usize],
+			voting_period_start: VOTING_PERIOD_START,
+			voting_period_end: VOTING_PERIOD_END,
+			options: (0..o).map(option_name::<T, I>).collect(),
+			required_answer_number: 1,
+		};
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T, I>(
+			Event::TopicRaised { id: T::TopicId::initial_value(), raiser }.into(),
+		);
+	}
+
+	issue_voting_right_token {
+		let v in 1 .. T::MaxVotersPerIssuance::get();
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), 2, 1);
+		let voters = (0..v).map(|i| funded_account::<T, I>("voter", i)).collect::<Vec<_>>();
+		let last_voter = voters.last().cloned().expect("at least one voter");
+	}: _(SystemOrigin::Signed(raiser), topic_id, voters, None)
+	verify {
+		assert!(VotingRightTokenCollection::<T, I>::contains_key(topic_id, last_voter));
+	}
+
+	vote_topic {
+		let o in 1 .. T::TopicOptionMaximumNumber::get() - 1;
+		let d in 0 .. T::MaxDelegators::get();
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), o + 1, o);
+		let voter = funded_account::<T, I>("voter", 0);
+		assert_ok!(Dao::<T, I>::issue_voting_right_token(
+			SystemOrigin::Signed(raiser).into(),
+			topic_id,
+			vec![voter.clone()],
+			None,
+		));
+		for i in 0..d {
+			let delegator: T::AccountId = account("delegator", i, SEED);
+			VotingRightTokenCollection::<T, I>::insert(topic_id, &delegator, T::Vote::from(1_000u32));
+			assert_ok!(Dao::<T, I>::delegate(
+				SystemOrigin::Signed(delegator).into(),
+				voter.clone(),
+				DelegationScope::Global,
+			));
+		}
+		set_now::<T>(VOTING_PERIOD_START);
+		let options: BoundedVec<_, _> = (0..o)
+			.map(|i| T::OptionIndex::from(u64::from(i)))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("fits in TopicOptionMaximumNumber");
+	}: _(SystemOrigin::Signed(voter), topic_id, options)
+	verify {
+		assert_eq!(TopicTurnout::<T, I>::get(topic_id), 1);
+		assert_eq!(DelegatedVotes::<T, I>::iter_prefix(topic_id).count() as u32, d);
+	}
+
+	close_topic {
+		let o in 2 .. T::TopicOptionMaximumNumber::get();
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser, o, 1);
+		for i in 0..o {
+			let option = T::OptionIndex::from(u64::from(i));
+			BallotBox::<T, I>::insert(topic_id, option, T::Vote::from(1u32));
+		}
+		TopicTurnout::<T, I>::insert(topic_id, o);
+		set_now::<T>(VOTING_PERIOD_END + 1);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(SystemOrigin::Signed(caller), topic_id)
+	verify {
+		assert!(TopicOutcomes::<T, I>::contains_key(topic_id));
+	}
+
+	close_ranked_choice_topic {
+		let o in 2 .. T::MaxRankedChoiceOptions::get();
+		let v in 0 .. T::MaxRankedChoiceVoters::get();
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser, o, o - 1);
+		TopicCollection::<T, I>::mutate(topic_id, |details| {
+			details.as_mut().expect("topic is raised").tally_method = TallyMethod::RankedChoice
+		});
+		// Every ballot puts a different option first with a different weight, so that a single
+		// option is eliminated every round.
+		for i in 0..v {
+			let first = i % o;
+			let ranking: BoundedVec<_, _> = (0..o - 1)
+				.map(|rank| T::OptionIndex::from(u64::from((first + rank) % o)))
+				.collect::<Vec<_>>()
+				.try_into()
+				.expect("fits in TopicOptionMaximumNumber");
+			let voter: T::AccountId = account("voter", i, SEED);
+			RankedBallots::<T, I>::insert(topic_id, voter, (ranking, T::Vote::from(first + 1)));
+		}
+		TopicTurnout::<T, I>::insert(topic_id, v);
+		set_now::<T>(VOTING_PERIOD_END + 1);
+		let caller: T::AccountId = whitelisted_caller();
+	}: close_topic(SystemOrigin::Signed(caller), topic_id)
+	verify {
+		assert!(TopicOutcomes::<T, I>::contains_key(topic_id));
+	}
+
+	finalize_topic {
+		let o in 2 .. T::TopicOptionMaximumNumber::get();
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser, o, 1);
+		for i in 0..o {
+			TopicOptionCalls::<T, I>::insert(
+				topic_id,
+				T::OptionIndex::from(u64::from(i)),
+				T::Preimages::bound(remark_call::<T, I>())?,
+			);
+		}
+		BallotBox::<T, I>::insert(topic_id, T::OptionIndex::from(0u64), T::Vote::from(1u32));
+		TopicTurnout::<T, I>::insert(topic_id, T::MinEnactmentTurnout::get());
+		set_now::<T>(VOTING_PERIOD_END + 1);
+		let caller: T::AccountId = whitelisted_caller();
+		assert_ok!(Dao::<T, I>::close_topic(SystemOrigin::Signed(caller.clone()).into(), topic_id));
+	}: _(SystemOrigin::Signed(caller), topic_id)
+	verify {
+		assert_last_event::<T, I>(Event::TopicFinalized { id: topic_id }.into());
+	}
+
+	set_option_call {
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), 2, 1);
+		let option = T::OptionIndex::from(0u64);
+		let call = T::Preimages::bound(remark_call::<T, I>())?;
+		TopicOptionCalls::<T, I>::insert(topic_id, option, call);
+	}: _(SystemOrigin::Signed(raiser), topic_id, option, Some(Box::new(remark_call::<T, I>())))
+	verify {
+		assert!(TopicOptionCalls::<T, I>::contains_key(topic_id, option));
+	}
+
+	slash_topic {
+		let o in 2 .. T::TopicOptionMaximumNumber::get();
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), o, 1);
+		for i in 0..o {
+			TopicOptionCalls::<T, I>::insert(
+				topic_id,
+				T::OptionIndex::from(u64::from(i)),
+				T::Preimages::bound(remark_call::<T, I>())?,
+			);
+		}
+		let amount = T::TopicDepositBase::get();
+		T::Currency::deposit_creating(&raiser, amount);
+		T::Currency::reserve(&raiser, amount)?;
+		TopicDepositOf::<T, I>::insert(topic_id, amount);
+		let origin = T::ForceOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let call = Call::<T, I>::slash_topic { topic_id };
+	}: { call.dispatch_bypass_filter(origin)? }
+	verify {
+		assert_last_event::<T, I>(Event::TopicSlashed { id: topic_id, raiser, amount }.into());
+	}
+
+	vote_topic_with_lock {
+		let o in 1 .. T::TopicOptionMaximumNumber::get() - 1;
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), o + 1, o);
+		TopicVotingMode::<T, I>::insert(topic_id, VotingMode::Locked);
+		let voter = funded_account::<T, I>("voter", 0);
+		assert_ok!(Dao::<T, I>::issue_voting_right_token(
+			SystemOrigin::Signed(raiser).into(),
+			topic_id,
+			vec![voter.clone()],
+			None,
+		));
+		set_now::<T>(VOTING_PERIOD_START);
+		let options: BoundedVec<_, _> = (0..o)
+			.map(|i| T::OptionIndex::from(u64::from(i)))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("fits in TopicOptionMaximumNumber");
+		let amount = T::Currency::minimum_balance();
+	}: _(SystemOrigin::Signed(voter.clone()), topic_id, options, amount, Conviction::Locked6x)
+	verify {
+		assert_eq!(VotingLocks::<T, I>::get(&voter).len(), 1);
+	}
+
+	set_voting_mode {
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), 2, 1);
+	}: _(SystemOrigin::Signed(raiser), topic_id, VotingMode::Locked)
+	verify {
+		assert_eq!(TopicVotingMode::<T, I>::get(topic_id), VotingMode::Locked);
+	}
+
+	unlock_vote {
+		let l in 1 .. T::MaxVoteLocks::get();
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser, 2, 1);
+		let voter = funded_account::<T, I>("voter", 0);
+		let amount = T::Currency::minimum_balance();
+		// The lock to remove comes last, behind locks on other topics.
+		let mut other_topic_id = topic_id;
+		let locks: BoundedVec<_, _> = (1..l)
+			.map(|_| {
+				other_topic_id = other_topic_id.increment();
+				VoteLock { topic_id: other_topic_id, amount, unlock_at: VOTING_PERIOD_END }
+			})
+			.chain(sp_std::iter::once(VoteLock { topic_id, amount, unlock_at: VOTING_PERIOD_END }))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("fits in MaxVoteLocks");
+		VotingLocks::<T, I>::insert(&voter, locks);
+		T::Currency::set_lock(T::VoteLockId::get(), &voter, amount, WithdrawReasons::all());
+		set_now::<T>(VOTING_PERIOD_END + 1);
+		let caller: T::AccountId = whitelisted_caller();
+		assert_ok!(Dao::<T, I>::close_topic(SystemOrigin::Signed(caller).into(), topic_id));
+	}: _(SystemOrigin::Signed(voter.clone()), topic_id)
+	verify {
+		assert_eq!(VotingLocks::<T, I>::get(&voter).len() as u32, l - 1);
+	}
+
+	revoke_voting_right_token {
+		let o in 2 .. T::TopicOptionMaximumNumber::get();
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), o, o - 1);
+		let voter = funded_account::<T, I>("voter", 0);
+		let delegate = funded_account::<T, I>("delegate", 0);
+		assert_ok!(Dao::<T, I>::issue_voting_right_token(
+			SystemOrigin::Signed(raiser.clone()).into(),
+			topic_id,
+			vec![voter.clone(), delegate.clone()],
+			None,
+		));
+		// The weight of the token is counted on the ballot of a delegate voting every option it
+		// can, so that it is withdrawn from as many options as possible.
+		assert_ok!(Dao::<T, I>::delegate(
+			SystemOrigin::Signed(voter.clone()).into(),
+			delegate.clone(),
+			DelegationScope::Global,
+		));
+		set_now::<T>(VOTING_PERIOD_START);
+		let options: BoundedVec<_, _> = (0..o - 1)
+			.map(|i| T::OptionIndex::from(u64::from(i)))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("fits in TopicOptionMaximumNumber");
+		assert_ok!(Dao::<T, I>::vote_topic(
+			SystemOrigin::Signed(delegate).into(),
+			topic_id,
+			options,
+		));
+		assert!(DelegatedVotes::<T, I>::contains_key(topic_id, &voter));
+	}: _(SystemOrigin::Signed(raiser), topic_id, voter.clone())
+	verify {
+		assert_last_event::<T, I>(Event::VotingRightTokenRevoked { topic_id, voter }.into());
+	}
+
+	add_topic_issuer {
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), 2, 1);
+		let issuers: BoundedVec<_, _> = (1..T::MaxIssuersPerTopic::get())
+			.map(|i| account("issuer", i, SEED))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("fits in MaxIssuersPerTopic");
+		TopicIssuers::<T, I>::insert(topic_id, issuers);
+		let issuer: T::AccountId = account("issuer", 0, SEED);
+	}: _(SystemOrigin::Signed(raiser), topic_id, issuer.clone())
+	verify {
+		assert_last_event::<T, I>(Event::TopicIssuerAdded { topic_id, issuer }.into());
+	}
+
+	remove_topic_issuer {
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), 2, 1);
+		let issuers: BoundedVec<T::AccountId, _> = (0..T::MaxIssuersPerTopic::get())
+			.map(|i| account("issuer", i, SEED))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("fits in MaxIssuersPerTopic");
+		let issuer = issuers.last().cloned().expect("at least one issuer");
+		TopicIssuers::<T, I>::insert(topic_id, issuers);
+	}: _(SystemOrigin::Signed(raiser), topic_id, issuer.clone())
+	verify {
+		assert_last_event::<T, I>(Event::TopicIssuerRemoved { topic_id, issuer }.into());
+	}
+
+	set_tally_method {
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), 2, 1);
+		let method = TallyMethod::Threshold { quorum: 1, approval: Perbill::from_percent(50) };
+	}: _(SystemOrigin::Signed(raiser), topic_id, method)
+	verify {
+		assert_last_event::<T, I>(Event::TopicTallyMethodSet { id: topic_id, method }.into());
+	}
+
+	on_initialize {
+		let a in 0 .. T::MaxActiveTopics::get();
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		// Only topics raised through `ForceOrigin` take every slot.
+		for _ in 0..a {
+			assert_ok!(Dao::<T, I>::do_raise_topic(
+				raiser.clone(),
+				vec![b'a'; T::TopicTitleMaximumLength::get() as usize],
+				vec![b'a'; T::TopicDescriptionMaximumLength::get() as usize],
+				VOTING_PERIOD_START,
+				VOTING_PERIOD_END,
+				(0..2).map(option_name::<T, I>).collect(),
+				1,
+				Zero::zero(),
+				true,
+			));
+		}
+		set_now::<T>(VOTING_PERIOD_START);
+	}: {
+		Dao::<T, I>::on_initialize(VOTING_PERIOD_START.saturated_into());
+	}
+	verify {
+		assert!(ActiveTopics::<T, I>::get().iter().all(|topic| topic.opened));
+	}
+
+	commit_vote {
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), 2, 1);
+		TopicVotingMode::<T, I>::insert(topic_id, VotingMode::CommitReveal);
+		let voter = funded_account::<T, I>("voter", 0);
+		assert_ok!(Dao::<T, I>::issue_voting_right_token(
+			SystemOrigin::Signed(raiser).into(),
+			topic_id,
+			vec![voter.clone()],
+			None,
+		));
+		set_now::<T>(VOTING_PERIOD_START);
+		let commitment = T::Hashing::hash_of(&0u32);
+	}: _(SystemOrigin::Signed(voter.clone()), topic_id, commitment)
+	verify {
+		assert_eq!(VoteCommitments::<T, I>::get(topic_id, &voter), Some(commitment));
+	}
+
+	reveal_vote {
+		let o in 1 .. T::TopicOptionMaximumNumber::get() - 1;
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), o + 1, o);
+		assert_ok!(Dao::<T, I>::set_voting_mode(
+			SystemOrigin::Signed(raiser.clone()).into(),
+			topic_id,
+			VotingMode::CommitReveal,
+		));
+		let voter = funded_account::<T, I>("voter", 0);
+		assert_ok!(Dao::<T, I>::issue_voting_right_token(
+			SystemOrigin::Signed(raiser).into(),
+			topic_id,
+			vec![voter.clone()],
+			None,
+		));
+		let options: BoundedVec<_, _> = (0..o)
+			.map(|i| T::OptionIndex::from(u64::from(i)))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("fits in TopicOptionMaximumNumber");
+		let salt = [7u8; 32];
+		set_now::<T>(VOTING_PERIOD_START);
+		assert_ok!(Dao::<T, I>::commit_vote(
+			SystemOrigin::Signed(voter.clone()).into(),
+			topic_id,
+			T::Hashing::hash_of(&(&options, &salt)),
+		));
+		set_now::<T>(VOTING_PERIOD_END + 1);
+	}: _(SystemOrigin::Signed(voter), topic_id, options, salt)
+	verify {
+		assert_eq!(TopicTurnout::<T, I>::get(topic_id), 1);
+	}
+
+	delegate {
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser, 2, 1);
+		let delegator: T::AccountId = whitelisted_caller();
+		let previous: T::AccountId = account("delegate", 0, SEED);
+		let delegate: T::AccountId = account("delegate", 1, SEED);
+		let scope = DelegationScope::Topic(topic_id);
+		assert_ok!(Dao::<T, I>::delegate(
+			SystemOrigin::Signed(delegator.clone()).into(),
+			previous,
+			scope,
+		));
+		for i in 1..T::MaxDelegators::get() {
+			Delegators::<T, I>::mutate(&delegate, |delegators| {
+				delegators.try_push((account("delegator", i, SEED), scope))
+			})
+			.expect("fits in MaxDelegators");
+		}
+	}: _(SystemOrigin::Signed(delegator.clone()), delegate.clone(), scope)
+	verify {
+		assert_last_event::<T, I>(Event::Delegated { delegator, delegate, scope }.into());
+	}
+
+	undelegate {
+		let delegator: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let scope = DelegationScope::Global;
+		for i in 1..T::MaxDelegators::get() {
+			Delegators::<T, I>::mutate(&delegate, |delegators| {
+				delegators.try_push((account("delegator", i, SEED), scope))
+			})
+			.expect("fits in MaxDelegators");
+		}
+		assert_ok!(Dao::<T, I>::delegate(
+			SystemOrigin::Signed(delegator.clone()).into(),
+			delegate,
+			scope,
+		));
+	}: _(SystemOrigin::Signed(delegator.clone()), scope)
+	verify {
+		assert_last_event::<T, I>(Event::Undelegated { delegator, scope }.into());
+	}
+
+	clear_topic {
+		let n in 0 .. T::RemoveItemsLimit::get();
+		let o in 0 .. T::TopicOptionMaximumNumber::get();
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), 2, 1);
+		for i in 0..n {
+			let voter: T::AccountId = account("voter", i, SEED);
+			VotingRightTokenCollection::<T, I>::insert(topic_id, voter, T::Vote::from(1u32));
+		}
+		for i in 0..o {
+			TopicOptionCalls::<T, I>::insert(
+				topic_id,
+				T::OptionIndex::from(u64::from(i)),
+				T::Preimages::bound(remark_call::<T, I>())?,
+			);
+		}
+		let amount = T::TopicDepositBase::get();
+		T::Currency::deposit_creating(&raiser, amount);
+		T::Currency::reserve(&raiser, amount)?;
+		TopicDepositOf::<T, I>::insert(topic_id, amount);
+		set_now::<T>(VOTING_PERIOD_END + 1);
+		let caller: T::AccountId = whitelisted_caller();
+		assert_ok!(Dao::<T, I>::close_topic(SystemOrigin::Signed(caller.clone()).into(), topic_id));
+		set_now::<T>(VOTING_PERIOD_END + T::TopicRetentionPeriod::get());
+	}: _(SystemOrigin::Signed(caller), topic_id)
+	verify {
+		assert_last_event::<T, I>(Event::TopicCleared { id: topic_id }.into());
+		assert!(!TopicCollection::<T, I>::contains_key(topic_id));
+	}
+
+	edit_topic {
+		let t in (T::TopicTitleMinimumLength::get()) .. T::TopicTitleMaximumLength::get();
+		let d in
+			(T::TopicDescriptionMinimumLength::get()) .. T::TopicDescriptionMaximumLength::get();
+		let o in 2 .. T::TopicOptionMaximumNumber::get();
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), o, 1);
+		for i in 0..o {
+			TopicOptionCalls::<T, I>::insert(
+				topic_id,
+				T::OptionIndex::from(u64::from(i)),
+				T::Preimages::bound(remark_call::<T, I>())?,
+			);
+		}
+		let title = vec![b'b'; t as usize];
+		let description = vec![b'b'; d as usize];
+		// Keep two options so that the calls attached to the others are dropped.
+		let options = (0..2).map(option_name::<T, I>).collect::<Vec<_>>();
+		T::Currency::deposit_creating(
+			&raiser,
+			Dao::<T, I>::topic_deposit(&title, &description, &options),
+		);
+	}: _(
+		SystemOrigin::Signed(raiser),
+		topic_id,
+		title,
+		description,
+		VOTING_PERIOD_START,
+		VOTING_PERIOD_END,
+		options,
+		1
+	)
+	verify {
+		assert_last_event::<T, I>(Event::TopicEdited { id: topic_id }.into());
+	}
+
+	cancel_topic {
+		let o in 2 .. T::TopicOptionMaximumNumber::get();
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), o, 1);
+		for i in 0..o {
+			TopicOptionCalls::<T, I>::insert(
+				topic_id,
+				T::OptionIndex::from(u64::from(i)),
+				T::Preimages::bound(remark_call::<T, I>())?,
+			);
+		}
+		let amount = T::TopicDepositBase::get();
+		T::Currency::deposit_creating(&raiser, amount);
+		T::Currency::reserve(&raiser, amount)?;
+		TopicDepositOf::<T, I>::insert(topic_id, amount);
+	}: _(SystemOrigin::Signed(raiser), topic_id)
+	verify {
+		assert_last_event::<T, I>(Event::TopicCancelled { id: topic_id }.into());
+	}
+
+	set_weight_source {
+		set_now::<T>(NOW);
+		let raiser = funded_account::<T, I>("raiser", 0);
+		let topic_id = raise::<T, I>(raiser.clone(), 2, 1);
+	}: _(SystemOrigin::Signed(raiser), topic_id, WeightSource::Native)
+	verify {
+		assert_last_event::<T, I>(
+			Event::TopicWeightSourceSet { id: topic_id, source: WeightSource::Native }.into(),
+		);
+	}
+
+	impl_benchmark_test_suite!(Dao, crate::mock::new_test_ext(), crate::mock::Test);
+}
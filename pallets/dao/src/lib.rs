@@ -1,7 +1,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod benchmarking;
 mod macros;
+pub mod migration;
 mod types;
+pub mod weights;
 
 #[cfg(test)]
 mod mock;
@@ -9,7 +12,15 @@ mod mock;
 mod tests;
 
 pub use pallet::*;
-pub use types::{Incrementable, TopicDetails, TopicVotingResult};
+pub use types::{
+	ActiveTopic, ClearCursor, Conviction, DelegationScope, Incrementable, PeriodMode, RawOrigin,
+	TallyMethod, TopicDetails, TopicInfo, TopicOutcome, TopicResults, TopicStatus,
+	TopicVotingResult, VoteLock, VoterStatus, VotingMode, WeightSource, MAX_CLEAR_CURSOR_LENGTH,
+};
+pub use weights::WeightInfo;
+
+use frame_support::traits::EnsureOrigin;
+use sp_std::marker::PhantomData;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -18,20 +29,44 @@ pub mod pallet {
 
 	use frame_support::{
 		pallet_prelude::*,
-		traits::{tokens::currency::Currency, UnixTime},
+		traits::{
+			schedule::{self, v3::Anon as ScheduleAnon, DispatchTime},
+			tokens::{
+				currency::{Currency, LockIdentifier, LockableCurrency, ReservableCurrency},
+				fungibles, nonfungibles_v2,
+			},
+			Bounded, DefensiveTruncateFrom, OnUnbalanced, QueryPreimage, StorePreimage, UnixTime,
+			WithdrawReasons,
+		},
 	};
 	use frame_system::pallet_prelude::*;
-	use sp_runtime::traits::{AtLeast32Bit, SaturatedConversion};
-	use sp_std::vec::Vec;
+	use sp_core::H256;
+	use sp_runtime::{
+		traits::{AtLeast32Bit, Hash, IntegerSquareRoot, SaturatedConversion, Saturating, Zero},
+		PerThing,
+	};
+	use sp_std::{boxed::Box, vec::Vec};
 
-	use crate::{Incrementable, TopicDetails, TopicVotingResult};
+	use crate::{
+		migration::STORAGE_VERSION, ActiveTopic, ClearCursor, Conviction, DelegationScope,
+		Incrementable, PeriodMode, RawOrigin, TallyMethod, TopicDetails, TopicInfo, TopicOutcome,
+		TopicResults, TopicStatus, TopicVotingResult, VoteLock, VoterStatus, VotingMode,
+		WeightInfo, WeightSource, MAX_CLEAR_CURSOR_LENGTH,
+	};
 
-	const ONE_HOUR: u64 = 60 * 60;
-	const THREE_HOURS: u64 = 3 * 60 * 60;
-	const THREE_MONTHS: u64 = 3 * 730 * ONE_HOUR;
+	/// Scheduler priority of the call enacted by a finalized topic.
+	const ENACTMENT_PRIORITY: schedule::Priority = 63;
 
 	type BalanceOf<T, I> =
 		<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	type NegativeImbalanceOf<T, I> = <<T as Config<I>>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	pub type CallOf<T> = <T as frame_system::Config>::RuntimeCall;
+	pub type BoundedCallOf<T> = Bounded<CallOf<T>>;
+	pub type WeightSourceOf<T, I> =
+		WeightSource<<T as Config<I>>::AssetId, <T as Config<I>>::CollectionId>;
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -52,6 +87,106 @@ pub mod pallet {
 			voter: T::AccountId,
 			voted_options: BoundedVec<T::OptionIndex, T::TopicOptionMaximumNumber>,
 		},
+
+		/// A topic was closed and its ballot box tallied
+		TopicClosed {
+			id: T::TopicId,
+			winning_options: BoundedVec<T::OptionIndex, T::TopicOptionMaximumNumber>,
+			turnout: u32,
+		},
+
+		/// A closed topic was finalized
+		TopicFinalized { id: T::TopicId },
+
+		/// A call was attached to, or with `call_hash: None` removed from, a topic option
+		TopicOptionCallSet { id: T::TopicId, option: T::OptionIndex, call_hash: Option<H256> },
+
+		/// The call attached to the winning option of a finalized topic was scheduled
+		TopicEnactmentScheduled { id: T::TopicId, option: T::OptionIndex },
+
+		/// The call attached to the winning option of a finalized topic was dropped, as the
+		/// turnout is below `MinEnactmentTurnout`
+		TopicEnactmentSkipped { id: T::TopicId, option: T::OptionIndex, turnout: u32 },
+
+		/// The deposit of a finalized topic was returned to its raiser
+		TopicDepositRefunded { id: T::TopicId, raiser: T::AccountId, amount: BalanceOf<T, I> },
+
+		/// A topic was cancelled and its deposit slashed
+		TopicSlashed { id: T::TopicId, raiser: T::AccountId, amount: BalanceOf<T, I> },
+
+		/// The voting mode of a topic was changed
+		TopicVotingModeSet { id: T::TopicId, mode: VotingMode },
+
+		/// A voter locked balance to vote on a topic
+		VoteLocked {
+			topic_id: T::TopicId,
+			voter: T::AccountId,
+			amount: BalanceOf<T, I>,
+			conviction: Conviction,
+			unlock_at: u64,
+		},
+
+		/// The balance a voter locked to vote on a topic was unlocked
+		VoteUnlocked { topic_id: T::TopicId, voter: T::AccountId, amount: BalanceOf<T, I> },
+
+		/// An unspent voting right token was revoked
+		VotingRightTokenRevoked { topic_id: T::TopicId, voter: T::AccountId },
+
+		/// An account was allowed to issue voting right tokens on a topic
+		TopicIssuerAdded { topic_id: T::TopicId, issuer: T::AccountId },
+
+		/// An account was no longer allowed to issue voting right tokens on a topic
+		TopicIssuerRemoved { topic_id: T::TopicId, issuer: T::AccountId },
+
+		/// The tally method of a topic was changed
+		TopicTallyMethodSet { id: T::TopicId, method: TallyMethod },
+
+		/// The voting period of a topic started
+		TopicOpened { id: T::TopicId },
+
+		/// A voter committed to a sealed ballot
+		VoteCommitted { topic_id: T::TopicId, voter: T::AccountId },
+
+		/// An account delegated its votes
+		Delegated {
+			delegator: T::AccountId,
+			delegate: T::AccountId,
+			scope: DelegationScope<T::TopicId>,
+		},
+
+		/// An account took its votes back
+		Undelegated { delegator: T::AccountId, scope: DelegationScope<T::TopicId> },
+
+		/// The vote weight of delegators was added to the ballot of their delegate
+		DelegatedVotesCounted {
+			topic_id: T::TopicId,
+			delegate: T::AccountId,
+			delegators: u32,
+			vote_weight: T::Vote,
+		},
+
+		/// A delegator voted directly and its vote weight was taken off the ballot of its delegate
+		DelegatedVoteWithdrawn {
+			topic_id: T::TopicId,
+			delegator: T::AccountId,
+			delegate: T::AccountId,
+			vote_weight: T::Vote,
+		},
+
+		/// Voter entries of a topic whose retention period is over were removed
+		TopicEntriesRemoved { id: T::TopicId, removed: u32 },
+
+		/// A topic was removed from storage; only its outcome is kept
+		TopicCleared { id: T::TopicId },
+
+		/// The contents or voting period of a topic were amended before voting opened
+		TopicEdited { id: T::TopicId },
+
+		/// The source of the vote weight of a topic was changed
+		TopicWeightSourceSet { id: T::TopicId, source: WeightSourceOf<T, I> },
+
+		/// A topic was cancelled and its deposit returned
+		TopicCancelled { id: T::TopicId },
 	}
 
 	#[pallet::error]
@@ -121,6 +256,85 @@ pub mod pallet {
 
 		/// A voting right token has been issued to particular voter.
 		VotingRightTokenIssued,
+
+		/// Voting period has not ended yet.
+		VoteNotEnded,
+
+		/// The topic's status does not allow this operation.
+		InvalidTopicStatus,
+
+		/// The origin is neither the topic raiser nor `ForceOrigin`, or only `ForceOrigin` may
+		/// manage the topic.
+		NoPermission,
+
+		/// Voting period has already started.
+		VoteAlreadyOpen,
+
+		/// Too many voters in a single voting right token issuance.
+		TooManyVoters,
+
+		/// Topic raiser's free balance cannot cover the topic deposit.
+		InsufficientDeposit,
+
+		/// The topic's voting mode does not allow this way of voting.
+		InvalidVotingMode,
+
+		/// Voter's free balance cannot cover the balance to lock.
+		InsufficientFunds,
+
+		/// Voter has too many locked votes; unlock some of them first.
+		TooManyVoteLocks,
+
+		/// Voter has no balance locked for the topic.
+		NoVoteLock,
+
+		/// The locked balance cannot be unlocked yet.
+		VoteLockNotExpired,
+
+		/// The account is already an issuer of the topic.
+		AlreadyIssuer,
+
+		/// The account is not an issuer of the topic.
+		NotIssuer,
+
+		/// The topic has too many issuers.
+		TooManyIssuers,
+
+		/// Too many topics are waiting for their voting period to end.
+		TooManyActiveTopics,
+
+		/// The voter has not committed to a ballot on the topic.
+		NoVoteCommitment,
+
+		/// The revealed options and salt do not match the commitment.
+		InvalidVoteReveal,
+
+		/// An account cannot delegate to itself.
+		SelfDelegation,
+
+		/// The delegate has too many delegators.
+		TooManyDelegators,
+
+		/// The account has not delegated in this scope.
+		NotDelegated,
+
+		/// The retention period of the topic is not over yet.
+		RetentionPeriodNotOver,
+
+		/// The topic was cancelled.
+		TopicCancelled,
+
+		/// The asset does not exist.
+		UnknownAsset,
+
+		/// The collection does not exist.
+		UnknownCollection,
+
+		/// The raiser has too many topics waiting for their voting period to end.
+		TooManyActiveTopicsOfRaiser,
+
+		/// The turnout of the topics raised before the v1 migration is still being counted.
+		TurnoutBeingCounted,
 	}
 
 	#[pallet::storage]
@@ -152,13 +366,172 @@ pub mod pallet {
 	pub type VotingRightTokenCollection<T: Config<I>, I: 'static = ()> =
 		StorageDoubleMap<_, Blake2_128Concat, T::TopicId, Blake2_128Concat, T::AccountId, T::Vote>;
 
+	/// Number of voters who have cast a ballot on a topic.
+	#[pallet::storage]
+	pub type TopicTurnout<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::TopicId, u32, ValueQuery>;
+
+	/// Outcome of a closed topic.
+	#[pallet::storage]
+	#[pallet::getter(fn get_outcome)]
+	pub type TopicOutcomes<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::TopicId,
+		TopicOutcome<T::OptionIndex, T::Vote, T::TopicOptionMaximumNumber>,
+	>;
+
+	/// Deposit reserved from the raiser of a topic.
+	#[pallet::storage]
+	pub type TopicDepositOf<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::TopicId, BalanceOf<T, I>>;
+
+	/// How the vote weight of a topic's voters is determined.
+	#[pallet::storage]
+	pub type TopicVotingMode<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::TopicId, VotingMode, ValueQuery>;
+
+	/// Balance locked by each voter for topics with [`VotingMode::Locked`].
+	#[pallet::storage]
+	pub type VotingLocks<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<VoteLock<T::TopicId, BalanceOf<T, I>>, T::MaxVoteLocks>,
+		ValueQuery,
+	>;
+
+	/// Topics whose voting period has not ended yet, opened and closed by the pallet hooks.
+	#[pallet::storage]
+	pub type ActiveTopics<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<ActiveTopic<T::TopicId>, T::MaxActiveTopics>, ValueQuery>;
+
+	/// Ballots cast on topics counted with [`TallyMethod::RankedChoice`]: the options in order of
+	/// preference and the vote weight.
+	#[pallet::storage]
+	pub type RankedBallots<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::TopicId,
+		Blake2_128Concat,
+		T::AccountId,
+		(BoundedVec<T::OptionIndex, T::TopicOptionMaximumNumber>, T::Vote),
+	>;
+
+	/// Commitments to the sealed ballots of topics with [`VotingMode::CommitReveal`]: the hash of
+	/// the options and a salt, removed when the ballot is revealed.
+	#[pallet::storage]
+	pub type VoteCommitments<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Blake2_128Concat, T::TopicId, Blake2_128Concat, T::AccountId, T::Hash>;
+
+	/// The delegate of each delegator, per scope.
+	#[pallet::storage]
+	pub type Delegations<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		DelegationScope<T::TopicId>,
+		T::AccountId,
+	>;
+
+	/// The delegators of each delegate and the scope of their delegation.
+	#[pallet::storage]
+	pub type Delegators<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(T::AccountId, DelegationScope<T::TopicId>), T::MaxDelegators>,
+		ValueQuery,
+	>;
+
+	/// Vote weight of delegators counted on the ballot of their delegate, per topic: the delegate
+	/// and the weight. Withdrawn when the delegator votes directly.
+	#[pallet::storage]
+	pub type DelegatedVotes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::TopicId,
+		Blake2_128Concat,
+		T::AccountId,
+		(T::AccountId, T::Vote),
+	>;
+
+	/// Options chosen by delegates who carry the vote weight of delegators, on topics not counted
+	/// with [`TallyMethod::RankedChoice`].
+	#[pallet::storage]
+	pub type DelegateBallots<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::TopicId,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::OptionIndex, T::TopicOptionMaximumNumber>,
+	>;
+
+	/// Accounts the raiser allowed to issue voting right tokens on a topic.
+	#[pallet::storage]
+	pub type TopicIssuers<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::TopicId,
+		BoundedVec<T::AccountId, T::MaxIssuersPerTopic>,
+		ValueQuery,
+	>;
+
+	/// Calls dispatched from [`RawOrigin::Topic`] or [`RawOrigin::ForcedTopic`] when the
+	/// corresponding option wins a topic.
+	#[pallet::storage]
+	pub type TopicOptionCalls<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::TopicId,
+		Blake2_128Concat,
+		T::OptionIndex,
+		BoundedCallOf<T>,
+	>;
+
+	/// What the voting right tokens of a topic are weighed with.
+	#[pallet::storage]
+	pub type TopicWeightSource<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::TopicId, WeightSourceOf<T, I>, ValueQuery>;
+
+	/// Progress of the removal of the voter entries of topics being cleared.
+	#[pallet::storage]
+	pub type TopicClearCursors<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::TopicId, ClearCursor>;
+
+	/// Topics raised through `ForceOrigin`. Only `ForceOrigin` manages them, and the call attached
+	/// to their winning option is dispatched from [`RawOrigin::ForcedTopic`].
+	#[pallet::storage]
+	pub type ForcedTopics<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::TopicId, ()>;
+
+	/// Number of topics tracked in [`ActiveTopics`] that an account raised, except through
+	/// `ForceOrigin`.
+	#[pallet::storage]
+	pub type ActiveTopicsOf<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The raw key of the last voting right token counted into the turnout of its topic by the
+	/// v1 migration, while it is still counting them.
+	#[pallet::storage]
+	pub type TurnoutCountCursor<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<u8, ConstU32<MAX_CLEAR_CURSOR_LENGTH>>>;
+
+	#[pallet::origin]
+	pub type Origin<T, I = ()> = RawOrigin<<T as Config<I>>::TopicId, I>;
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The time source of voting periods in [`PeriodMode::Timestamp`].
 		type UnixTime: UnixTime;
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>
+			+ LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
 
 		type RuntimeEvent: From<Event<Self, I>>
 			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
@@ -185,6 +558,28 @@ pub mod pallet {
 
 		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// The caller origin, overarching type of all pallets origins.
+		type PalletsOrigin: From<RawOrigin<Self::TopicId, I>>;
+
+		/// The scheduler used to enact the call attached to a topic's winning option.
+		type Scheduler: ScheduleAnon<Self::BlockNumber, CallOf<Self>, Self::PalletsOrigin>;
+
+		/// The preimage provider used to store the calls attached to topic options.
+		type Preimages: QueryPreimage + StorePreimage;
+
+		/// The minimum turnout of a topic for the call attached to its winning option to be
+		/// enacted.
+		///
+		/// The turnout counts accounts, which are free to create, so this only keeps calls of
+		/// topics hardly anyone voted on from being enacted. It is no protection against a raiser
+		/// issuing voting right tokens to its own accounts; privileged origins must only accept
+		/// [`RawOrigin::ForcedTopic`], see [`EnsureForcedTopic`].
+		#[pallet::constant]
+		type MinEnactmentTurnout: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
 		/// The minimum length of topic title on-chain.
 		#[pallet::constant]
 		type TopicTitleMinimumLength: Get<u32>;
@@ -223,12 +618,190 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type CurrencyUnits: Get<u128>;
+
+		/// The maximum number of voters in a single voting right token issuance.
+		#[pallet::constant]
+		type MaxVotersPerIssuance: Get<u32>;
+
+		/// The basic amount of funds that must be reserved when raising a topic.
+		#[pallet::constant]
+		type TopicDepositBase: Get<BalanceOf<Self, I>>;
+
+		/// The additional funds that must be reserved for every byte of title, description and
+		/// options of a topic.
+		#[pallet::constant]
+		type TopicDepositPerByte: Get<BalanceOf<Self, I>>;
+
+		/// Handler for the deposits slashed from abusive topics.
+		type Slashed: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
+
+		/// The identifier of the lock on the balance voters lock on topics.
+		#[pallet::constant]
+		type VoteLockId: Get<LockIdentifier>;
+
+		/// The time, in the unit of [`Config::PeriodMode`], a locked vote stays locked after the
+		/// voting period ends for each lock period of its [`Conviction`].
+		#[pallet::constant]
+		type VoteLockingPeriod: Get<u64>;
+
+		/// The maximum number of topics a voter can have balance locked for at the same time.
+		#[pallet::constant]
+		type MaxVoteLocks: Get<u32>;
+
+		/// The maximum number of accounts, besides the raiser, allowed to issue voting right
+		/// tokens on a topic.
+		#[pallet::constant]
+		type MaxIssuersPerTopic: Get<u32>;
+
+		/// The maximum number of voters on a topic counted with [`TallyMethod::RankedChoice`].
+		#[pallet::constant]
+		type MaxRankedChoiceVoters: Get<u32>;
+
+		/// The maximum number of options of a topic counted with [`TallyMethod::RankedChoice`].
+		///
+		/// Along with [`Config::MaxRankedChoiceVoters`], it bounds the work of tallying such a
+		/// topic when it is closed, including from `on_initialize`.
+		#[pallet::constant]
+		type MaxRankedChoiceOptions: Get<u32>;
+
+		/// The unit of voting periods and of the durations below.
+		///
+		/// Changing it turns the voting periods of existing topics into nonsense, so it must only
+		/// change while no topic is active.
+		#[pallet::constant]
+		type PeriodMode: Get<PeriodMode>;
+
+		/// The minimum time between raising a topic and the start of its voting period.
+		#[pallet::constant]
+		type MinVotingDelay: Get<u64>;
+
+		/// The maximum time between raising a topic and the start of its voting period.
+		#[pallet::constant]
+		type MaxVotingDelay: Get<u64>;
+
+		/// The minimum length of a voting period.
+		#[pallet::constant]
+		type MinVotingDuration: Get<u64>;
+
+		/// The maximum length of a voting period.
+		#[pallet::constant]
+		type MaxVotingDuration: Get<u64>;
+
+		/// The maximum number of topics whose voting period has not ended yet.
+		#[pallet::constant]
+		type MaxActiveTopics: Get<u32>;
+
+		/// How many of the `MaxActiveTopics` only topics raised through `ForceOrigin` can take,
+		/// so that the others filling up does not block governance.
+		#[pallet::constant]
+		type ReservedActiveTopics: Get<u32>;
+
+		/// The maximum number of topics whose voting period has not ended yet an account can
+		/// raise. Topics raised through `ForceOrigin` are not counted.
+		#[pallet::constant]
+		type MaxActiveTopicsPerRaiser: Get<u32>;
+
+		/// The maximum number of topics closed by the hooks in a block. The others are closed in
+		/// the following blocks.
+		#[pallet::constant]
+		type MaxAutoClosePerBlock: Get<u32>;
+
+		/// The length of the window following the voting period of a topic with
+		/// [`VotingMode::CommitReveal`], in which the sealed ballots are revealed.
+		#[pallet::constant]
+		type RevealPeriod: Get<u64>;
+
+		/// The maximum number of delegators of an account.
+		#[pallet::constant]
+		type MaxDelegators: Get<u32>;
+
+		/// How long a topic stays in storage after its voting period ends, in the unit of
+		/// [`Config::PeriodMode`]. Afterwards anyone can remove it with `clear_topic`, so it
+		/// should outlast `VoteLockingPeriod` times the largest conviction lock periods.
+		#[pallet::constant]
+		type TopicRetentionPeriod: Get<u64>;
+
+		/// The maximum number of voter entries removed by a single `clear_topic` call, and per
+		/// block while finishing the clearing of topics in `on_initialize`.
+		#[pallet::constant]
+		type RemoveItemsLimit: Get<u32>;
+
+		/// Identifier of the fungible assets topics can weigh votes with.
+		type AssetId: Member + Parameter + MaxEncodedLen + Copy;
+
+		/// The fungible assets topics can weigh votes with, see [`WeightSource::Asset`].
+		type Assets: fungibles::Inspect<Self::AccountId, AssetId = Self::AssetId>;
+
+		/// Identifier of the non-fungible collections topics can weigh votes with.
+		type CollectionId: Member + Parameter + MaxEncodedLen + Copy;
+
+		/// The non-fungible items topics can weigh votes with, see [`WeightSource::Collection`].
+		type Nfts: nonfungibles_v2::InspectEnumerable<
+			Self::AccountId,
+			CollectionId = Self::CollectionId,
+		>;
+
+		/// The maximum number of items of a collection counted for a single voter.
+		#[pallet::constant]
+		type MaxItemsPerVoter: Get<u32>;
+	}
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		/// Open the topics whose voting period started and close those whose voting period
+		/// ended, at most `MaxAutoClosePerBlock` of them, then carry on clearing the topics
+		/// being cleared, at most `RemoveItemsLimit` voter entries of them.
+		///
+		/// While the v1 migration is still counting the turnout of the topics raised before it,
+		/// at most `RemoveItemsLimit` voting right tokens per block, topics are only opened.
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			let mut weight = Self::count_turnout();
+			let counting = TurnoutCountCursor::<T, I>::exists();
+
+			let now = Self::now();
+			let active_topics = ActiveTopics::<T, I>::get();
+			weight.saturating_accrue(T::WeightInfo::on_initialize(active_topics.len() as u32));
+
+			let mut changed = false;
+			let mut closed = 0;
+			let mut still_active = Vec::with_capacity(active_topics.len());
+			for mut topic in active_topics.into_inner() {
+				if !counting && topic.ends_at() < now && closed < T::MaxAutoClosePerBlock::get() {
+					closed += 1;
+					changed = true;
+					weight.saturating_accrue(Self::release_raiser_slot(topic.id));
+					weight.saturating_accrue(Self::auto_close_topic(topic.id));
+					continue
+				}
+				if !topic.opened && topic.voting_period_start <= now {
+					topic.opened = true;
+					changed = true;
+					Self::auto_open_topic(topic.id);
+				}
+				still_active.push(topic);
+			}
+
+			if changed {
+				// Topics are only ever removed here, so they all fit.
+				ActiveTopics::<T, I>::put(BoundedVec::defensive_truncate_from(still_active));
+			}
+
+			if !counting {
+				weight.saturating_accrue(Self::clear_pending_topics());
+			}
+
+			weight
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		#[pallet::call_index(0)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::raise_topic(
+			title.len() as u32,
+			description.len() as u32,
+			options.len() as u32,
+		))]
 		pub fn raise_topic(
 			origin: OriginFor<T>,
 			title: Vec<u8>,
@@ -247,6 +820,8 @@ pub mod pallet {
 				Error::<T, I>::InsufficientBalance
 			);
 
+			let deposit = Self::topic_deposit(&title, &description, &options);
+
 			Self::do_raise_topic(
 				raiser,
 				title,
@@ -255,11 +830,22 @@ pub mod pallet {
 				voting_period_end,
 				options,
 				required_answer_number,
+				deposit,
+				false,
 			)
 		}
 
+		/// Raise a topic on behalf of `raiser` without a deposit.
+		///
+		/// Only `ForceOrigin` manages the topic: its raiser cannot edit or cancel it, attach calls,
+		/// issue voting right tokens or change how it is voted on. The call attached to its winning
+		/// option is dispatched from [`RawOrigin::ForcedTopic`].
 		#[pallet::call_index(3)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::force_raise_topic(
+			title.len() as u32,
+			description.len() as u32,
+			options.len() as u32,
+		))]
 		pub fn force_raise_topic(
 			origin: OriginFor<T>,
 			raiser: T::AccountId,
@@ -281,19 +867,28 @@ pub mod pallet {
 				voting_period_end,
 				options,
 				required_answer_number,
+				Zero::zero(),
+				true,
 			)
 		}
 
+		/// Issue voting right tokens to `voters`, weighing their holdings in the topic's
+		/// [`WeightSource`] times `weight_ratio_for_voters`.
 		#[pallet::call_index(1)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::issue_voting_right_token(
+			voters.len() as u32,
+			T::MaxItemsPerVoter::get(),
+		))]
 		pub fn issue_voting_right_token(
 			origin: OriginFor<T>,
 			topic_id: T::TopicId,
 			voters: Vec<T::AccountId>,
 			weight_ratio_for_voters: Option<u64>,
-		) -> DispatchResult {
-			// Make sure the caller is from a signed origin
-			let _raiser = frame_system::ensure_signed(origin)?;
+		) -> DispatchResultWithPostInfo {
+			ensure!(
+				voters.len() as u32 <= T::MaxVotersPerIssuance::get(),
+				Error::<T, I>::TooManyVoters
+			);
 
 			let weight_ratio_for_voters = weight_ratio_for_voters.unwrap_or(1);
 			ensure!(
@@ -307,22 +902,28 @@ pub mod pallet {
 			} else {
 				return Err(Error::<T, I>::UnknownTopic.into())
 			};
+			Self::ensure_issuer(origin, topic_id, &topic_details.raiser)?;
+			ensure!(topic_details.status != TopicStatus::Cancelled, Error::<T, I>::TopicCancelled);
 
 			{
-				let now = T::UnixTime::now().as_secs().saturated_into::<u64>();
+				let now = Self::now();
 				ensure!(now <= topic_details.voting_period_end, Error::<T, I>::VoteClosed);
 			}
 
+			let voter_count = voters.len() as u32;
+			let weight_source = TopicWeightSource::<T, I>::get(topic_id);
+			let mut items_counted = 0;
 			for voter in voters {
 				ensure!(
 					!VotingRightTokenCollection::<T, I>::contains_key(topic_id, &voter),
 					Error::<T, I>::VotingRightTokenIssued
 				);
 
-				let weight_per_required_option = (T::Currency::total_balance(&voter) *
-					weight_ratio_for_voters.saturated_into::<BalanceOf<T, I>>())
-				.saturated_into::<u128>()
-				.saturated_into::<T::Vote>();
+				let (holdings, items) = Self::holdings(weight_source, &voter);
+				items_counted = items_counted.max(items);
+				let weight_per_required_option = holdings
+					.saturating_mul(u128::from(weight_ratio_for_voters))
+					.saturated_into::<T::Vote>();
 
 				if weight_per_required_option > T::Vote::initial_value() {
 					VotingRightTokenCollection::<T, I>::insert(
@@ -338,46 +939,901 @@ pub mod pallet {
 					});
 				}
 			}
-			Ok(())
+
+			Ok(Some(T::WeightInfo::issue_voting_right_token(voter_count, items_counted)).into())
 		}
 
+		/// Vote on a topic with [`VotingMode::Snapshot`].
+		///
+		/// The vote weight of the accounts that delegated to the voter, and hold an unspent voting
+		/// right token, is added to the ballot.
 		#[pallet::call_index(2)]
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::vote_topic(
+			options.len() as u32,
+			T::MaxDelegators::get(),
+		))]
 		pub fn vote_topic(
 			origin: OriginFor<T>,
 			topic_id: T::TopicId,
 			options: BoundedVec<T::OptionIndex, T::TopicOptionMaximumNumber>,
-		) -> DispatchResult {
-			let topic_details = if let Some(topic_details) = TopicCollection::<T, I>::get(&topic_id)
-			{
-				topic_details
-			} else {
-				return Err(Error::<T, I>::UnknownTopic.into())
-			};
+		) -> DispatchResultWithPostInfo {
+			// Make sure the caller is from a signed origin
+			let voter = frame_system::ensure_signed(origin)?;
+
+			ensure!(
+				TopicVotingMode::<T, I>::get(topic_id) == VotingMode::Snapshot,
+				Error::<T, I>::InvalidVotingMode
+			);
+
+			let option_count = options.len() as u32;
+			Self::do_vote_topic(voter.clone(), topic_id, options.clone(), None)?;
+			let delegators = Self::count_delegated_votes(topic_id, voter, options)?;
+
+			Ok(Some(T::WeightInfo::vote_topic(option_count, delegators)).into())
+		}
+
+		/// Close a topic whose voting period has ended and record its outcome.
+		///
+		/// The ballots are tallied with the topic's [`TallyMethod`] into a [`TopicOutcome`] holding
+		/// the winning options and the turnout. Anyone can close a topic.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::close_topic(T::TopicOptionMaximumNumber::get()).max(
+			T::WeightInfo::close_ranked_choice_topic(
+				T::MaxRankedChoiceOptions::get(),
+				T::MaxRankedChoiceVoters::get(),
+			),
+		))]
+		pub fn close_topic(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+		) -> DispatchResultWithPostInfo {
+			frame_system::ensure_signed(origin)?;
+
+			let topic_details =
+				TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+			ensure!(
+				matches!(topic_details.status, TopicStatus::Pending | TopicStatus::Open),
+				Error::<T, I>::InvalidTopicStatus
+			);
+			ensure!(!TurnoutCountCursor::<T, I>::exists(), Error::<T, I>::TurnoutBeingCounted);
 
 			{
-				let now = T::UnixTime::now().as_secs().saturated_into::<u64>();
-				ensure!(topic_details.voting_period_start <= now, Error::<T, I>::VoteNotOpen);
-				ensure!(now <= topic_details.voting_period_end, Error::<T, I>::VoteClosed);
+				let now = Self::now();
+				let (_, ballot_period_end) = Self::ballot_period(topic_id, &topic_details);
+				ensure!(now > ballot_period_end, Error::<T, I>::VoteNotEnded);
 			}
 
-			// Make sure the caller is from a signed origin
-			let voter = frame_system::ensure_signed(origin)?;
+			Self::deactivate_topic(topic_id);
+			let weight = Self::do_close_topic(topic_id, topic_details)?;
 
-			let vote_weight = if let Some(vote_weight) =
-				VotingRightTokenCollection::<T, I>::get(topic_id, &voter)
-			{
-				ensure!(vote_weight > T::Vote::initial_value(), Error::<T, I>::VoterHasVoted);
-				vote_weight
-			} else {
-				return Err(Error::<T, I>::VoterHasNoVotingRight.into())
-			};
+			Ok(Some(weight).into())
+		}
 
-			{
-				let mut options = options.clone().into_inner();
-				options.sort_unstable();
-				let origin_len = options.len();
-				options.dedup();
+		/// Finalize a closed topic.
+		///
+		/// Between closing and finalizing, the outcome is fixed but not yet acted upon. If the
+		/// topic has a single winning option with an attached call and a turnout of at least
+		/// `MinEnactmentTurnout`, the call is scheduled for dispatch from [`RawOrigin::Topic`], or
+		/// [`RawOrigin::ForcedTopic`] for topics raised through `ForceOrigin`.
+		/// Anyone can finalize a closed topic.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::finalize_topic(T::TopicOptionMaximumNumber::get()))]
+		pub fn finalize_topic(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+		) -> DispatchResultWithPostInfo {
+			frame_system::ensure_signed(origin)?;
+
+			let (raiser, option_count) = TopicCollection::<T, I>::try_mutate(
+				topic_id,
+				|maybe_details| -> Result<(T::AccountId, u32), DispatchError> {
+					let details = maybe_details.as_mut().ok_or(Error::<T, I>::UnknownTopic)?;
+					ensure!(
+						details.status == TopicStatus::Closed,
+						Error::<T, I>::InvalidTopicStatus
+					);
+					details.status = TopicStatus::Finalized;
+					Ok((details.raiser.clone(), details.options.len() as u32))
+				},
+			)?;
+
+			if let Some(amount) = TopicDepositOf::<T, I>::take(topic_id) {
+				T::Currency::unreserve(&raiser, amount);
+				Self::deposit_event(Event::TopicDepositRefunded { id: topic_id, raiser, amount });
+			}
+
+			let winning_option = TopicOutcomes::<T, I>::get(topic_id).and_then(|outcome| {
+				match outcome.winning_options.as_slice() {
+					[option] if outcome.turnout >= T::MinEnactmentTurnout::get() => Some(*option),
+					[option] => {
+						if TopicOptionCalls::<T, I>::contains_key(topic_id, option) {
+							Self::deposit_event(Event::TopicEnactmentSkipped {
+								id: topic_id,
+								option: *option,
+								turnout: outcome.turnout,
+							});
+						}
+						None
+					},
+					_ => None,
+				}
+			});
+			Self::enact_topic(topic_id, winning_option)?;
+
+			Self::deposit_event(Event::TopicFinalized { id: topic_id });
+
+			Ok(Some(T::WeightInfo::finalize_topic(option_count)).into())
+		}
+
+		/// Attach a call to an option of a topic, or remove it by passing `None`.
+		///
+		/// The call is dispatched from [`RawOrigin::Topic`], or [`RawOrigin::ForcedTopic`] for
+		/// topics raised through `ForceOrigin`, when the topic is finalized with this option as its
+		/// only winner and a turnout of at least `MinEnactmentTurnout`. Only the raiser or
+		/// `ForceOrigin` can set it, and only before the voting period starts.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::set_option_call())]
+		pub fn set_option_call(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			option: T::OptionIndex,
+			call: Option<Box<CallOf<T>>>,
+		) -> DispatchResult {
+			let topic_details =
+				TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+			Self::ensure_raiser_or_force(origin, topic_id, &topic_details.raiser)?;
+
+			ensure!(
+				topic_details.status == TopicStatus::Pending,
+				Error::<T, I>::InvalidTopicStatus
+			);
+			{
+				let now = Self::now();
+				ensure!(now < topic_details.voting_period_start, Error::<T, I>::VoteAlreadyOpen);
+			}
+			ensure!(
+				option < T::OptionIndex::from(topic_details.options.len() as u64),
+				Error::<T, I>::InvalidOption
+			);
+
+			if let Some(old_call) = TopicOptionCalls::<T, I>::take(topic_id, option) {
+				T::Preimages::drop(&old_call);
+			}
+
+			let call_hash = match call {
+				Some(call) => {
+					let call = T::Preimages::bound(*call)?;
+					let call_hash = call.hash();
+					TopicOptionCalls::<T, I>::insert(topic_id, option, call);
+					Some(call_hash)
+				},
+				None => None,
+			};
+
+			Self::deposit_event(Event::TopicOptionCallSet { id: topic_id, option, call_hash });
+
+			Ok(())
+		}
+
+		/// Cancel an abusive topic and slash its deposit.
+		///
+		/// The topic's title, description and options are wiped and any attached calls are
+		/// dropped. Only topics that are not closed yet, or closed but not finalized, can be
+		/// slashed.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::slash_topic(T::TopicOptionMaximumNumber::get()))]
+		pub fn slash_topic(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			let (raiser, option_count) = TopicCollection::<T, I>::try_mutate(
+				topic_id,
+				|maybe_details| -> Result<(T::AccountId, u32), DispatchError> {
+					let details = maybe_details.as_mut().ok_or(Error::<T, I>::UnknownTopic)?;
+					ensure!(
+						matches!(
+							details.status,
+							TopicStatus::Pending | TopicStatus::Open | TopicStatus::Closed
+						),
+						Error::<T, I>::InvalidTopicStatus
+					);
+					let option_count = details.options.len() as u32;
+					details.status = TopicStatus::Cancelled;
+					details.title = Default::default();
+					details.description = Default::default();
+					details.options = Default::default();
+					Ok((details.raiser.clone(), option_count))
+				},
+			)?;
+
+			Self::deactivate_topic(topic_id);
+			for (_, call) in TopicOptionCalls::<T, I>::drain_prefix(topic_id) {
+				T::Preimages::drop(&call);
+			}
+
+			let amount = TopicDepositOf::<T, I>::take(topic_id).unwrap_or_else(Zero::zero);
+			if !amount.is_zero() {
+				let (imbalance, _) = T::Currency::slash_reserved(&raiser, amount);
+				T::Slashed::on_unbalanced(imbalance);
+			}
+
+			Self::deposit_event(Event::TopicSlashed { id: topic_id, raiser, amount });
+
+			Ok(Some(T::WeightInfo::slash_topic(option_count)).into())
+		}
+
+		/// Vote on a topic with [`VotingMode::Locked`] by locking `amount` of free balance.
+		///
+		/// The vote weighs `amount` scaled by `conviction`. The balance stays locked until the
+		/// topic is closed and, for convictions above [`Conviction::None`], for
+		/// `VoteLockingPeriod` times the lock periods of the conviction after the voting period
+		/// ends. The voter still needs a voting right token on the topic.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::vote_topic_with_lock(options.len() as u32))]
+		pub fn vote_topic_with_lock(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			options: BoundedVec<T::OptionIndex, T::TopicOptionMaximumNumber>,
+			amount: BalanceOf<T, I>,
+			conviction: Conviction,
+		) -> DispatchResult {
+			let voter = frame_system::ensure_signed(origin)?;
+
+			ensure!(
+				TopicVotingMode::<T, I>::get(topic_id) == VotingMode::Locked,
+				Error::<T, I>::InvalidVotingMode
+			);
+			ensure!(
+				!amount.is_zero() && amount <= T::Currency::free_balance(&voter),
+				Error::<T, I>::InsufficientFunds
+			);
+
+			let vote_weight =
+				conviction.votes(amount).saturated_into::<u128>().saturated_into::<T::Vote>();
+			Self::do_vote_topic(voter.clone(), topic_id, options, Some(vote_weight))?;
+
+			let voting_period_end = TopicCollection::<T, I>::get(&topic_id)
+				.ok_or(Error::<T, I>::UnknownTopic)?
+				.voting_period_end;
+			let unlock_at = voting_period_end.saturating_add(
+				T::VoteLockingPeriod::get().saturating_mul(conviction.lock_periods().into()),
+			);
+			VotingLocks::<T, I>::try_mutate(&voter, |locks| {
+				locks.try_push(VoteLock { topic_id, amount, unlock_at })
+			})
+			.map_err(|_| Error::<T, I>::TooManyVoteLocks)?;
+			Self::update_vote_lock(&voter);
+
+			Self::deposit_event(Event::VoteLocked {
+				topic_id,
+				voter,
+				amount,
+				conviction,
+				unlock_at,
+			});
+
+			Ok(())
+		}
+
+		/// Set the voting mode of a topic.
+		///
+		/// Only the raiser or `ForceOrigin` can set it, and only before the voting period starts.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::set_voting_mode())]
+		pub fn set_voting_mode(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			mode: VotingMode,
+		) -> DispatchResult {
+			let topic_details =
+				TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+			Self::ensure_raiser_or_force(origin, topic_id, &topic_details.raiser)?;
+
+			ensure!(
+				topic_details.status == TopicStatus::Pending,
+				Error::<T, I>::InvalidTopicStatus
+			);
+			{
+				let now = Self::now();
+				ensure!(now < topic_details.voting_period_start, Error::<T, I>::VoteAlreadyOpen);
+			}
+
+			TopicVotingMode::<T, I>::insert(topic_id, mode);
+			ActiveTopics::<T, I>::mutate(|topics| {
+				if let Some(topic) = topics.iter_mut().find(|topic| topic.id == topic_id) {
+					topic.reveal_period =
+						if mode == VotingMode::CommitReveal { T::RevealPeriod::get() } else { 0 };
+				}
+			});
+
+			Self::deposit_event(Event::TopicVotingModeSet { id: topic_id, mode });
+
+			Ok(())
+		}
+
+		/// Unlock the balance the caller locked to vote on a topic.
+		///
+		/// The topic must be closed and the lock expired, or the topic cancelled or removed.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::unlock_vote(T::MaxVoteLocks::get()))]
+		pub fn unlock_vote(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+		) -> DispatchResultWithPostInfo {
+			let voter = frame_system::ensure_signed(origin)?;
+
+			let expired = |unlock_at: u64| match TopicCollection::<T, I>::get(&topic_id)
+				.map(|details| details.status)
+			{
+				None | Some(TopicStatus::Cancelled) => true,
+				Some(TopicStatus::Closed | TopicStatus::Finalized) => Self::now() >= unlock_at,
+				Some(TopicStatus::Pending | TopicStatus::Open) => false,
+			};
+
+			let (amount, lock_count) = VotingLocks::<T, I>::try_mutate(
+				&voter,
+				|locks| -> Result<(BalanceOf<T, I>, u32), DispatchError> {
+					let lock_count = locks.len() as u32;
+					let index = locks
+						.iter()
+						.position(|lock| lock.topic_id == topic_id)
+						.ok_or(Error::<T, I>::NoVoteLock)?;
+					ensure!(expired(locks[index].unlock_at), Error::<T, I>::VoteLockNotExpired);
+					Ok((locks.remove(index).amount, lock_count))
+				},
+			)?;
+			Self::update_vote_lock(&voter);
+
+			Self::deposit_event(Event::VoteUnlocked { topic_id, voter, amount });
+
+			Ok(Some(T::WeightInfo::unlock_vote(lock_count)).into())
+		}
+
+		/// Revoke the voting right token of `voter` on a topic, as long as it is unspent.
+		///
+		/// If the weight of the token is already counted on the ballot of a delegate of `voter`,
+		/// it is withdrawn from there first. Anyone allowed to issue voting right tokens on the
+		/// topic can revoke them.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::revoke_voting_right_token(
+			T::TopicOptionMaximumNumber::get()
+		))]
+		pub fn revoke_voting_right_token(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			voter: T::AccountId,
+		) -> DispatchResult {
+			let topic_details =
+				TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+			Self::ensure_issuer(origin, topic_id, &topic_details.raiser)?;
+
+			ensure!(
+				matches!(topic_details.status, TopicStatus::Pending | TopicStatus::Open),
+				Error::<T, I>::VoteClosed
+			);
+
+			let vote_weight = VotingRightTokenCollection::<T, I>::get(topic_id, &voter)
+				.ok_or(Error::<T, I>::VoterHasNoVotingRight)?;
+			ensure!(vote_weight > T::Vote::initial_value(), Error::<T, I>::VoterHasVoted);
+			Self::withdraw_delegated_vote(topic_id, &voter, topic_details.tally_method);
+			VotingRightTokenCollection::<T, I>::remove(topic_id, &voter);
+
+			Self::deposit_event(Event::VotingRightTokenRevoked { topic_id, voter });
+
+			Ok(())
+		}
+
+		/// Allow `issuer` to issue and revoke voting right tokens on a topic.
+		///
+		/// Only the raiser or `ForceOrigin` can manage the issuers of a topic.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::add_topic_issuer())]
+		pub fn add_topic_issuer(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			issuer: T::AccountId,
+		) -> DispatchResult {
+			let topic_details =
+				TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+			Self::ensure_raiser_or_force(origin, topic_id, &topic_details.raiser)?;
+
+			TopicIssuers::<T, I>::try_mutate(topic_id, |issuers| -> DispatchResult {
+				ensure!(!issuers.contains(&issuer), Error::<T, I>::AlreadyIssuer);
+				issuers.try_push(issuer.clone()).map_err(|_| Error::<T, I>::TooManyIssuers)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::TopicIssuerAdded { topic_id, issuer });
+
+			Ok(())
+		}
+
+		/// Disallow `issuer` to issue and revoke voting right tokens on a topic.
+		///
+		/// Tokens it already issued stay valid. Only the raiser or `ForceOrigin` can manage the
+		/// issuers of a topic.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::remove_topic_issuer())]
+		pub fn remove_topic_issuer(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			issuer: T::AccountId,
+		) -> DispatchResult {
+			let topic_details =
+				TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+			Self::ensure_raiser_or_force(origin, topic_id, &topic_details.raiser)?;
+
+			TopicIssuers::<T, I>::try_mutate_exists(topic_id, |maybe_issuers| -> DispatchResult {
+				let issuers = maybe_issuers.as_mut().ok_or(Error::<T, I>::NotIssuer)?;
+				let index = issuers
+					.iter()
+					.position(|account| account == &issuer)
+					.ok_or(Error::<T, I>::NotIssuer)?;
+				issuers.remove(index);
+				if issuers.is_empty() {
+					*maybe_issuers = None;
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::TopicIssuerRemoved { topic_id, issuer });
+
+			Ok(())
+		}
+
+		/// Set the method used to count the ballots of a topic.
+		///
+		/// Only the raiser or `ForceOrigin` can set it, and only before the voting period starts.
+		/// [`TallyMethod::RankedChoice`] is limited to topics with at most
+		/// [`Config::MaxRankedChoiceOptions`] options.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::set_tally_method())]
+		pub fn set_tally_method(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			method: TallyMethod,
+		) -> DispatchResult {
+			let mut topic_details =
+				TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+			Self::ensure_raiser_or_force(origin, topic_id, &topic_details.raiser)?;
+
+			ensure!(
+				topic_details.status == TopicStatus::Pending,
+				Error::<T, I>::InvalidTopicStatus
+			);
+			{
+				let now = Self::now();
+				ensure!(now < topic_details.voting_period_start, Error::<T, I>::VoteAlreadyOpen);
+			}
+
+			if method == TallyMethod::RankedChoice {
+				ensure!(
+					topic_details.options.len() as u32 <= T::MaxRankedChoiceOptions::get(),
+					Error::<T, I>::OptionTooMany
+				);
+			}
+
+			topic_details.tally_method = method;
+			TopicCollection::<T, I>::insert(topic_id, topic_details);
+
+			Self::deposit_event(Event::TopicTallyMethodSet { id: topic_id, method });
+
+			Ok(())
+		}
+
+		/// Commit to a sealed ballot on a topic with [`VotingMode::CommitReveal`].
+		///
+		/// `commitment` is the hash, with the system hasher, of the SCALE encoded
+		/// `(options, salt)` the voter reveals with [`Pallet::reveal_vote`] once the voting period
+		/// ended. A commitment can be replaced until the voting period ends. Ballots that are
+		/// never revealed are not counted.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::commit_vote())]
+		pub fn commit_vote(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			commitment: T::Hash,
+		) -> DispatchResult {
+			let voter = frame_system::ensure_signed(origin)?;
+
+			let topic_details =
+				TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+			ensure!(
+				TopicVotingMode::<T, I>::get(topic_id) == VotingMode::CommitReveal,
+				Error::<T, I>::InvalidVotingMode
+			);
+			ensure!(topic_details.status != TopicStatus::Cancelled, Error::<T, I>::TopicCancelled);
+			ensure!(
+				matches!(topic_details.status, TopicStatus::Pending | TopicStatus::Open),
+				Error::<T, I>::VoteClosed
+			);
+			{
+				let now = Self::now();
+				ensure!(topic_details.voting_period_start <= now, Error::<T, I>::VoteNotOpen);
+				ensure!(now <= topic_details.voting_period_end, Error::<T, I>::VoteClosed);
+			}
+
+			let token_weight = VotingRightTokenCollection::<T, I>::get(topic_id, &voter)
+				.ok_or(Error::<T, I>::VoterHasNoVotingRight)?;
+			ensure!(token_weight > T::Vote::initial_value(), Error::<T, I>::VoterHasVoted);
+
+			VoteCommitments::<T, I>::insert(topic_id, &voter, commitment);
+
+			Self::deposit_event(Event::VoteCommitted { topic_id, voter });
+
+			Ok(())
+		}
+
+		/// Reveal the ballot committed to with [`Pallet::commit_vote`] and count it.
+		///
+		/// Only possible in the reveal window that follows the voting period.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::reveal_vote(options.len() as u32))]
+		pub fn reveal_vote(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			options: BoundedVec<T::OptionIndex, T::TopicOptionMaximumNumber>,
+			salt: [u8; 32],
+		) -> DispatchResult {
+			let voter = frame_system::ensure_signed(origin)?;
+
+			ensure!(
+				TopicVotingMode::<T, I>::get(topic_id) == VotingMode::CommitReveal,
+				Error::<T, I>::InvalidVotingMode
+			);
+			let commitment = VoteCommitments::<T, I>::take(topic_id, &voter)
+				.ok_or(Error::<T, I>::NoVoteCommitment)?;
+			ensure!(
+				T::Hashing::hash_of(&(&options, &salt)) == commitment,
+				Error::<T, I>::InvalidVoteReveal
+			);
+
+			Self::do_vote_topic(voter, topic_id, options, None)
+		}
+
+		/// Delegate the votes of the caller to `delegate` in `scope`, replacing the delegation
+		/// the caller may have in that scope.
+		///
+		/// The delegate adds the vote weight of the caller to its ballot when voting with
+		/// [`Pallet::vote_topic`]. Delegations are not transitive. The caller can still vote
+		/// directly, which withdraws its vote weight from the ballot of the delegate.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::delegate())]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			delegate: T::AccountId,
+			scope: DelegationScope<T::TopicId>,
+		) -> DispatchResult {
+			let delegator = frame_system::ensure_signed(origin)?;
+			ensure!(delegator != delegate, Error::<T, I>::SelfDelegation);
+
+			if let DelegationScope::Topic(topic_id) = scope {
+				let topic_details =
+					TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+				ensure!(
+					matches!(topic_details.status, TopicStatus::Pending | TopicStatus::Open),
+					Error::<T, I>::InvalidTopicStatus
+				);
+			}
+
+			if let Some(previous) = Delegations::<T, I>::get(&delegator, scope) {
+				Self::remove_delegator(&previous, &delegator, scope);
+			}
+			Delegators::<T, I>::try_mutate(&delegate, |delegators| {
+				delegators.try_push((delegator.clone(), scope))
+			})
+			.map_err(|_| Error::<T, I>::TooManyDelegators)?;
+			Delegations::<T, I>::insert(&delegator, scope, &delegate);
+
+			Self::deposit_event(Event::Delegated { delegator, delegate, scope });
+
+			Ok(())
+		}
+
+		/// Take back the votes the caller delegated in `scope`.
+		///
+		/// Vote weight already counted on the ballot of the delegate stays there, unless the
+		/// caller votes directly.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::undelegate())]
+		pub fn undelegate(
+			origin: OriginFor<T>,
+			scope: DelegationScope<T::TopicId>,
+		) -> DispatchResult {
+			let delegator = frame_system::ensure_signed(origin)?;
+
+			let delegate =
+				Delegations::<T, I>::take(&delegator, scope).ok_or(Error::<T, I>::NotDelegated)?;
+			Self::remove_delegator(&delegate, &delegator, scope);
+
+			Self::deposit_event(Event::Undelegated { delegator, scope });
+
+			Ok(())
+		}
+
+		/// Remove a topic whose retention period is over from storage, leaving only its outcome.
+		///
+		/// At most `RemoveItemsLimit` voter entries are removed per call, so topics with many
+		/// voters take several calls, or are finished by `on_initialize` over the following
+		/// blocks. The call removing the last entry also removes the topic
+		/// and returns any deposit still reserved to the raiser. Anyone can clear a closed,
+		/// finalized or cancelled topic once `TopicRetentionPeriod` has passed since the end of
+		/// its voting period.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::clear_topic(
+			T::RemoveItemsLimit::get(),
+			T::TopicOptionMaximumNumber::get(),
+		))]
+		pub fn clear_topic(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+		) -> DispatchResultWithPostInfo {
+			frame_system::ensure_signed(origin)?;
+
+			let topic_details =
+				TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+			ensure!(
+				matches!(
+					topic_details.status,
+					TopicStatus::Closed | TopicStatus::Finalized | TopicStatus::Cancelled
+				),
+				Error::<T, I>::InvalidTopicStatus
+			);
+			{
+				let now = Self::now();
+				let retained_until =
+					topic_details.voting_period_end.saturating_add(T::TopicRetentionPeriod::get());
+				ensure!(now >= retained_until, Error::<T, I>::RetentionPeriodNotOver);
+			}
+
+			let (removed, option_calls) =
+				Self::do_clear_topic(topic_id, topic_details.raiser, T::RemoveItemsLimit::get());
+
+			Ok(Some(T::WeightInfo::clear_topic(removed, option_calls)).into())
+		}
+
+		/// Amend a topic before its voting period starts.
+		///
+		/// The new contents and voting period are checked as when raising a topic, and the deposit
+		/// is adjusted to them. Calls attached to options beyond the new number of options are
+		/// dropped. Only the raiser can edit a topic, unless it was raised through `ForceOrigin`.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::edit_topic(
+			title.len() as u32,
+			description.len() as u32,
+			T::TopicOptionMaximumNumber::get(),
+		))]
+		pub fn edit_topic(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			title: Vec<u8>,
+			description: Vec<u8>,
+			voting_period_start: u64,
+			voting_period_end: u64,
+			options: Vec<Vec<u8>>,
+			required_answer_number: u32,
+		) -> DispatchResultWithPostInfo {
+			let raiser = frame_system::ensure_signed(origin)?;
+
+			let old_details =
+				TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+			ensure!(
+				old_details.raiser == raiser && !ForcedTopics::<T, I>::contains_key(topic_id),
+				Error::<T, I>::NoPermission
+			);
+			ensure!(old_details.status == TopicStatus::Pending, Error::<T, I>::InvalidTopicStatus);
+			{
+				let now = Self::now();
+				ensure!(now < old_details.voting_period_start, Error::<T, I>::VoteAlreadyOpen);
+			}
+
+			let title_length = title.len() as u32;
+			let description_length = description.len() as u32;
+			let deposit = Self::topic_deposit(&title, &description, &options);
+			let mut topic_details = Self::new_topic_details(
+				raiser.clone(),
+				title,
+				description,
+				voting_period_start,
+				voting_period_end,
+				options,
+				required_answer_number,
+			)?;
+			topic_details.tally_method = old_details.tally_method;
+			if topic_details.tally_method == TallyMethod::RankedChoice {
+				ensure!(
+					topic_details.options.len() as u32 <= T::MaxRankedChoiceOptions::get(),
+					Error::<T, I>::OptionTooMany
+				);
+			}
+
+			if let Some(old_deposit) = TopicDepositOf::<T, I>::get(topic_id) {
+				if deposit > old_deposit {
+					T::Currency::reserve(&raiser, deposit.saturating_sub(old_deposit))
+						.map_err(|_| Error::<T, I>::InsufficientDeposit)?;
+				} else {
+					T::Currency::unreserve(&raiser, old_deposit.saturating_sub(deposit));
+				}
+				TopicDepositOf::<T, I>::insert(topic_id, deposit);
+			}
+
+			let option_count = T::OptionIndex::from(topic_details.options.len() as u64);
+			let dropped_calls = TopicOptionCalls::<T, I>::iter_prefix(topic_id)
+				.filter(|(option, _)| *option >= option_count)
+				.collect::<Vec<_>>();
+			for (option, call) in dropped_calls {
+				TopicOptionCalls::<T, I>::remove(topic_id, option);
+				T::Preimages::drop(&call);
+				Self::deposit_event(Event::TopicOptionCallSet {
+					id: topic_id,
+					option,
+					call_hash: None,
+				});
+			}
+
+			ActiveTopics::<T, I>::mutate(|topics| {
+				if let Some(topic) = topics.iter_mut().find(|topic| topic.id == topic_id) {
+					topic.voting_period_start = voting_period_start;
+					topic.voting_period_end = voting_period_end;
+				}
+			});
+			let option_weight = old_details.options.len().max(topic_details.options.len()) as u32;
+			TopicCollection::<T, I>::insert(topic_id, topic_details);
+
+			Self::deposit_event(Event::TopicEdited { id: topic_id });
+
+			Ok(Some(T::WeightInfo::edit_topic(title_length, description_length, option_weight))
+				.into())
+		}
+
+		/// Cancel a topic and return its deposit to the raiser.
+		///
+		/// The raiser can cancel a topic not raised through `ForceOrigin` before its voting period
+		/// starts, and `ForceOrigin` any topic at any time before it is finalized. Attached calls
+		/// are dropped and ballots already cast are never counted.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::cancel_topic(T::TopicOptionMaximumNumber::get()))]
+		pub fn cancel_topic(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+		) -> DispatchResultWithPostInfo {
+			let mut topic_details =
+				TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+			if let Err(origin) = T::ForceOrigin::try_origin(origin) {
+				let who = frame_system::ensure_signed(origin)?;
+				ensure!(
+					who == topic_details.raiser && !ForcedTopics::<T, I>::contains_key(topic_id),
+					Error::<T, I>::NoPermission
+				);
+				ensure!(
+					topic_details.status == TopicStatus::Pending,
+					Error::<T, I>::InvalidTopicStatus
+				);
+				let now = Self::now();
+				ensure!(now < topic_details.voting_period_start, Error::<T, I>::VoteAlreadyOpen);
+			}
+			ensure!(
+				matches!(
+					topic_details.status,
+					TopicStatus::Pending | TopicStatus::Open | TopicStatus::Closed
+				),
+				Error::<T, I>::InvalidTopicStatus
+			);
+
+			let option_count = topic_details.options.len() as u32;
+			let raiser = topic_details.raiser.clone();
+			topic_details.status = TopicStatus::Cancelled;
+			TopicCollection::<T, I>::insert(topic_id, topic_details);
+
+			Self::deactivate_topic(topic_id);
+			for (_, call) in TopicOptionCalls::<T, I>::drain_prefix(topic_id) {
+				T::Preimages::drop(&call);
+			}
+
+			if let Some(amount) = TopicDepositOf::<T, I>::take(topic_id) {
+				T::Currency::unreserve(&raiser, amount);
+				Self::deposit_event(Event::TopicDepositRefunded { id: topic_id, raiser, amount });
+			}
+
+			Self::deposit_event(Event::TopicCancelled { id: topic_id });
+
+			Ok(Some(T::WeightInfo::cancel_topic(option_count)).into())
+		}
+
+		/// Set what the voting right tokens of a topic are weighed with.
+		///
+		/// Only the raiser or `ForceOrigin` can set it, before the voting period starts and
+		/// before any voting right token is issued. Topics with [`VotingMode::Locked`] weigh
+		/// votes with the locked balance regardless.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::set_weight_source())]
+		pub fn set_weight_source(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			source: WeightSourceOf<T, I>,
+		) -> DispatchResult {
+			let topic_details =
+				TopicCollection::<T, I>::get(&topic_id).ok_or(Error::<T, I>::UnknownTopic)?;
+			Self::ensure_raiser_or_force(origin, topic_id, &topic_details.raiser)?;
+
+			ensure!(
+				topic_details.status == TopicStatus::Pending,
+				Error::<T, I>::InvalidTopicStatus
+			);
+			{
+				let now = Self::now();
+				ensure!(now < topic_details.voting_period_start, Error::<T, I>::VoteAlreadyOpen);
+			}
+			ensure!(
+				VotingRightTokenCollection::<T, I>::iter_prefix(topic_id).next().is_none(),
+				Error::<T, I>::VotingRightTokenIssued
+			);
+			match source {
+				WeightSource::Native => {},
+				WeightSource::Asset(asset) =>
+					ensure!(T::Assets::asset_exists(asset), Error::<T, I>::UnknownAsset),
+				WeightSource::Collection(collection) => ensure!(
+					T::Nfts::collection_owner(&collection).is_some(),
+					Error::<T, I>::UnknownCollection
+				),
+			}
+
+			TopicWeightSource::<T, I>::insert(topic_id, source);
+
+			Self::deposit_event(Event::TopicWeightSourceSet { id: topic_id, source });
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Cast the ballot of `voter`, who holds a voting right token on the topic.
+		///
+		/// The ballot weighs `vote_weight`, or the weight of the voting right token if `None`.
+		fn do_vote_topic(
+			voter: T::AccountId,
+			topic_id: T::TopicId,
+			options: BoundedVec<T::OptionIndex, T::TopicOptionMaximumNumber>,
+			vote_weight: Option<T::Vote>,
+		) -> DispatchResult {
+			let topic_details = if let Some(topic_details) = TopicCollection::<T, I>::get(&topic_id)
+			{
+				topic_details
+			} else {
+				return Err(Error::<T, I>::UnknownTopic.into())
+			};
+
+			ensure!(topic_details.status != TopicStatus::Cancelled, Error::<T, I>::TopicCancelled);
+			ensure!(
+				matches!(topic_details.status, TopicStatus::Pending | TopicStatus::Open),
+				Error::<T, I>::VoteClosed
+			);
+
+			{
+				let now = Self::now();
+				let (ballot_period_start, ballot_period_end) =
+					Self::ballot_period(topic_id, &topic_details);
+				ensure!(ballot_period_start <= now, Error::<T, I>::VoteNotOpen);
+				ensure!(now <= ballot_period_end, Error::<T, I>::VoteClosed);
+			}
+
+			let token_weight = if let Some(token_weight) =
+				VotingRightTokenCollection::<T, I>::get(topic_id, &voter)
+			{
+				ensure!(token_weight > T::Vote::initial_value(), Error::<T, I>::VoterHasVoted);
+				token_weight
+			} else {
+				return Err(Error::<T, I>::VoterHasNoVotingRight.into())
+			};
+			let vote_weight = vote_weight.unwrap_or(token_weight);
+
+			{
+				let mut options = options.clone().into_inner();
+				options.sort_unstable();
+				let origin_len = options.len();
+				options.dedup();
 				ensure!(origin_len == options.len(), Error::<T, I>::DuplicatedVoting);
 				ensure!(
 					options.len() == topic_details.required_answer_number as usize,
@@ -389,19 +1845,54 @@ pub mod pallet {
 					Error::<T, I>::InvalidOption
 				);
 
-				for opt in options.iter() {
-					let mut vote_count = BallotBox::<T, I>::get(topic_id, opt)
-						.unwrap_or_else(T::Vote::initial_value);
-					vote_count += vote_weight;
-					BallotBox::<T, I>::insert(topic_id, opt, vote_count);
+				if topic_details.tally_method != TallyMethod::RankedChoice {
+					let vote_weight = Self::ballot_weight(topic_details.tally_method, vote_weight);
+					for opt in options.iter() {
+						let mut vote_count = BallotBox::<T, I>::get(topic_id, opt)
+							.unwrap_or_else(T::Vote::initial_value);
+						vote_count += vote_weight;
+						BallotBox::<T, I>::insert(topic_id, opt, vote_count);
+					}
 				}
 			}
 
+			// The ballot box only holds the first preferences of a ranked choice topic, the
+			// rankings are counted when the topic is closed.
+
+			if topic_details.tally_method == TallyMethod::RankedChoice {
+				ensure!(
+					TopicTurnout::<T, I>::get(topic_id) < T::MaxRankedChoiceVoters::get(),
+					Error::<T, I>::TooManyVoters
+				);
+				let first_preference = options.first().expect("options is not empty");
+				BallotBox::<T, I>::mutate(topic_id, first_preference, |vote_count| {
+					*vote_count = Some(
+						vote_count
+							.unwrap_or_else(T::Vote::initial_value)
+							.saturating_add(vote_weight),
+					)
+				});
+				RankedBallots::<T, I>::insert(topic_id, &voter, (options.clone(), vote_weight));
+			}
+
+			Self::withdraw_delegated_vote(topic_id, &voter, topic_details.tally_method);
 			VotingRightTokenCollection::<T, I>::insert(
 				topic_id,
 				voter.clone(),
 				T::Vote::initial_value(),
 			);
+			if Self::counts_towards_turnout(topic_id, &voter) {
+				TopicTurnout::<T, I>::mutate(topic_id, |turnout| turnout.saturating_inc());
+			}
+
+			if topic_details.status == TopicStatus::Pending {
+				TopicCollection::<T, I>::mutate(topic_id, |maybe_details| {
+					if let Some(details) = maybe_details {
+						details.status = TopicStatus::Open;
+					}
+				});
+				Self::deposit_event(Event::TopicOpened { id: topic_id });
+			}
 
 			Self::deposit_event(Event::<T, I>::TopicVoted {
 				id: topic_id,
@@ -411,9 +1902,7 @@ pub mod pallet {
 
 			Ok(())
 		}
-	}
 
-	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		pub fn do_raise_topic(
 			raiser: T::AccountId,
 			title: Vec<u8>,
@@ -422,17 +1911,88 @@ pub mod pallet {
 			voting_period_end: u64,
 			options: Vec<Vec<u8>>,
 			required_answer_number: u32,
+			deposit: BalanceOf<T, I>,
+			forced: bool,
 		) -> DispatchResult {
+			let topic_id = TopicCount::<T, I>::get().unwrap_or(T::TopicId::initial_value());
+			let topic_details = Self::new_topic_details(
+				raiser.clone(),
+				title,
+				description,
+				voting_period_start,
+				voting_period_end,
+				options,
+				required_answer_number,
+			)?;
+
+			if !deposit.is_zero() {
+				T::Currency::reserve(&raiser, deposit)
+					.map_err(|_| Error::<T, I>::InsufficientDeposit)?;
+				TopicDepositOf::<T, I>::insert(topic_id, deposit);
+			}
+
+			if forced {
+				ForcedTopics::<T, I>::insert(topic_id, ());
+			} else {
+				let active_topics = ActiveTopics::<T, I>::decode_len().unwrap_or(0) as u32;
+				ensure!(
+					active_topics <
+						T::MaxActiveTopics::get().saturating_sub(T::ReservedActiveTopics::get()),
+					Error::<T, I>::TooManyActiveTopics
+				);
+				ActiveTopicsOf::<T, I>::try_mutate(&raiser, |count| {
+					ensure!(
+						*count < T::MaxActiveTopicsPerRaiser::get(),
+						Error::<T, I>::TooManyActiveTopicsOfRaiser
+					);
+					count.saturating_inc();
+					Ok::<_, Error<T, I>>(())
+				})?;
+			}
+			ActiveTopics::<T, I>::try_append(ActiveTopic {
+				id: topic_id,
+				voting_period_start,
+				voting_period_end,
+				reveal_period: 0,
+				opened: false,
+			})
+			.map_err(|_| Error::<T, I>::TooManyActiveTopics)?;
+			TopicCollection::<T, I>::insert(topic_id, topic_details);
+
+			Self::deposit_event(Event::TopicRaised { id: topic_id, raiser });
+
+			let next_topic_id = topic_id.increment();
+			TopicCount::<T, I>::set(Some(next_topic_id));
+
+			Ok(())
+		}
+
+		/// Check the contents and voting period of a topic about to be raised or edited, and
+		/// build its details.
+		fn new_topic_details(
+			raiser: T::AccountId,
+			title: Vec<u8>,
+			description: Vec<u8>,
+			voting_period_start: u64,
+			voting_period_end: u64,
+			options: Vec<Vec<u8>>,
+			required_answer_number: u32,
+		) -> Result<
+			TopicDetails<T::AccountId, T::StringLimit, T::TopicOptionMaximumNumber>,
+			DispatchError,
+		> {
 			{
-				let now = T::UnixTime::now().as_secs().saturated_into::<u64>();
+				let now = Self::now();
 				ensure!(
-					voting_period_start >= now + ONE_HOUR &&
-						voting_period_start <= now + THREE_MONTHS,
+					voting_period_start >= now.saturating_add(T::MinVotingDelay::get()) &&
+						voting_period_start <= now.saturating_add(T::MaxVotingDelay::get()),
 					Error::<T, I>::InvalidVotingPeriodStart
 				);
 				ensure!(
-					voting_period_end >= voting_period_start + THREE_HOURS &&
-						voting_period_end <= voting_period_start + THREE_MONTHS,
+					voting_period_end >=
+						voting_period_start.saturating_add(T::MinVotingDuration::get()) &&
+						voting_period_end <=
+							voting_period_start.saturating_add(T::MaxVotingDuration::get()),
 					Error::<T, I>::InvalidVotingPeriodEnd
 				);
 			}
@@ -447,40 +2007,534 @@ pub mod pallet {
 				Error::<T, I>::InvalidAnswerNumber
 			);
 
-			let topic_id = TopicCount::<T, I>::get().unwrap_or(T::TopicId::initial_value());
-			let topic_details: TopicDetails<
-				T::AccountId,
-				T::StringLimit,
-				T::TopicOptionMaximumNumber,
-			> = {
-				let mut opts = BoundedVec::default();
-				for option in options {
-					opts.try_push(
-						BoundedVec::try_from(option).map_err(|_| Error::<T, I>::OptionTooLong)?,
+			let mut opts = BoundedVec::default();
+			for option in options {
+				opts.try_push(
+					BoundedVec::try_from(option).map_err(|_| Error::<T, I>::OptionTooLong)?,
+				)
+				.map_err(|_| Error::<T, I>::OptionTooMany)?;
+			}
+
+			Ok(TopicDetails {
+				raiser,
+				title: BoundedVec::try_from(title).map_err(|_| Error::<T, I>::TitleTooLong)?,
+				description: BoundedVec::try_from(description)
+					.map_err(|_| Error::<T, I>::DescriptionTooLong)?,
+				voting_period_start,
+				voting_period_end,
+				options: opts,
+				required_answer_number,
+				status: TopicStatus::Pending,
+				tally_method: TallyMethod::Plain,
+			})
+		}
+
+		/// Add the vote weight of the delegators of `delegate` to its ballot on a topic.
+		///
+		/// A delegation limited to the topic takes precedence over a global one. Delegators who
+		/// voted, or whose weight is already counted for another delegate, are skipped. Returns
+		/// the number of delegators counted.
+		fn count_delegated_votes(
+			topic_id: T::TopicId,
+			delegate: T::AccountId,
+			options: BoundedVec<T::OptionIndex, T::TopicOptionMaximumNumber>,
+		) -> Result<u32, DispatchError> {
+			let tally_method = TopicCollection::<T, I>::get(&topic_id)
+				.ok_or(Error::<T, I>::UnknownTopic)?
+				.tally_method;
+
+			let mut delegators = 0u32;
+			let mut vote_weight = T::Vote::initial_value();
+			let mut ballot_weight = T::Vote::initial_value();
+			for (delegator, scope) in Delegators::<T, I>::get(&delegate) {
+				let applies = match scope {
+					DelegationScope::Topic(id) => id == topic_id,
+					DelegationScope::Global => !Delegations::<T, I>::contains_key(
+						&delegator,
+						DelegationScope::Topic(topic_id),
+					),
+				};
+				if !applies || DelegatedVotes::<T, I>::contains_key(topic_id, &delegator) {
+					continue
+				}
+				let weight = match VotingRightTokenCollection::<T, I>::get(topic_id, &delegator) {
+					Some(weight) if weight > T::Vote::initial_value() => weight,
+					_ => continue,
+				};
+
+				DelegatedVotes::<T, I>::insert(topic_id, &delegator, (delegate.clone(), weight));
+				delegators.saturating_inc();
+				vote_weight = vote_weight.saturating_add(weight);
+				ballot_weight =
+					ballot_weight.saturating_add(Self::ballot_weight(tally_method, weight));
+			}
+			if delegators == 0 {
+				return Ok(0)
+			}
+
+			if tally_method == TallyMethod::RankedChoice {
+				let first_preference = *options.first().expect("options is not empty");
+				RankedBallots::<T, I>::mutate(topic_id, &delegate, |ballot| {
+					if let Some((_, weight)) = ballot {
+						*weight = weight.saturating_add(vote_weight);
+					}
+				});
+				BallotBox::<T, I>::mutate(topic_id, first_preference, |vote_count| {
+					*vote_count = Some(
+						vote_count
+							.unwrap_or_else(T::Vote::initial_value)
+							.saturating_add(vote_weight),
 					)
-					.map_err(|_| Error::<T, I>::OptionTooMany)?;
+				});
+			} else {
+				for option in options.iter() {
+					BallotBox::<T, I>::mutate(topic_id, option, |vote_count| {
+						*vote_count = Some(
+							vote_count
+								.unwrap_or_else(T::Vote::initial_value)
+								.saturating_add(ballot_weight),
+						)
+					});
 				}
+				DelegateBallots::<T, I>::insert(topic_id, &delegate, options);
+			}
+
+			Self::deposit_event(Event::DelegatedVotesCounted {
+				topic_id,
+				delegate,
+				delegators,
+				vote_weight,
+			});
+
+			Ok(delegators)
+		}
+
+		/// Take the vote weight of `delegator` off the ballot of its delegate on a topic, if it
+		/// was counted there.
+		fn withdraw_delegated_vote(
+			topic_id: T::TopicId,
+			delegator: &T::AccountId,
+			tally_method: TallyMethod,
+		) {
+			let (delegate, vote_weight) = match DelegatedVotes::<T, I>::take(topic_id, delegator) {
+				Some(delegated_vote) => delegated_vote,
+				None => return,
+			};
 
-				TopicDetails {
-					raiser: raiser.clone(),
-					title: BoundedVec::try_from(title).map_err(|_| Error::<T, I>::TitleTooLong)?,
-					description: BoundedVec::try_from(description)
-						.map_err(|_| Error::<T, I>::DescriptionTooLong)?,
-					voting_period_start,
-					voting_period_end,
-					options: opts,
-					required_answer_number,
+			let withdraw = |option: &T::OptionIndex, weight: T::Vote| {
+				BallotBox::<T, I>::mutate(topic_id, option, |vote_count| {
+					if let Some(vote_count) = vote_count {
+						*vote_count = vote_count.saturating_sub(weight);
+					}
+				});
+			};
+			if tally_method == TallyMethod::RankedChoice {
+				RankedBallots::<T, I>::mutate(topic_id, &delegate, |ballot| {
+					if let Some((options, weight)) = ballot {
+						*weight = weight.saturating_sub(vote_weight);
+						if let Some(first_preference) = options.first() {
+							withdraw(first_preference, vote_weight);
+						}
+					}
+				});
+			} else {
+				let ballot_weight = Self::ballot_weight(tally_method, vote_weight);
+				for option in DelegateBallots::<T, I>::get(topic_id, &delegate).unwrap_or_default()
+				{
+					withdraw(&option, ballot_weight);
 				}
+			}
+
+			Self::deposit_event(Event::DelegatedVoteWithdrawn {
+				topic_id,
+				delegator: delegator.clone(),
+				delegate,
+				vote_weight,
+			});
+		}
+
+		/// The weight a vote of `vote_weight` adds to each chosen option.
+		fn ballot_weight(tally_method: TallyMethod, vote_weight: T::Vote) -> T::Vote {
+			if tally_method == TallyMethod::Quadratic {
+				vote_weight.integer_sqrt()
+			} else {
+				vote_weight
+			}
+		}
+
+		fn remove_delegator(
+			delegate: &T::AccountId,
+			delegator: &T::AccountId,
+			scope: DelegationScope<T::TopicId>,
+		) {
+			Delegators::<T, I>::mutate(delegate, |delegators| {
+				delegators.retain(|(account, s)| !(account == delegator && *s == scope))
+			});
+		}
+
+		/// The current time in the unit of [`Config::PeriodMode`].
+		pub fn now() -> u64 {
+			match T::PeriodMode::get() {
+				PeriodMode::Timestamp => T::UnixTime::now().as_secs().saturated_into::<u64>(),
+				PeriodMode::BlockNumber =>
+					frame_system::Pallet::<T>::block_number().saturated_into::<u64>(),
+			}
+		}
+
+		/// The period in which ballots are counted: the voting period, or the reveal window
+		/// following it for [`VotingMode::CommitReveal`].
+		fn ballot_period(
+			topic_id: T::TopicId,
+			topic_details: &TopicDetails<T::AccountId, T::StringLimit, T::TopicOptionMaximumNumber>,
+		) -> (u64, u64) {
+			match TopicVotingMode::<T, I>::get(topic_id) {
+				VotingMode::CommitReveal => (
+					topic_details.voting_period_end.saturating_add(1),
+					topic_details.voting_period_end.saturating_add(T::RevealPeriod::get()),
+				),
+				VotingMode::Snapshot | VotingMode::Locked =>
+					(topic_details.voting_period_start, topic_details.voting_period_end),
+			}
+		}
+
+		/// Tally a topic whose voting period ended and record its outcome.
+		///
+		/// Returns the weight consumed.
+		pub(crate) fn do_close_topic(
+			topic_id: T::TopicId,
+			mut topic_details: TopicDetails<
+				T::AccountId,
+				T::StringLimit,
+				T::TopicOptionMaximumNumber,
+			>,
+		) -> Result<Weight, DispatchError> {
+			let option_count = topic_details.options.len() as u32;
+			let outcome = Self::tally(topic_id, &topic_details)?;
+			let winning_options = outcome.winning_options.clone();
+			let turnout = outcome.turnout;
+			let weight = if topic_details.tally_method == TallyMethod::RankedChoice {
+				T::WeightInfo::close_ranked_choice_topic(option_count, turnout)
+			} else {
+				T::WeightInfo::close_topic(option_count)
 			};
 
+			topic_details.status = TopicStatus::Closed;
 			TopicCollection::<T, I>::insert(topic_id, topic_details);
+			TopicOutcomes::<T, I>::insert(topic_id, outcome);
 
-			Self::deposit_event(Event::TopicRaised { id: topic_id, raiser });
+			Self::deposit_event(Event::TopicClosed { id: topic_id, winning_options, turnout });
 
-			let next_topic_id = topic_id.increment();
-			TopicCount::<T, I>::set(Some(next_topic_id));
+			Ok(weight)
+		}
 
-			Ok(())
+		/// Move a pending topic to open, as its voting period started.
+		fn auto_open_topic(topic_id: T::TopicId) {
+			TopicCollection::<T, I>::mutate(topic_id, |maybe_details| {
+				if let Some(details) = maybe_details {
+					if details.status == TopicStatus::Pending {
+						details.status = TopicStatus::Open;
+						Self::deposit_event(Event::TopicOpened { id: topic_id });
+					}
+				}
+			});
+		}
+
+		/// Close a topic whose voting period ended, unless it was already closed or cancelled.
+		///
+		/// Returns the weight consumed.
+		fn auto_close_topic(topic_id: T::TopicId) -> Weight {
+			let read = T::DbWeight::get().reads(1);
+			match TopicCollection::<T, I>::get(&topic_id) {
+				Some(details)
+					if matches!(details.status, TopicStatus::Pending | TopicStatus::Open) =>
+					match frame_support::storage::with_storage_layer(|| {
+						Self::do_close_topic(topic_id, details)
+					}) {
+						Ok(weight) => weight,
+						Err(error) => {
+							log::error!(
+								target: "runtime::dao",
+								"Failed to close topic {:?}: {:?}",
+								topic_id,
+								error,
+							);
+							read
+						},
+					},
+				_ => read,
+			}
+		}
+
+		/// Stop tracking a topic in the hooks.
+		pub(crate) fn deactivate_topic(topic_id: T::TopicId) {
+			let tracked = ActiveTopics::<T, I>::mutate(|topics| {
+				let count = topics.len();
+				topics.retain(|topic| topic.id != topic_id);
+				topics.len() < count
+			});
+			if tracked {
+				Self::release_raiser_slot(topic_id);
+			}
+		}
+
+		/// Give the raiser of a topic no longer tracked in the hooks back the slot it took of
+		/// `MaxActiveTopicsPerRaiser`.
+		///
+		/// Returns the weight consumed.
+		fn release_raiser_slot(topic_id: T::TopicId) -> Weight {
+			let weight = T::DbWeight::get().reads_writes(3, 1);
+			if ForcedTopics::<T, I>::contains_key(topic_id) {
+				return weight
+			}
+			if let Some(details) = TopicCollection::<T, I>::get(topic_id) {
+				// Topics tracked before the count existed did not take a slot.
+				ActiveTopicsOf::<T, I>::mutate_exists(&details.raiser, |count| {
+					*count = count.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0)
+				});
+			}
+			weight
+		}
+
+		/// Whether spending the voting right token of `voter` on a topic adds to its turnout,
+		/// which it does not until the v1 migration has counted the token.
+		fn counts_towards_turnout(topic_id: T::TopicId, voter: &T::AccountId) -> bool {
+			match TurnoutCountCursor::<T, I>::get() {
+				Some(cursor) =>
+					VotingRightTokenCollection::<T, I>::hashed_key_for(topic_id, voter) <=
+						cursor.into_inner(),
+				None => true,
+			}
+		}
+
+		/// Carry on counting the voting right tokens spent before the v1 migration into the
+		/// turnout of their topic, at most `RemoveItemsLimit` of them.
+		///
+		/// Returns the weight consumed.
+		fn count_turnout() -> Weight {
+			let cursor = match TurnoutCountCursor::<T, I>::get() {
+				Some(cursor) => cursor,
+				None => return T::DbWeight::get().reads(1),
+			};
+			let mut reads = 1u64;
+			let mut writes = 1u64;
+			let mut tokens = VotingRightTokenCollection::<T, I>::iter_from(cursor.into_inner());
+			let mut finished = false;
+			for _ in 0..T::RemoveItemsLimit::get() {
+				let (topic_id, _, vote_weight) = match tokens.next() {
+					Some(token) => token,
+					None => {
+						finished = true;
+						break
+					},
+				};
+				reads.saturating_inc();
+				if vote_weight == T::Vote::initial_value() {
+					writes.saturating_inc();
+					TopicTurnout::<T, I>::mutate(topic_id, |turnout| turnout.saturating_inc());
+				}
+			}
+			if !finished {
+				match BoundedVec::try_from(tokens.last_raw_key().to_vec()) {
+					Ok(cursor) => {
+						TurnoutCountCursor::<T, I>::put(cursor);
+						return T::DbWeight::get().reads_writes(reads, writes)
+					},
+					Err(_) => log::error!(
+						target: "runtime::dao",
+						"Voting right token key too long to carry on counting the turnout",
+					),
+				}
+			}
+
+			TurnoutCountCursor::<T, I>::kill();
+			log::info!(target: "runtime::dao", "Counted the turnout of the topics raised before v1");
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		/// Remove at most `limit` voter entries of a topic and, once none is left, the topic
+		/// itself, returning any deposit still reserved to `raiser`. The outcome is kept.
+		///
+		/// Returns the number of voter entries and of option calls removed.
+		pub(crate) fn do_clear_topic(
+			topic_id: T::TopicId,
+			raiser: T::AccountId,
+			limit: u32,
+		) -> (u32, u32) {
+			let mut cursor = TopicClearCursors::<T, I>::get(topic_id).unwrap_or_default();
+			let mut removed = 0u32;
+			let mut cleared = false;
+			while removed < limit {
+				let results = match Self::clear_voter_entries(
+					cursor.map,
+					topic_id,
+					limit - removed,
+					cursor.key.as_ref().map(|key| key.as_slice()),
+				) {
+					Some(results) => results,
+					None => {
+						cleared = true;
+						break
+					},
+				};
+				removed.saturating_accrue(results.unique);
+				match results.maybe_cursor {
+					Some(key) => {
+						// A key too long to keep only costs the next call a restart of the map.
+						cursor.key = BoundedVec::try_from(key).ok();
+						break
+					},
+					None => {
+						cursor.map.saturating_inc();
+						cursor.key = None;
+					},
+				}
+			}
+
+			if removed > 0 {
+				Self::deposit_event(Event::TopicEntriesRemoved { id: topic_id, removed });
+			}
+			if !cleared {
+				TopicClearCursors::<T, I>::insert(topic_id, cursor);
+				return (removed, 0)
+			}
+
+			TopicClearCursors::<T, I>::remove(topic_id);
+			let mut option_calls = 0u32;
+			for (_, call) in TopicOptionCalls::<T, I>::drain_prefix(topic_id) {
+				T::Preimages::drop(&call);
+				option_calls.saturating_inc();
+			}
+			if let Some(amount) = TopicDepositOf::<T, I>::take(topic_id) {
+				T::Currency::unreserve(&raiser, amount);
+				Self::deposit_event(Event::TopicDepositRefunded { id: topic_id, raiser, amount });
+			}
+			TopicTurnout::<T, I>::remove(topic_id);
+			TopicVotingMode::<T, I>::remove(topic_id);
+			TopicWeightSource::<T, I>::remove(topic_id);
+			TopicIssuers::<T, I>::remove(topic_id);
+			ForcedTopics::<T, I>::remove(topic_id);
+			TopicCollection::<T, I>::remove(topic_id);
+
+			Self::deposit_event(Event::TopicCleared { id: topic_id });
+
+			(removed, option_calls)
+		}
+
+		/// Carry on clearing the topics with a clear cursor, such as those whose retention period
+		/// was over at the v2 migration, removing at most `RemoveItemsLimit` voter entries.
+		///
+		/// Topics the v2 migration left open, as their turnout was still being counted, are
+		/// closed first.
+		fn clear_pending_topics() -> Weight {
+			let mut weight = Weight::zero();
+			let mut limit = T::RemoveItemsLimit::get();
+			while limit > 0 {
+				weight.saturating_accrue(T::DbWeight::get().reads(2));
+				let topic_id = match TopicClearCursors::<T, I>::iter_keys().next() {
+					Some(topic_id) => topic_id,
+					None => break,
+				};
+				let details = match TopicCollection::<T, I>::get(topic_id) {
+					Some(details) => details,
+					None => {
+						weight.saturating_accrue(T::DbWeight::get().writes(1));
+						TopicClearCursors::<T, I>::remove(topic_id);
+						continue
+					},
+				};
+				let raiser = details.raiser.clone();
+				if matches!(details.status, TopicStatus::Pending | TopicStatus::Open) {
+					match frame_support::storage::with_storage_layer(|| {
+						Self::do_close_topic(topic_id, details)
+					}) {
+						Ok(close_weight) => weight.saturating_accrue(close_weight),
+						Err(error) => {
+							log::error!(
+								target: "runtime::dao",
+								"Failed to close topic {:?}: {:?}",
+								topic_id,
+								error,
+							);
+							weight.saturating_accrue(T::DbWeight::get().writes(1));
+							TopicClearCursors::<T, I>::remove(topic_id);
+							continue
+						},
+					}
+				}
+				let (removed, option_calls) = Self::do_clear_topic(topic_id, raiser, limit);
+				weight.saturating_accrue(T::WeightInfo::clear_topic(removed, option_calls));
+				if TopicClearCursors::<T, I>::contains_key(topic_id) {
+					break
+				}
+				limit = limit.saturating_sub(removed);
+			}
+			weight
+		}
+
+		/// Remove at most `limit` entries of a topic from the `map`-th of the storage maps
+		/// holding its ballots and voter entries, or `None` once past the last map.
+		fn clear_voter_entries(
+			map: u8,
+			topic_id: T::TopicId,
+			limit: u32,
+			cursor: Option<&[u8]>,
+		) -> Option<sp_io::MultiRemovalResults> {
+			Some(match map {
+				0 => VotingRightTokenCollection::<T, I>::clear_prefix(topic_id, limit, cursor),
+				1 => BallotBox::<T, I>::clear_prefix(topic_id, limit, cursor),
+				2 => RankedBallots::<T, I>::clear_prefix(topic_id, limit, cursor),
+				3 => VoteCommitments::<T, I>::clear_prefix(topic_id, limit, cursor),
+				4 => DelegatedVotes::<T, I>::clear_prefix(topic_id, limit, cursor),
+				5 => DelegateBallots::<T, I>::clear_prefix(topic_id, limit, cursor),
+				_ => return None,
+			})
+		}
+
+		/// Lock the largest balance `who` locked on any topic, or remove the lock if none is left.
+		fn update_vote_lock(who: &T::AccountId) {
+			let locks = VotingLocks::<T, I>::get(who);
+			match locks.iter().map(|lock| lock.amount).max() {
+				Some(amount) => T::Currency::set_lock(
+					T::VoteLockId::get(),
+					who,
+					amount,
+					WithdrawReasons::except(WithdrawReasons::RESERVE),
+				),
+				None => {
+					VotingLocks::<T, I>::remove(who);
+					T::Currency::remove_lock(T::VoteLockId::get(), who)
+				},
+			}
+		}
+
+		/// What `who` holds in `source`, and the number of collection items counted for it.
+		fn holdings(source: WeightSourceOf<T, I>, who: &T::AccountId) -> (u128, u32) {
+			match source {
+				WeightSource::Native => (T::Currency::total_balance(who).saturated_into(), 0),
+				WeightSource::Asset(asset) => (T::Assets::balance(asset, who).saturated_into(), 0),
+				WeightSource::Collection(collection) => {
+					let items = T::Nfts::owned_in_collection(&collection, who)
+						.take(T::MaxItemsPerVoter::get() as usize)
+						.count() as u32;
+					(u128::from(items), items)
+				},
+			}
+		}
+
+		/// The deposit required to raise a topic with the given title, description and options.
+		pub fn topic_deposit(
+			title: &[u8],
+			description: &[u8],
+			options: &[Vec<u8>],
+		) -> BalanceOf<T, I> {
+			let bytes = options
+				.iter()
+				.fold(title.len().saturating_add(description.len()), |bytes, option| {
+					bytes.saturating_add(option.len())
+				});
+			T::TopicDepositPerByte::get()
+				.saturating_mul(bytes.saturated_into::<BalanceOf<T, I>>())
+				.saturating_add(T::TopicDepositBase::get())
 		}
 
 		pub fn get_topic_by_id(
@@ -496,6 +2550,295 @@ pub mod pallet {
 				.map(|(index, vote_weight)| TopicVotingResult { index, vote_weight })
 				.collect()
 		}
+
+		pub fn get_topic_outcome_by_id(
+			topic_id: T::TopicId,
+		) -> Option<TopicOutcome<T::OptionIndex, T::Vote, T::TopicOptionMaximumNumber>> {
+			TopicOutcomes::<T, I>::get(&topic_id)
+		}
+
+		/// At most `limit` topics, in storage order, starting after the topic `start_after`.
+		pub fn topics(
+			start_after: Option<T::TopicId>,
+			limit: u32,
+		) -> Vec<TopicInfo<T::TopicId, T::AccountId>> {
+			let topics = match start_after {
+				Some(topic_id) => TopicCollection::<T, I>::iter_from(
+					TopicCollection::<T, I>::hashed_key_for(topic_id),
+				),
+				None => TopicCollection::<T, I>::iter(),
+			};
+			topics
+				.take(limit as usize)
+				.map(|(topic_id, details)| Self::to_topic_info(topic_id, details))
+				.collect()
+		}
+
+		/// The topic `topic_id`, if any.
+		pub fn topic_info(topic_id: T::TopicId) -> Option<TopicInfo<T::TopicId, T::AccountId>> {
+			TopicCollection::<T, I>::get(&topic_id)
+				.map(|details| Self::to_topic_info(topic_id, details))
+		}
+
+		/// The ballots cast on the topic `topic_id` and its outcome, if the topic exists.
+		pub fn topic_results(
+			topic_id: T::TopicId,
+		) -> Option<TopicResults<T::OptionIndex, T::Vote>> {
+			TopicCollection::<T, I>::contains_key(&topic_id).then(|| TopicResults {
+				votes: Self::get_topic_votes_result_by_id(topic_id),
+				turnout: TopicTurnout::<T, I>::get(topic_id),
+				winning_options: TopicOutcomes::<T, I>::get(&topic_id)
+					.map(|outcome| outcome.winning_options.into_inner()),
+			})
+		}
+
+		/// Where `who` stands on the topic `topic_id`.
+		pub fn voter_status(topic_id: T::TopicId, who: &T::AccountId) -> VoterStatus<T::Vote> {
+			match VotingRightTokenCollection::<T, I>::get(topic_id, who) {
+				None => VoterStatus::NoVotingRight,
+				Some(vote_weight) if vote_weight == T::Vote::initial_value() => VoterStatus::Voted,
+				Some(vote_weight) if VoteCommitments::<T, I>::contains_key(topic_id, who) =>
+					VoterStatus::Committed { vote_weight },
+				Some(vote_weight) => VoterStatus::NotVoted { vote_weight },
+			}
+		}
+
+		/// Whether `who` can vote on the topic `topic_id` now: commit or reveal a ballot for
+		/// [`VotingMode::CommitReveal`], cast one otherwise.
+		pub fn can_vote(topic_id: T::TopicId, who: &T::AccountId) -> bool {
+			let topic_details = match TopicCollection::<T, I>::get(&topic_id) {
+				Some(topic_details) => topic_details,
+				None => return false,
+			};
+			if !matches!(topic_details.status, TopicStatus::Pending | TopicStatus::Open) {
+				return false
+			}
+
+			let now = Self::now();
+			let in_voting_period =
+				topic_details.voting_period_start <= now && now <= topic_details.voting_period_end;
+			match (TopicVotingMode::<T, I>::get(topic_id), Self::voter_status(topic_id, who)) {
+				(VotingMode::CommitReveal, VoterStatus::Committed { .. }) => {
+					let (reveal_start, reveal_end) = Self::ballot_period(topic_id, &topic_details);
+					in_voting_period || (reveal_start <= now && now <= reveal_end)
+				},
+				(_, VoterStatus::NotVoted { .. }) => in_voting_period,
+				_ => false,
+			}
+		}
+
+		fn to_topic_info(
+			topic_id: T::TopicId,
+			details: TopicDetails<T::AccountId, T::StringLimit, T::TopicOptionMaximumNumber>,
+		) -> TopicInfo<T::TopicId, T::AccountId> {
+			TopicInfo {
+				id: topic_id,
+				raiser: details.raiser,
+				title: details.title.into_inner(),
+				description: details.description.into_inner(),
+				voting_period_start: details.voting_period_start,
+				voting_period_end: details.voting_period_end,
+				options: details.options.into_iter().map(BoundedVec::into_inner).collect(),
+				required_answer_number: details.required_answer_number,
+				status: details.status,
+				tally_method: details.tally_method,
+				voting_mode: TopicVotingMode::<T, I>::get(topic_id),
+			}
+		}
+
+		/// Schedule the call attached to `winning_option`, if any, and drop every other call
+		/// attached to the topic.
+		fn enact_topic(
+			topic_id: T::TopicId,
+			winning_option: Option<T::OptionIndex>,
+		) -> DispatchResult {
+			for (option, call) in TopicOptionCalls::<T, I>::drain_prefix(topic_id) {
+				if Some(option) == winning_option {
+					let origin = if ForcedTopics::<T, I>::contains_key(topic_id) {
+						RawOrigin::ForcedTopic(topic_id)
+					} else {
+						RawOrigin::Topic(topic_id)
+					};
+					T::Scheduler::schedule(
+						DispatchTime::After(Zero::zero()),
+						None,
+						ENACTMENT_PRIORITY,
+						origin.into(),
+						call,
+					)?;
+					Self::deposit_event(Event::TopicEnactmentScheduled { id: topic_id, option });
+				} else {
+					T::Preimages::drop(&call);
+				}
+			}
+			Ok(())
+		}
+
+		/// Ensure `origin` is `ForceOrigin`, or the raiser of a topic not raised through
+		/// `ForceOrigin`.
+		fn ensure_raiser_or_force(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			raiser: &T::AccountId,
+		) -> DispatchResult {
+			if let Err(origin) = T::ForceOrigin::try_origin(origin) {
+				let who = frame_system::ensure_signed(origin)?;
+				ensure!(
+					&who == raiser && !ForcedTopics::<T, I>::contains_key(topic_id),
+					Error::<T, I>::NoPermission
+				);
+			}
+			Ok(())
+		}
+
+		/// Ensure `origin` may issue voting right tokens on a topic: `ForceOrigin`, one of the
+		/// topic's issuers, or the raiser of a topic not raised through `ForceOrigin`.
+		fn ensure_issuer(
+			origin: OriginFor<T>,
+			topic_id: T::TopicId,
+			raiser: &T::AccountId,
+		) -> DispatchResult {
+			if let Err(origin) = T::ForceOrigin::try_origin(origin) {
+				let who = frame_system::ensure_signed(origin)?;
+				let is_raiser = &who == raiser && !ForcedTopics::<T, I>::contains_key(topic_id);
+				ensure!(
+					is_raiser || TopicIssuers::<T, I>::get(topic_id).contains(&who),
+					Error::<T, I>::NoPermission
+				);
+			}
+			Ok(())
+		}
+
+		/// Tally the ballots of a topic into its outcome, following its [`TallyMethod`].
+		fn tally(
+			topic_id: T::TopicId,
+			topic_details: &TopicDetails<T::AccountId, T::StringLimit, T::TopicOptionMaximumNumber>,
+		) -> Result<TopicOutcome<T::OptionIndex, T::Vote, T::TopicOptionMaximumNumber>, DispatchError>
+		{
+			let turnout = TopicTurnout::<T, I>::get(topic_id);
+			let (mut winning_options, winning_vote_weight) = match topic_details.tally_method {
+				TallyMethod::Plain | TallyMethod::Quadratic =>
+					Self::most_weighted_options(BallotBox::<T, I>::iter_prefix(topic_id)),
+				TallyMethod::RankedChoice =>
+					Self::ranked_choice_winners(topic_id, topic_details.options.len()),
+				TallyMethod::Threshold { quorum, approval } => {
+					let mut total_vote_weight = T::Vote::initial_value();
+					let (winning_options, winning_vote_weight) = Self::most_weighted_options(
+						BallotBox::<T, I>::iter_prefix(topic_id).inspect(|(_, vote_weight)| {
+							total_vote_weight = total_vote_weight.saturating_add(*vote_weight)
+						}),
+					);
+					let approved = approval.mul_ceil(total_vote_weight.saturated_into::<u128>()) <=
+						winning_vote_weight.saturated_into::<u128>();
+					if turnout >= quorum && approved {
+						(winning_options, winning_vote_weight)
+					} else {
+						(Vec::new(), T::Vote::initial_value())
+					}
+				},
+			};
+			winning_options.sort_unstable();
+
+			Ok(TopicOutcome {
+				winning_options: BoundedVec::try_from(winning_options)
+					.map_err(|_| Error::<T, I>::OptionTooMany)?,
+				winning_vote_weight,
+				turnout,
+			})
+		}
+
+		/// The options sharing the highest non-zero vote weight, and that weight.
+		fn most_weighted_options(
+			vote_weights: impl Iterator<Item = (T::OptionIndex, T::Vote)>,
+		) -> (Vec<T::OptionIndex>, T::Vote) {
+			let mut winning_vote_weight = T::Vote::initial_value();
+			let mut winning_options = Vec::new();
+			for (index, vote_weight) in vote_weights {
+				if vote_weight > winning_vote_weight {
+					winning_vote_weight = vote_weight;
+					winning_options.clear();
+					winning_options.push(index);
+				} else if vote_weight == winning_vote_weight &&
+					vote_weight > T::Vote::initial_value()
+				{
+					winning_options.push(index);
+				}
+			}
+			(winning_options, winning_vote_weight)
+		}
+
+		/// Instant-runoff count of the ranked ballots of a topic.
+		///
+		/// Every round, each ballot counts for its most preferred option that is still running.
+		/// An option holding a strict majority of the counted weight wins. Otherwise the options
+		/// with the least weight are eliminated, unless all running options are tied, in which
+		/// case they all win.
+		fn ranked_choice_winners(
+			topic_id: T::TopicId,
+			option_count: usize,
+		) -> (Vec<T::OptionIndex>, T::Vote) {
+			let ballots = RankedBallots::<T, I>::iter_prefix_values(topic_id).collect::<Vec<_>>();
+			let mut running = sp_std::vec![true; option_count];
+			let mut vote_weights = sp_std::vec![T::Vote::initial_value(); option_count];
+			let mut total_vote_weight = T::Vote::initial_value();
+			// The ballots each option holds, and how far down its ranking each ballot went. Only
+			// the ballots of eliminated options move on, so every ranking is walked at most once
+			// over the whole count.
+			let mut holders = sp_std::vec![Vec::new(); option_count];
+			let mut positions = sp_std::vec![0usize; ballots.len()];
+			let mut moving = (0..ballots.len()).collect::<Vec<_>>();
+
+			loop {
+				for ballot in moving.drain(..) {
+					let (ranking, vote_weight) = &ballots[ballot];
+					while let Some(option) = ranking.get(positions[ballot]) {
+						let option = (*option).saturated_into::<u64>() as usize;
+						if running.get(option).copied().unwrap_or(false) {
+							vote_weights[option] =
+								vote_weights[option].saturating_add(*vote_weight);
+							total_vote_weight = total_vote_weight.saturating_add(*vote_weight);
+							holders[option].push(ballot);
+							break
+						}
+						positions[ballot] += 1;
+					}
+				}
+
+				if total_vote_weight == T::Vote::initial_value() {
+					return (Vec::new(), T::Vote::initial_value())
+				}
+
+				let running_weights = || {
+					(0..option_count)
+						.filter(|option| running[*option])
+						.map(|option| (option, vote_weights[option]))
+				};
+				let highest = running_weights()
+					.map(|(_, vote_weight)| vote_weight)
+					.max()
+					.unwrap_or_else(T::Vote::initial_value);
+				let lowest = running_weights()
+					.map(|(_, vote_weight)| vote_weight)
+					.min()
+					.unwrap_or_else(T::Vote::initial_value);
+
+				if highest.saturating_add(highest) > total_vote_weight || highest == lowest {
+					let winning_options = running_weights()
+						.filter(|(_, vote_weight)| *vote_weight == highest)
+						.map(|(option, _)| T::OptionIndex::from(option as u64))
+						.collect();
+					return (winning_options, highest)
+				}
+
+				for option in 0..option_count {
+					if running[option] && vote_weights[option] == lowest {
+						running[option] = false;
+						total_vote_weight = total_vote_weight.saturating_sub(vote_weights[option]);
+						moving.append(&mut holders[option]);
+					}
+				}
+			}
+		}
 	}
 
 	fn check_title<T: Config<I>, I: 'static>(title: &[u8]) -> DispatchResult {
@@ -549,3 +2892,54 @@ pub mod pallet {
 		Ok(())
 	}
 }
+
+/// Ensure the origin is [`RawOrigin::Topic`] or [`RawOrigin::ForcedTopic`], yielding the topic
+/// identifier.
+///
+/// Any account able to raise a topic can make it dispatch calls from this origin, so it must not
+/// be given any privilege.
+pub struct EnsureTopic<TopicId, I: 'static = ()>(PhantomData<(TopicId, I)>);
+impl<
+		O: Into<Result<RawOrigin<TopicId, I>, O>> + From<RawOrigin<TopicId, I>>,
+		TopicId: Incrementable,
+		I: 'static,
+	> EnsureOrigin<O> for EnsureTopic<TopicId, I>
+{
+	type Success = TopicId;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Topic(id) | RawOrigin::ForcedTopic(id) => Ok(id),
+			r => Err(O::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<O, ()> {
+		Ok(O::from(RawOrigin::Topic(TopicId::initial_value())))
+	}
+}
+
+/// Ensure the origin is [`RawOrigin::ForcedTopic`], yielding the topic identifier.
+///
+/// Only topics raised through `ForceOrigin` dispatch calls from this origin, so it can be given
+/// the privileges `ForceOrigin` is trusted with.
+pub struct EnsureForcedTopic<TopicId, I: 'static = ()>(PhantomData<(TopicId, I)>);
+impl<
+		O: Into<Result<RawOrigin<TopicId, I>, O>> + From<RawOrigin<TopicId, I>>,
+		TopicId: Incrementable,
+		I: 'static,
+	> EnsureOrigin<O> for EnsureForcedTopic<TopicId, I>
+{
+	type Success = TopicId;
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().and_then(|o| match o {
+			RawOrigin::ForcedTopic(id) => Ok(id),
+			r => Err(O::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<O, ()> {
+		Ok(O::from(RawOrigin::ForcedTopic(TopicId::initial_value())))
+	}
+}
@@ -0,0 +1,250 @@
+//! Storage migrations for the DAO pallet.
+
+use frame_support::traits::StorageVersion;
+
+/// The current storage version.
+///
+/// v0-v1: topic lifecycle (`TopicDetails::status`, `TopicTurnout`, `TopicOutcomes`) and
+/// `TopicDetails::tally_method`, and `ActiveTopics`.
+/// v1-v2: `TopicRetentionPeriod` enforced on existing topics.
+pub const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
+pub mod v1 {
+	use frame_support::{pallet_prelude::*, storage::StoragePrefixedMap, traits::OnRuntimeUpgrade};
+	use sp_runtime::traits::Saturating;
+	use sp_std::vec::Vec;
+
+	use super::*;
+	use crate::{
+		ActiveTopic, ActiveTopics, Config, Pallet, TallyMethod, TopicCollection, TopicDetails,
+		TopicStatus, TurnoutCountCursor, VotingRightTokenCollection,
+	};
+
+	#[derive(Decode)]
+	pub struct OldTopicDetails<AccountId, StringLimit: Get<u32>, OptionLimit: Get<u32>> {
+		pub raiser: AccountId,
+		pub title: BoundedVec<u8, StringLimit>,
+		pub description: BoundedVec<u8, StringLimit>,
+		pub voting_period_start: u64,
+		pub voting_period_end: u64,
+		pub options: BoundedVec<BoundedVec<u8, StringLimit>, OptionLimit>,
+		pub required_answer_number: u32,
+	}
+
+	/// Adds a status and the plain tally method to every topic, hands the topics over to the
+	/// hooks for automatic opening and closing and backfills the turnout from the voting right
+	/// tokens that have already been spent.
+	///
+	/// The tokens are counted by `on_initialize` over the following blocks, at most
+	/// `RemoveItemsLimit` of them per block. Until they all are, no topic can be closed.
+	///
+	/// Topics beyond `MaxActiveTopics` are not tracked and have to be closed with `close_topic`.
+	/// Those whose voting period is not over are tracked first, the ones that already ended are
+	/// dropped first, and the dropped topics are logged.
+	pub struct MigrateToV1<T, I = ()>(PhantomData<(T, I)>);
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV1<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() >= 1 {
+				log::info!(target: "runtime::dao", "DAO storage up to date - no need for migration");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let now = Pallet::<T, I>::now();
+			let mut reads = 1u64;
+			let mut writes = 2u64;
+			let mut active_topics = Vec::new();
+
+			TopicCollection::<T, I>::translate::<
+				OldTopicDetails<T::AccountId, T::StringLimit, T::TopicOptionMaximumNumber>,
+				_,
+			>(|topic_id, old| {
+				reads.saturating_inc();
+				writes.saturating_inc();
+				let status = if now < old.voting_period_start {
+					TopicStatus::Pending
+				} else {
+					TopicStatus::Open
+				};
+				active_topics.push(ActiveTopic {
+					id: topic_id,
+					voting_period_start: old.voting_period_start,
+					voting_period_end: old.voting_period_end,
+					reveal_period: 0,
+					opened: status == TopicStatus::Open,
+				});
+				Some(TopicDetails {
+					raiser: old.raiser,
+					title: old.title,
+					description: old.description,
+					voting_period_start: old.voting_period_start,
+					voting_period_end: old.voting_period_end,
+					options: old.options,
+					required_answer_number: old.required_answer_number,
+					status,
+					tally_method: TallyMethod::Plain,
+				})
+			});
+			// Anyone can close a topic whose voting period ended, the others need the hooks to
+			// be opened and closed on time.
+			active_topics
+				.sort_by_key(|topic| (topic.voting_period_end < now, topic.voting_period_end));
+			let dropped = active_topics
+				.iter()
+				.skip(T::MaxActiveTopics::get() as usize)
+				.map(|topic| topic.id)
+				.collect::<Vec<_>>();
+			if !dropped.is_empty() {
+				log::warn!(
+					target: "runtime::dao",
+					"{} topics beyond `MaxActiveTopics` are not tracked and have to be closed with \
+					 `close_topic`: {:?}",
+					dropped.len(),
+					dropped,
+				);
+			}
+			ActiveTopics::<T, I>::put(BoundedVec::truncate_from(active_topics));
+
+			// Counting starts right before the first voting right token.
+			TurnoutCountCursor::<T, I>::put(BoundedVec::truncate_from(
+				VotingRightTokenCollection::<T, I>::final_prefix().to_vec(),
+			));
+			writes.saturating_inc();
+
+			StorageVersion::new(1).put::<Pallet<T, I>>();
+			log::info!(
+				target: "runtime::dao",
+				"Migrated DAO storage to v1, {} topics not tracked",
+				dropped.len(),
+			);
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 0,
+				"Storage version should be `0` before the migration"
+			);
+			Ok((TopicCollection::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let topic_count: u32 =
+				Decode::decode(&mut &state[..]).map_err(|_| "Failed to decode topic count")?;
+			ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 1,
+				"Storage version should be `1` after the migration"
+			);
+			ensure!(
+				TopicCollection::<T, I>::iter_values().count() as u32 == topic_count,
+				"Every topic should decode after the migration"
+			);
+			ensure!(
+				TurnoutCountCursor::<T, I>::exists(),
+				"The turnout should be counted after the migration"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v2 {
+	use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+	use sp_runtime::traits::Saturating;
+	use sp_std::vec::Vec;
+
+	use super::*;
+	use crate::{
+		ClearCursor, Config, Pallet, TopicClearCursors, TopicCollection, TopicStatus,
+		TurnoutCountCursor,
+	};
+
+	/// Enforces `TopicRetentionPeriod` on the topics raised before it existed: those whose
+	/// retention period is over are closed if they were still pending or open, then handed over
+	/// to `on_initialize` to be removed with their voter entries, keeping only their outcome.
+	///
+	/// The voter entries are removed over the following blocks, at most `RemoveItemsLimit` of
+	/// them per block. If the v1 migration is still counting the turnout, closing the topics is
+	/// left to `on_initialize` too, once it is done.
+	pub struct MigrateToV2<T, I = ()>(PhantomData<(T, I)>);
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for MigrateToV2<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T, I>::on_chain_storage_version() >= 2 {
+				log::info!(target: "runtime::dao", "DAO storage up to date - no need for migration");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let now = Pallet::<T, I>::now();
+			let retention_period = T::TopicRetentionPeriod::get();
+			let counting = TurnoutCountCursor::<T, I>::exists();
+			let topics = TopicCollection::<T, I>::iter().collect::<Vec<_>>();
+			let mut weight = T::DbWeight::get().reads_writes(topics.len() as u64 + 2, 1);
+			let mut to_clear = 0u32;
+
+			for (topic_id, details) in topics {
+				if now < details.voting_period_end.saturating_add(retention_period) {
+					continue
+				}
+				if matches!(details.status, TopicStatus::Pending | TopicStatus::Open) {
+					Pallet::<T, I>::deactivate_topic(topic_id);
+					// Otherwise the hooks close the topic once its turnout is counted.
+					if !counting {
+						match Pallet::<T, I>::do_close_topic(topic_id, details) {
+							Ok(close_weight) => weight.saturating_accrue(close_weight),
+							Err(error) => {
+								log::error!(
+									target: "runtime::dao",
+									"Failed to close topic {:?}: {:?}",
+									topic_id,
+									error,
+								);
+								continue
+							},
+						}
+					}
+				}
+				if !TopicClearCursors::<T, I>::contains_key(topic_id) {
+					TopicClearCursors::<T, I>::insert(topic_id, ClearCursor::default());
+				}
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+				to_clear.saturating_inc();
+			}
+
+			STORAGE_VERSION.put::<Pallet<T, I>>();
+			log::info!(
+				target: "runtime::dao",
+				"Migrated DAO storage to v2, {} topics to be cleared",
+				to_clear,
+			);
+
+			weight
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 1,
+				"Storage version should be `1` before the migration"
+			);
+			Ok(Vec::new())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 2,
+				"Storage version should be `2` after the migration"
+			);
+			let now = Pallet::<T, I>::now();
+			ensure!(
+				TopicCollection::<T, I>::iter().all(|(topic_id, details)| now <
+					details.voting_period_end.saturating_add(T::TopicRetentionPeriod::get()) ||
+					TopicClearCursors::<T, I>::contains_key(topic_id)),
+				"Every topic past its retention period should be cleared after the migration"
+			);
+			Ok(())
+		}
+	}
+}
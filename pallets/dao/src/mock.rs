@@ -3,17 +3,23 @@
 use std::sync::Arc;
 
 use frame_support::{
-	construct_runtime,
-	traits::{ConstU128, ConstU32, ConstU64},
+	construct_runtime, parameter_types,
+	traits::{
+		AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64, EitherOfDiverse, EqualPrivilegeOnly,
+		LockIdentifier,
+	},
+	weights::Weight,
 };
+use frame_system::{EnsureRoot, EnsureSigned};
+use pallet_nfts::PalletFeatures;
 use sp_core::H256;
 use sp_keystore::{testing::KeyStore, KeystoreExt};
 use sp_runtime::{
-	testing::Header,
+	testing::{Header, TestSignature, UintAuthorityId},
 	traits::{BlakeTwo256, IdentityLookup},
 };
 
-use crate::{self as pallet_dao};
+use crate::{self as pallet_dao, PeriodMode};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -29,7 +35,11 @@ construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Timestamp: pallet_timestamp,
-		Dao: pallet_dao::{Pallet, Call, Storage, Event<T>},
+		Preimage: pallet_preimage,
+		Scheduler: pallet_scheduler,
+		Assets: pallet_assets,
+		Nfts: pallet_nfts,
+		Dao: pallet_dao::{Pallet, Call, Storage, Event<T>, Origin<T>},
 	}
 );
 
@@ -79,13 +89,129 @@ impl pallet_timestamp::Config for Test {
 	type WeightInfo = ();
 }
 
+impl pallet_preimage::Config for Test {
+	type BaseDeposit = ConstU128<0>;
+	type ByteDeposit = ConstU128<0>;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Weight::from_parts(1_000_000_000_000, u64::MAX);
+}
+
+impl pallet_scheduler::Config for Test {
+	type MaxScheduledPerBlock = ConstU32<100>;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PalletsOrigin = OriginCaller;
+	type Preimages = Preimage;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+impl pallet_assets::Config for Test {
+	type ApprovalDeposit = ConstU128<1>;
+	type AssetAccountDeposit = ConstU128<1>;
+	type AssetDeposit = ConstU128<1>;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Balance = u128;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+	type CallbackHandle = ();
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<u64>>;
+	type Currency = Balances;
+	type Extra = ();
+	type ForceOrigin = EitherOfDiverse<EnsureRoot<u64>, pallet_dao::EnsureForcedTopic<u64>>;
+	type Freezer = ();
+	type MetadataDepositBase = ConstU128<1>;
+	type MetadataDepositPerByte = ConstU128<1>;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type RuntimeEvent = RuntimeEvent;
+	type StringLimit = ConstU32<50>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub NftsFeatures: PalletFeatures = PalletFeatures::all_enabled();
+}
+
+impl pallet_nfts::Config for Test {
+	type ApprovalsLimit = ConstU32<10>;
+	type AttributeDepositBase = ConstU128<1>;
+	type CollectionDeposit = ConstU128<1>;
+	type CollectionId = u32;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<u64>>;
+	type Currency = Balances;
+	type DepositPerByte = ConstU128<1>;
+	type Features = NftsFeatures;
+	type ForceOrigin = EnsureRoot<u64>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = ();
+	type ItemAttributesApprovalsLimit = ConstU32<2>;
+	type ItemDeposit = ConstU128<1>;
+	type ItemId = u32;
+	type KeyLimit = ConstU32<50>;
+	type Locker = ();
+	type MaxAttributesPerCall = ConstU32<2>;
+	type MaxDeadlineDuration = ConstU64<10_000>;
+	type MaxTips = ConstU32<10>;
+	type MetadataDepositBase = ConstU128<1>;
+	type OffchainPublic = UintAuthorityId;
+	type OffchainSignature = TestSignature;
+	type RuntimeEvent = RuntimeEvent;
+	type StringLimit = ConstU32<50>;
+	type ValueLimit = ConstU32<50>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const DaoVoteLockId: LockIdentifier = *b"dao/vote";
+	pub static DaoPeriodMode: PeriodMode = PeriodMode::Timestamp;
+}
+
 impl pallet_dao::Config for Test {
+	type AssetId = u32;
+	type Assets = Assets;
+	type CollectionId = u32;
 	type Currency = Balances;
 	type CurrencyUnits = ConstU128<{ UNITS }>;
 	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type MaxActiveTopics = ConstU32<64>;
+	type MaxActiveTopicsPerRaiser = ConstU32<16>;
+	type MaxAutoClosePerBlock = ConstU32<2>;
+	type MaxDelegators = ConstU32<16>;
+	type MaxIssuersPerTopic = ConstU32<16>;
+	type MaxItemsPerVoter = ConstU32<4>;
+	type MaxRankedChoiceOptions = ConstU32<8>;
+	type MaxRankedChoiceVoters = ConstU32<1024>;
+	type MaxVoteLocks = ConstU32<64>;
+	type MaxVotersPerIssuance = ConstU32<1024>;
+	type MaxVotingDelay = ConstU64<{ 3 * 730 * 60 * 60 }>;
+	type MaxVotingDuration = ConstU64<{ 3 * 730 * 60 * 60 }>;
+	type MinEnactmentTurnout = ConstU32<3>;
+	type MinVotingDelay = ConstU64<{ 60 * 60 }>;
+	type MinVotingDuration = ConstU64<{ 3 * 60 * 60 }>;
+	type Nfts = Nfts;
 	type OptionIndex = u64;
+	type PalletsOrigin = OriginCaller;
+	type PeriodMode = DaoPeriodMode;
+	type Preimages = Preimage;
+	type RemoveItemsLimit = ConstU32<4>;
+	type ReservedActiveTopics = ConstU32<8>;
+	type RevealPeriod = ConstU64<{ 24 * 60 * 60 }>;
 	type RuntimeEvent = RuntimeEvent;
+	type Scheduler = Scheduler;
+	type Slashed = ();
 	type StringLimit = ConstU32<{ 4 * 2048 }>;
+	type TopicDepositBase = ConstU128<{ UNITS }>;
+	type TopicDepositPerByte = ConstU128<1_000>;
 	type TopicDescriptionMaximumLength = ConstU32<2048>;
 	type TopicDescriptionMinimumLength = ConstU32<1>;
 	type TopicId = u64;
@@ -93,10 +219,14 @@ impl pallet_dao::Config for Test {
 	type TopicOptionMaximumNumber = ConstU32<1024>;
 	type TopicOptionMinimumLength = ConstU32<1>;
 	type TopicRaiserBalanceLowerBound = ConstU128<{ 1_000_000 * UNITS }>;
+	type TopicRetentionPeriod = ConstU64<{ 60 * 24 * 60 * 60 }>;
 	type TopicTitleMaximumLength = ConstU32<256>;
 	type TopicTitleMinimumLength = ConstU32<1>;
 	type UnixTime = pallet_timestamp::Pallet<Test>;
 	type Vote = u128;
+	type VoteLockId = DaoVoteLockId;
+	type VoteLockingPeriod = ConstU64<{ 24 * 60 * 60 }>;
+	type WeightInfo = ();
 }
 
 pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
@@ -5,13 +5,19 @@ use std::sync::Arc;
 
 use frame_support::{
 	assert_err, assert_ok,
-	traits::{Currency, Hooks},
+	traits::{Currency, Get, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
 };
 use sp_keystore::{testing::KeyStore, KeystoreExt};
-use sp_runtime::BoundedVec;
+use sp_runtime::{
+	traits::{Hash, IntegerSquareRoot},
+	BoundedVec, DispatchError, Perbill,
+};
 
 use super::*;
-use crate::mock::{new_test_ext, Balances, Dao, RuntimeOrigin, System, Test, Timestamp, UNITS};
+use crate::mock::{
+	new_test_ext, Assets, Balances, Dao, DaoPeriodMode, Nfts, RuntimeCall, RuntimeOrigin,
+	Scheduler, System, Test, Timestamp, UNITS,
+};
 
 const ONE_MILLISECOND: u64 = 1000;
 const ONE_SECOND: u64 = 1;
@@ -1411,6 +1417,8 @@ fn test_raise_topic() {
 					BoundedVec::try_from("charlie".as_bytes().to_vec()).unwrap(),
 				])
 				.unwrap(),
+				status: TopicStatus::Pending,
+				tally_method: TallyMethod::Plain,
 			})
 		);
 
@@ -1443,6 +1451,8 @@ fn test_raise_topic() {
 					BoundedVec::try_from("foxtrot".as_bytes().to_vec()).unwrap(),
 				])
 				.unwrap(),
+				status: TopicStatus::Pending,
+				tally_method: TallyMethod::Plain,
 			})
 		);
 
@@ -1499,3 +1509,2088 @@ fn test_raise_topic() {
 		);
 	});
 }
+
+fn set_now_in_seconds(now: u64) {
+	<Timestamp as Hooks<u64>>::on_finalize(System::block_number());
+	System::on_finalize(System::block_number());
+	System::set_block_number(System::block_number() + 1);
+	<Timestamp as Hooks<u64>>::on_initialize(System::block_number());
+	assert_ok!(Timestamp::set(RuntimeOrigin::none(), now * ONE_MILLISECOND));
+}
+
+fn raise_topic_with_voters(
+	raiser: u64,
+	voting_period_start: u64,
+	voting_period_end: u64,
+	voters: Vec<u64>,
+) -> u64 {
+	let topic_id = TopicCount::<Test>::get().unwrap_or_default();
+	assert_ok!(Dao::raise_topic(
+		RuntimeOrigin::signed(raiser),
+		"dao title".as_bytes().to_vec(),
+		"dao description".as_bytes().to_vec(),
+		voting_period_start,
+		voting_period_end,
+		vec![
+			"alpha".as_bytes().to_vec(),
+			"bravo".as_bytes().to_vec(),
+			"charlie".as_bytes().to_vec()
+		],
+		1
+	));
+	assert_ok!(Dao::issue_voting_right_token(
+		RuntimeOrigin::signed(raiser),
+		topic_id,
+		voters,
+		None
+	));
+	topic_id
+}
+
+#[test]
+fn test_close_and_finalize_topic() {
+	let voting_period_start = 1_000_000;
+	let voting_period_end = voting_period_start + 1_000_000;
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id =
+			raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![3, 4, 5]);
+		assert_eq!(Dao::get_topic_by_id(topic_id).unwrap().status, TopicStatus::Pending);
+
+		set_now_in_seconds(voting_period_start);
+		assert_ok!(Dao::vote_topic(
+			RuntimeOrigin::signed(3),
+			topic_id,
+			BoundedVec::try_from(vec![1]).unwrap()
+		));
+		assert_eq!(Dao::get_topic_by_id(topic_id).unwrap().status, TopicStatus::Open);
+		assert_ok!(Dao::vote_topic(
+			RuntimeOrigin::signed(4),
+			topic_id,
+			BoundedVec::try_from(vec![1]).unwrap()
+		));
+		assert_ok!(Dao::vote_topic(
+			RuntimeOrigin::signed(5),
+			topic_id,
+			BoundedVec::try_from(vec![2]).unwrap()
+		));
+
+		assert_err!(
+			Dao::close_topic(RuntimeOrigin::signed(9), topic_id),
+			Error::<Test, _>::VoteNotEnded
+		);
+		assert_err!(
+			Dao::finalize_topic(RuntimeOrigin::signed(9), topic_id),
+			Error::<Test, _>::InvalidTopicStatus
+		);
+
+		set_now_in_seconds(voting_period_end + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+		System::assert_last_event(
+			Event::<Test>::TopicClosed {
+				id: topic_id,
+				winning_options: BoundedVec::try_from(vec![1]).unwrap(),
+				turnout: 3,
+			}
+			.into(),
+		);
+		assert_eq!(Dao::get_topic_by_id(topic_id).unwrap().status, TopicStatus::Closed);
+		assert_eq!(
+			Dao::get_topic_outcome_by_id(topic_id),
+			Some(TopicOutcome {
+				winning_options: BoundedVec::try_from(vec![1]).unwrap(),
+				winning_vote_weight: Balances::total_balance(&3) + Balances::total_balance(&4),
+				turnout: 3,
+			})
+		);
+		assert_err!(
+			Dao::close_topic(RuntimeOrigin::signed(9), topic_id),
+			Error::<Test, _>::InvalidTopicStatus
+		);
+
+		assert_ok!(Dao::finalize_topic(RuntimeOrigin::signed(9), topic_id));
+		System::assert_last_event(Event::<Test>::TopicFinalized { id: topic_id }.into());
+		assert_eq!(Dao::get_topic_by_id(topic_id).unwrap().status, TopicStatus::Finalized);
+		assert_err!(
+			Dao::finalize_topic(RuntimeOrigin::signed(9), topic_id),
+			Error::<Test, _>::InvalidTopicStatus
+		);
+	});
+}
+
+#[test]
+fn test_close_topic_with_tie_and_without_votes() {
+	let voting_period_start = 1_000_000;
+	let voting_period_end = voting_period_start + 1_000_000;
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let tied_topic =
+			raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![3, 4]);
+		let empty_topic =
+			raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![3, 4]);
+
+		set_now_in_seconds(voting_period_start);
+		assert_ok!(Dao::vote_topic(
+			RuntimeOrigin::signed(3),
+			tied_topic,
+			BoundedVec::try_from(vec![2]).unwrap()
+		));
+		assert_ok!(Dao::vote_topic(
+			RuntimeOrigin::signed(4),
+			tied_topic,
+			BoundedVec::try_from(vec![0]).unwrap()
+		));
+
+		set_now_in_seconds(voting_period_end + 1);
+		assert_err!(
+			Dao::vote_topic(
+				RuntimeOrigin::signed(3),
+				empty_topic,
+				BoundedVec::try_from(vec![0]).unwrap()
+			),
+			Error::<Test, _>::VoteClosed
+		);
+
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), tied_topic));
+		assert_eq!(
+			Dao::get_topic_outcome_by_id(tied_topic).unwrap().winning_options.into_inner(),
+			vec![0, 2]
+		);
+
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), empty_topic));
+		assert_eq!(
+			Dao::get_topic_outcome_by_id(empty_topic),
+			Some(TopicOutcome {
+				winning_options: BoundedVec::default(),
+				winning_vote_weight: 0,
+				turnout: 0,
+			})
+		);
+	});
+}
+
+#[test]
+fn test_finalize_topic_schedules_winning_option_call() {
+	let voting_period_start = 1_000_000;
+	let voting_period_end = voting_period_start + 1_000_000;
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id =
+			raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![3, 4, 5]);
+		let alpha_call: RuntimeCall =
+			frame_system::Call::remark_with_event { remark: b"alpha".to_vec() }.into();
+		let bravo_call: RuntimeCall =
+			frame_system::Call::remark_with_event { remark: b"bravo".to_vec() }.into();
+
+		assert_err!(
+			Dao::set_option_call(
+				RuntimeOrigin::signed(3),
+				topic_id,
+				0,
+				Some(Box::new(alpha_call.clone()))
+			),
+			Error::<Test, _>::NoPermission
+		);
+		assert_err!(
+			Dao::set_option_call(
+				RuntimeOrigin::signed(2),
+				topic_id,
+				3,
+				Some(Box::new(alpha_call.clone()))
+			),
+			Error::<Test, _>::InvalidOption
+		);
+		assert_ok!(Dao::set_option_call(
+			RuntimeOrigin::signed(2),
+			topic_id,
+			0,
+			Some(Box::new(alpha_call.clone()))
+		));
+		assert_ok!(Dao::set_option_call(
+			RuntimeOrigin::root(),
+			topic_id,
+			1,
+			Some(Box::new(bravo_call))
+		));
+		assert_eq!(TopicOptionCalls::<Test>::iter_prefix(topic_id).count(), 2);
+
+		set_now_in_seconds(voting_period_start);
+		assert_err!(
+			Dao::set_option_call(RuntimeOrigin::signed(2), topic_id, 0, None),
+			Error::<Test, _>::VoteAlreadyOpen
+		);
+		for (voter, option) in [(3, 0), (4, 0), (5, 1)] {
+			assert_ok!(Dao::vote_topic(
+				RuntimeOrigin::signed(voter),
+				topic_id,
+				BoundedVec::try_from(vec![option]).unwrap()
+			));
+		}
+
+		set_now_in_seconds(voting_period_end + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+		assert_ok!(Dao::finalize_topic(RuntimeOrigin::signed(9), topic_id));
+		System::assert_has_event(
+			Event::<Test>::TopicEnactmentScheduled { id: topic_id, option: 0 }.into(),
+		);
+		assert_eq!(TopicOptionCalls::<Test>::iter_prefix(topic_id).count(), 0);
+
+		// The call is dispatched from the topic origin, which `remark_with_event` rejects.
+		let enactment_block = System::block_number() + 1;
+		System::set_block_number(enactment_block);
+		<Scheduler as Hooks<u64>>::on_initialize(enactment_block);
+		System::assert_has_event(
+			pallet_scheduler::Event::<Test>::Dispatched {
+				task: (enactment_block, 0),
+				id: None,
+				result: Err(DispatchError::BadOrigin),
+			}
+			.into(),
+		);
+	});
+}
+
+/// Vote for the first option of a topic with `MinEnactmentTurnout` voters, finalize it and run
+/// the scheduler on the enactment block.
+fn enact_first_option(topic_id: u64, voting_period_start: u64, voting_period_end: u64) {
+	let voters: Vec<u64> = (0..<Test as Config>::MinEnactmentTurnout::get())
+		.map(|seed| 10 + u64::from(seed))
+		.collect();
+	assert_ok!(Dao::issue_voting_right_token(
+		RuntimeOrigin::root(),
+		topic_id,
+		voters.clone(),
+		None
+	));
+
+	set_now_in_seconds(voting_period_start);
+	for voter in voters {
+		assert_ok!(Dao::vote_topic(
+			RuntimeOrigin::signed(voter),
+			topic_id,
+			BoundedVec::try_from(vec![0]).unwrap()
+		));
+	}
+
+	set_now_in_seconds(voting_period_end + 1);
+	assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+	assert_ok!(Dao::finalize_topic(RuntimeOrigin::signed(9), topic_id));
+
+	let enactment_block = System::block_number() + 1;
+	System::set_block_number(enactment_block);
+	<Scheduler as Hooks<u64>>::on_initialize(enactment_block);
+}
+
+#[test]
+fn test_only_force_raised_topics_dispatch_privileged_calls() {
+	let voting_period_start = 1_000_000;
+	let voting_period_end = voting_period_start + 1_000_000;
+	let force_create = |id: u32| -> Box<RuntimeCall> {
+		Box::new(
+			pallet_assets::Call::force_create { id, owner: 2, is_sufficient: true, min_balance: 1 }
+				.into(),
+		)
+	};
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		// Anyone able to raise a topic picks its voters, so its origin has no privileges.
+		let topic_id = raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![]);
+		assert_ok!(Dao::set_option_call(
+			RuntimeOrigin::signed(2),
+			topic_id,
+			0,
+			Some(force_create(1))
+		));
+		enact_first_option(topic_id, voting_period_start, voting_period_end);
+		System::assert_has_event(
+			pallet_scheduler::Event::<Test>::Dispatched {
+				task: (System::block_number(), 0),
+				id: None,
+				result: Err(DispatchError::BadOrigin),
+			}
+			.into(),
+		);
+		assert!(!pallet_assets::Asset::<Test>::contains_key(1));
+
+		let now = voting_period_end + 2 * ONE_HOUR;
+		let (voting_period_start, voting_period_end) = (now + ONE_HOUR, now + 4 * ONE_HOUR);
+		let topic_id = TopicCount::<Test>::get().unwrap();
+		assert_ok!(Dao::force_raise_topic(
+			RuntimeOrigin::root(),
+			2,
+			b"dao title".to_vec(),
+			b"dao description".to_vec(),
+			voting_period_start,
+			voting_period_end,
+			vec![b"alpha".to_vec(), b"bravo".to_vec()],
+			1
+		));
+		assert_ok!(Dao::set_option_call(RuntimeOrigin::root(), topic_id, 0, Some(force_create(2))));
+		enact_first_option(topic_id, voting_period_start, voting_period_end);
+		assert!(pallet_assets::Asset::<Test>::contains_key(2));
+	});
+}
+
+#[test]
+fn test_force_raised_topic_is_managed_by_force_origin_only() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id = TopicCount::<Test>::get().unwrap_or_default();
+		assert_ok!(Dao::force_raise_topic(
+			RuntimeOrigin::root(),
+			2,
+			b"dao title".to_vec(),
+			b"dao description".to_vec(),
+			1_000_000,
+			2_000_000,
+			vec![b"alpha".to_vec(), b"bravo".to_vec()],
+			1
+		));
+		let raiser = || RuntimeOrigin::signed(2);
+
+		assert_err!(
+			Dao::set_option_call(raiser(), topic_id, 0, None),
+			Error::<Test, _>::NoPermission
+		);
+		assert_err!(
+			Dao::issue_voting_right_token(raiser(), topic_id, vec![3], None),
+			Error::<Test, _>::NoPermission
+		);
+		assert_err!(Dao::add_topic_issuer(raiser(), topic_id, 3), Error::<Test, _>::NoPermission);
+		assert_err!(
+			Dao::set_tally_method(raiser(), topic_id, TallyMethod::Quadratic),
+			Error::<Test, _>::NoPermission
+		);
+		assert_err!(
+			Dao::edit_topic(
+				raiser(),
+				topic_id,
+				b"dao title".to_vec(),
+				b"dao description".to_vec(),
+				1_000_000,
+				2_000_000,
+				vec![b"alpha".to_vec(), b"bravo".to_vec()],
+				1
+			),
+			Error::<Test, _>::NoPermission
+		);
+		assert_err!(Dao::cancel_topic(raiser(), topic_id), Error::<Test, _>::NoPermission);
+
+		// Issuers added by `ForceOrigin` can still issue voting right tokens.
+		assert_ok!(Dao::add_topic_issuer(RuntimeOrigin::root(), topic_id, 3));
+		assert_ok!(Dao::issue_voting_right_token(
+			RuntimeOrigin::signed(3),
+			topic_id,
+			vec![4],
+			None
+		));
+	});
+}
+
+#[test]
+fn test_finalize_tied_topic_does_not_schedule_calls() {
+	let voting_period_start = 1_000_000;
+	let voting_period_end = voting_period_start + 1_000_000;
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id =
+			raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![3, 4]);
+		for option in [0, 1] {
+			let call: RuntimeCall =
+				frame_system::Call::remark_with_event { remark: vec![option as u8] }.into();
+			assert_ok!(Dao::set_option_call(
+				RuntimeOrigin::signed(2),
+				topic_id,
+				option,
+				Some(Box::new(call))
+			));
+		}
+
+		set_now_in_seconds(voting_period_start);
+		for (voter, option) in [(3, 0), (4, 1)] {
+			assert_ok!(Dao::vote_topic(
+				RuntimeOrigin::signed(voter),
+				topic_id,
+				BoundedVec::try_from(vec![option]).unwrap()
+			));
+		}
+
+		set_now_in_seconds(voting_period_end + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+		assert_ok!(Dao::finalize_topic(RuntimeOrigin::signed(9), topic_id));
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			crate::mock::RuntimeEvent::Dao(Event::TopicEnactmentScheduled { .. })
+		)));
+		assert_eq!(TopicOptionCalls::<Test>::iter_prefix(topic_id).count(), 0);
+	});
+}
+
+#[test]
+fn test_finalize_topic_below_enactment_turnout_does_not_schedule_calls() {
+	let voting_period_start = 1_000_000;
+	let voting_period_end = voting_period_start + 1_000_000;
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id =
+			raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![3, 4, 5]);
+		let call: RuntimeCall =
+			frame_system::Call::remark_with_event { remark: b"alpha".to_vec() }.into();
+		assert_ok!(Dao::set_option_call(
+			RuntimeOrigin::signed(2),
+			topic_id,
+			0,
+			Some(Box::new(call))
+		));
+
+		// Two of the three voters turn out, one less than `MinEnactmentTurnout`.
+		set_now_in_seconds(voting_period_start);
+		for voter in [3, 4] {
+			assert_ok!(Dao::vote_topic(
+				RuntimeOrigin::signed(voter),
+				topic_id,
+				BoundedVec::try_from(vec![0]).unwrap()
+			));
+		}
+
+		set_now_in_seconds(voting_period_end + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+		assert_eq!(
+			Dao::get_topic_outcome_by_id(topic_id).unwrap().winning_options.into_inner(),
+			vec![0]
+		);
+		assert_ok!(Dao::finalize_topic(RuntimeOrigin::signed(9), topic_id));
+		System::assert_has_event(
+			Event::<Test>::TopicEnactmentSkipped { id: topic_id, option: 0, turnout: 2 }.into(),
+		);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			crate::mock::RuntimeEvent::Dao(Event::TopicEnactmentScheduled { .. })
+		)));
+		assert_eq!(TopicOptionCalls::<Test>::iter_prefix(topic_id).count(), 0);
+	});
+}
+
+#[test]
+fn test_vrt_issuance_with_too_many_voters() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id = raise_topic_with_voters(2, 1_000_000, 2_000_000, vec![3]);
+		assert_err!(
+			Dao::issue_voting_right_token(
+				RuntimeOrigin::signed(2),
+				topic_id,
+				(0..1025).collect(),
+				None
+			),
+			Error::<Test, _>::TooManyVoters
+		);
+	});
+}
+
+#[test]
+fn test_topic_deposit_is_refunded_on_finalize() {
+	let voting_period_start = 1_000_000;
+	let voting_period_end = voting_period_start + 1_000_000;
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		// "dao title", "dao description", "alpha", "bravo" and "charlie" are 41 bytes.
+		let deposit = UNITS + 41 * 1_000;
+		let free_balance = Balances::free_balance(&2);
+		let topic_id = raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![3]);
+		assert_eq!(
+			Dao::topic_deposit(
+				b"dao title",
+				b"dao description",
+				&[b"alpha".to_vec(), b"bravo".to_vec(), b"charlie".to_vec()]
+			),
+			deposit
+		);
+		assert_eq!(TopicDepositOf::<Test>::get(topic_id), Some(deposit));
+		assert_eq!(Balances::reserved_balance(&2), deposit);
+		assert_eq!(Balances::free_balance(&2), free_balance - deposit);
+
+		set_now_in_seconds(voting_period_end + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+		assert_eq!(Balances::reserved_balance(&2), deposit);
+
+		assert_ok!(Dao::finalize_topic(RuntimeOrigin::signed(9), topic_id));
+		System::assert_has_event(
+			Event::<Test>::TopicDepositRefunded { id: topic_id, raiser: 2, amount: deposit }.into(),
+		);
+		assert_eq!(TopicDepositOf::<Test>::get(topic_id), None);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_eq!(Balances::free_balance(&2), free_balance);
+	});
+}
+
+#[test]
+fn test_force_raised_topic_has_no_deposit() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		assert_ok!(Dao::force_raise_topic(
+			RuntimeOrigin::root(),
+			2,
+			b"dao title".to_vec(),
+			b"dao description".to_vec(),
+			1_000_000,
+			2_000_000,
+			vec![b"alpha".to_vec(), b"bravo".to_vec()],
+			1
+		));
+		assert_eq!(TopicDepositOf::<Test>::get(0), None);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+	});
+}
+
+#[test]
+fn test_slash_topic() {
+	let voting_period_start = 1_000_000;
+	let voting_period_end = voting_period_start + 1_000_000;
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let total_issuance = Balances::total_issuance();
+		let total_balance = Balances::total_balance(&2);
+		let topic_id = raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![3]);
+		let deposit = TopicDepositOf::<Test>::get(topic_id).unwrap();
+		assert_ok!(Dao::set_option_call(
+			RuntimeOrigin::signed(2),
+			topic_id,
+			0,
+			Some(Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] })))
+		));
+
+		assert_err!(Dao::slash_topic(RuntimeOrigin::signed(2), topic_id), DispatchError::BadOrigin);
+		assert_err!(Dao::slash_topic(RuntimeOrigin::root(), 42), Error::<Test, _>::UnknownTopic);
+
+		assert_ok!(Dao::slash_topic(RuntimeOrigin::root(), topic_id));
+		System::assert_last_event(
+			Event::<Test>::TopicSlashed { id: topic_id, raiser: 2, amount: deposit }.into(),
+		);
+		let topic = Dao::get_topic_by_id(topic_id).unwrap();
+		assert_eq!(topic.status, TopicStatus::Cancelled);
+		assert!(topic.title.is_empty());
+		assert!(topic.description.is_empty());
+		assert!(topic.options.is_empty());
+		assert!(!TopicOptionCalls::<Test>::contains_key(topic_id, 0));
+		assert_eq!(TopicDepositOf::<Test>::get(topic_id), None);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_eq!(Balances::total_balance(&2), total_balance - deposit);
+		assert_eq!(Balances::total_issuance(), total_issuance - deposit);
+
+		// A cancelled topic can neither be slashed again nor voted on.
+		assert_err!(
+			Dao::slash_topic(RuntimeOrigin::root(), topic_id),
+			Error::<Test, _>::InvalidTopicStatus
+		);
+		set_now_in_seconds(voting_period_start);
+		assert_err!(
+			Dao::vote_topic(
+				RuntimeOrigin::signed(3),
+				topic_id,
+				BoundedVec::try_from(vec![0]).unwrap()
+			),
+			Error::<Test, _>::VoteClosed
+		);
+	});
+}
+
+#[test]
+fn test_set_voting_mode() {
+	let voting_period_start = 1_000_000;
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id = raise_topic_with_voters(
+			2,
+			voting_period_start,
+			voting_period_start + 1_000_000,
+			vec![3],
+		);
+		assert_eq!(TopicVotingMode::<Test>::get(topic_id), VotingMode::Snapshot);
+
+		assert_err!(
+			Dao::set_voting_mode(RuntimeOrigin::signed(3), topic_id, VotingMode::Locked),
+			Error::<Test, _>::NoPermission
+		);
+		assert_ok!(Dao::set_voting_mode(RuntimeOrigin::signed(2), topic_id, VotingMode::Locked));
+		System::assert_last_event(
+			Event::<Test>::TopicVotingModeSet { id: topic_id, mode: VotingMode::Locked }.into(),
+		);
+		assert_eq!(TopicVotingMode::<Test>::get(topic_id), VotingMode::Locked);
+
+		set_now_in_seconds(voting_period_start);
+		assert_err!(
+			Dao::set_voting_mode(RuntimeOrigin::root(), topic_id, VotingMode::Snapshot),
+			Error::<Test, _>::VoteAlreadyOpen
+		);
+	});
+}
+
+#[test]
+fn test_locked_voting() {
+	let voting_period_start = 1_000_000;
+	let voting_period_end = voting_period_start + 1_000_000;
+	let one_day = 24 * ONE_HOUR;
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id =
+			raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![3, 4, 5]);
+		assert_ok!(Dao::set_voting_mode(RuntimeOrigin::signed(2), topic_id, VotingMode::Locked));
+
+		set_now_in_seconds(voting_period_start);
+		assert_err!(
+			Dao::vote_topic(
+				RuntimeOrigin::signed(3),
+				topic_id,
+				BoundedVec::try_from(vec![0]).unwrap()
+			),
+			Error::<Test, _>::InvalidVotingMode
+		);
+		assert_err!(
+			Dao::vote_topic_with_lock(
+				RuntimeOrigin::signed(3),
+				topic_id,
+				BoundedVec::try_from(vec![0]).unwrap(),
+				Balances::free_balance(&3) + 1,
+				Conviction::None
+			),
+			Error::<Test, _>::InsufficientFunds
+		);
+		assert_err!(
+			Dao::vote_topic_with_lock(
+				RuntimeOrigin::signed(9),
+				topic_id,
+				BoundedVec::try_from(vec![0]).unwrap(),
+				100 * UNITS,
+				Conviction::None
+			),
+			Error::<Test, _>::VoterHasNoVotingRight
+		);
+
+		assert_ok!(Dao::vote_topic_with_lock(
+			RuntimeOrigin::signed(3),
+			topic_id,
+			BoundedVec::try_from(vec![0]).unwrap(),
+			100 * UNITS,
+			Conviction::Locked2x
+		));
+		System::assert_last_event(
+			Event::<Test>::VoteLocked {
+				topic_id,
+				voter: 3,
+				amount: 100 * UNITS,
+				conviction: Conviction::Locked2x,
+				unlock_at: voting_period_end + 2 * one_day,
+			}
+			.into(),
+		);
+		assert_ok!(Dao::vote_topic_with_lock(
+			RuntimeOrigin::signed(4),
+			topic_id,
+			BoundedVec::try_from(vec![1]).unwrap(),
+			1_000 * UNITS,
+			Conviction::None
+		));
+		assert_err!(
+			Dao::vote_topic_with_lock(
+				RuntimeOrigin::signed(4),
+				topic_id,
+				BoundedVec::try_from(vec![1]).unwrap(),
+				1_000 * UNITS,
+				Conviction::None
+			),
+			Error::<Test, _>::VoterHasVoted
+		);
+
+		// The weight is the locked balance scaled by the conviction, not the balance snapshot.
+		assert_eq!(Dao::get_vote_result(topic_id, 0), Some(200 * UNITS));
+		assert_eq!(Dao::get_vote_result(topic_id, 1), Some(100 * UNITS));
+		assert_eq!(Balances::locks(&3)[0].amount, 100 * UNITS);
+		assert_eq!(Balances::locks(&4)[0].amount, 1_000 * UNITS);
+		assert_eq!(Balances::usable_balance(&3), Balances::free_balance(&3) - 100 * UNITS);
+
+		assert_err!(
+			Dao::unlock_vote(RuntimeOrigin::signed(4), topic_id),
+			Error::<Test, _>::VoteLockNotExpired
+		);
+		assert_err!(
+			Dao::unlock_vote(RuntimeOrigin::signed(5), topic_id),
+			Error::<Test, _>::NoVoteLock
+		);
+
+		set_now_in_seconds(voting_period_end + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+
+		assert_ok!(Dao::unlock_vote(RuntimeOrigin::signed(4), topic_id));
+		System::assert_last_event(
+			Event::<Test>::VoteUnlocked { topic_id, voter: 4, amount: 1_000 * UNITS }.into(),
+		);
+		assert!(Balances::locks(&4).is_empty());
+		assert!(VotingLocks::<Test>::get(&4).is_empty());
+
+		assert_err!(
+			Dao::unlock_vote(RuntimeOrigin::signed(3), topic_id),
+			Error::<Test, _>::VoteLockNotExpired
+		);
+		set_now_in_seconds(voting_period_end + 2 * one_day);
+		assert_ok!(Dao::unlock_vote(RuntimeOrigin::signed(3), topic_id));
+		assert!(Balances::locks(&3).is_empty());
+		assert_err!(
+			Dao::unlock_vote(RuntimeOrigin::signed(3), topic_id),
+			Error::<Test, _>::NoVoteLock
+		);
+	});
+}
+
+#[test]
+fn test_vote_locks_on_several_topics() {
+	let voting_period_start = 1_000_000;
+	let voting_period_end = voting_period_start + 1_000_000;
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let first = raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![3]);
+		let second = raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![3]);
+		assert_ok!(Dao::set_voting_mode(RuntimeOrigin::signed(2), first, VotingMode::Locked));
+		assert_ok!(Dao::set_voting_mode(RuntimeOrigin::signed(2), second, VotingMode::Locked));
+
+		set_now_in_seconds(voting_period_start);
+		for (topic_id, amount) in [(first, 300 * UNITS), (second, 100 * UNITS)] {
+			assert_ok!(Dao::vote_topic_with_lock(
+				RuntimeOrigin::signed(3),
+				topic_id,
+				BoundedVec::try_from(vec![0]).unwrap(),
+				amount,
+				Conviction::None
+			));
+		}
+		// Locks on several topics overlap rather than add up.
+		assert_eq!(Balances::locks(&3)[0].amount, 300 * UNITS);
+
+		// A cancelled topic releases its locks right away.
+		assert_ok!(Dao::slash_topic(RuntimeOrigin::root(), first));
+		assert_ok!(Dao::unlock_vote(RuntimeOrigin::signed(3), first));
+		assert_eq!(Balances::locks(&3)[0].amount, 100 * UNITS);
+	});
+}
+
+#[test]
+fn test_vrt_issuance_is_restricted_to_issuers() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id = raise_topic_with_voters(2, 1_000_000, 2_000_000, vec![3]);
+		assert_err!(
+			Dao::issue_voting_right_token(RuntimeOrigin::signed(6), topic_id, vec![4], None),
+			Error::<Test, _>::NoPermission
+		);
+		assert_ok!(Dao::issue_voting_right_token(RuntimeOrigin::root(), topic_id, vec![4], None));
+
+		assert_err!(
+			Dao::add_topic_issuer(RuntimeOrigin::signed(6), topic_id, 6),
+			Error::<Test, _>::NoPermission
+		);
+		assert_ok!(Dao::add_topic_issuer(RuntimeOrigin::signed(2), topic_id, 6));
+		System::assert_last_event(Event::<Test>::TopicIssuerAdded { topic_id, issuer: 6 }.into());
+		assert_err!(
+			Dao::add_topic_issuer(RuntimeOrigin::root(), topic_id, 6),
+			Error::<Test, _>::AlreadyIssuer
+		);
+		assert_ok!(Dao::issue_voting_right_token(
+			RuntimeOrigin::signed(6),
+			topic_id,
+			vec![5],
+			None
+		));
+		assert!(VotingRightTokenCollection::<Test>::contains_key(topic_id, 5));
+
+		// Issuers of one topic cannot issue on another one.
+		let other_topic_id = raise_topic_with_voters(2, 1_000_000, 2_000_000, vec![3]);
+		assert_err!(
+			Dao::issue_voting_right_token(RuntimeOrigin::signed(6), other_topic_id, vec![5], None),
+			Error::<Test, _>::NoPermission
+		);
+
+		assert_ok!(Dao::remove_topic_issuer(RuntimeOrigin::signed(2), topic_id, 6));
+		System::assert_last_event(Event::<Test>::TopicIssuerRemoved { topic_id, issuer: 6 }.into());
+		assert!(!TopicIssuers::<Test>::contains_key(topic_id));
+		assert_err!(
+			Dao::remove_topic_issuer(RuntimeOrigin::signed(2), topic_id, 6),
+			Error::<Test, _>::NotIssuer
+		);
+		assert_err!(
+			Dao::issue_voting_right_token(RuntimeOrigin::signed(6), topic_id, vec![7], None),
+			Error::<Test, _>::NoPermission
+		);
+
+		for issuer in 10..26 {
+			assert_ok!(Dao::add_topic_issuer(RuntimeOrigin::signed(2), topic_id, issuer));
+		}
+		assert_err!(
+			Dao::add_topic_issuer(RuntimeOrigin::signed(2), topic_id, 26),
+			Error::<Test, _>::TooManyIssuers
+		);
+	});
+}
+
+#[test]
+fn test_revoke_voting_right_token() {
+	let voting_period_start = 1_000_000;
+	let voting_period_end = voting_period_start + 1_000_000;
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id =
+			raise_topic_with_voters(2, voting_period_start, voting_period_end, vec![3, 4, 5]);
+		assert_ok!(Dao::add_topic_issuer(RuntimeOrigin::signed(2), topic_id, 6));
+
+		assert_err!(
+			Dao::revoke_voting_right_token(RuntimeOrigin::signed(7), topic_id, 3),
+			Error::<Test, _>::NoPermission
+		);
+		assert_err!(
+			Dao::revoke_voting_right_token(RuntimeOrigin::signed(2), topic_id, 7),
+			Error::<Test, _>::VoterHasNoVotingRight
+		);
+		assert_ok!(Dao::revoke_voting_right_token(RuntimeOrigin::signed(2), topic_id, 3));
+		System::assert_last_event(
+			Event::<Test>::VotingRightTokenRevoked { topic_id, voter: 3 }.into(),
+		);
+		assert_ok!(Dao::revoke_voting_right_token(RuntimeOrigin::signed(6), topic_id, 4));
+		assert!(!VotingRightTokenCollection::<Test>::contains_key(topic_id, 3));
+
+		set_now_in_seconds(voting_period_start);
+		assert_err!(
+			Dao::vote_topic(
+				RuntimeOrigin::signed(3),
+				topic_id,
+				BoundedVec::try_from(vec![0]).unwrap()
+			),
+			Error::<Test, _>::VoterHasNoVotingRight
+		);
+		assert_ok!(Dao::vote_topic(
+			RuntimeOrigin::signed(5),
+			topic_id,
+			BoundedVec::try_from(vec![0]).unwrap()
+		));
+		// A spent token cannot be revoked.
+		assert_err!(
+			Dao::revoke_voting_right_token(RuntimeOrigin::root(), topic_id, 5),
+			Error::<Test, _>::VoterHasVoted
+		);
+
+		// The token can be issued again after being revoked.
+		assert_ok!(Dao::issue_voting_right_token(
+			RuntimeOrigin::signed(2),
+			topic_id,
+			vec![3],
+			None
+		));
+
+		set_now_in_seconds(voting_period_end + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+		assert_err!(
+			Dao::revoke_voting_right_token(RuntimeOrigin::signed(2), topic_id, 3),
+			Error::<Test, _>::VoteClosed
+		);
+	});
+}
+
+const TALLY_VOTING_PERIOD_START: u64 = 1_000_000;
+const TALLY_VOTING_PERIOD_END: u64 = TALLY_VOTING_PERIOD_START + 1_000_000;
+
+/// Raise a topic with four options counted with `method`, and give every `(voter, ratio)` a
+/// voting right token weighing `ratio` times the voter's balance.
+fn raise_topic_with_tally_method(
+	method: TallyMethod,
+	required_answer_number: u32,
+	voters: &[(u64, u64)],
+) -> u64 {
+	let raiser = 2;
+	let topic_id = TopicCount::<Test>::get().unwrap_or_default();
+	assert_ok!(Dao::raise_topic(
+		RuntimeOrigin::signed(raiser),
+		"dao title".as_bytes().to_vec(),
+		"dao description".as_bytes().to_vec(),
+		TALLY_VOTING_PERIOD_START,
+		TALLY_VOTING_PERIOD_END,
+		vec![
+			"alpha".as_bytes().to_vec(),
+			"bravo".as_bytes().to_vec(),
+			"charlie".as_bytes().to_vec(),
+			"delta".as_bytes().to_vec()
+		],
+		required_answer_number
+	));
+	assert_ok!(Dao::set_tally_method(RuntimeOrigin::signed(raiser), topic_id, method));
+	for (voter, ratio) in voters {
+		assert_ok!(Dao::issue_voting_right_token(
+			RuntimeOrigin::signed(raiser),
+			topic_id,
+			vec![*voter],
+			Some(*ratio)
+		));
+	}
+	topic_id
+}
+
+fn vote(voter: u64, topic_id: u64, options: Vec<u64>) {
+	assert_ok!(Dao::vote_topic(
+		RuntimeOrigin::signed(voter),
+		topic_id,
+		BoundedVec::try_from(options).unwrap()
+	));
+}
+
+#[test]
+fn test_set_tally_method() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id = raise_topic_with_tally_method(TallyMethod::Quadratic, 1, &[]);
+		System::assert_last_event(
+			Event::<Test>::TopicTallyMethodSet { id: topic_id, method: TallyMethod::Quadratic }
+				.into(),
+		);
+		assert_eq!(Dao::get_topic_by_id(topic_id).unwrap().tally_method, TallyMethod::Quadratic);
+		assert_err!(
+			Dao::set_tally_method(RuntimeOrigin::signed(3), topic_id, TallyMethod::Plain),
+			Error::<Test, _>::NoPermission
+		);
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		assert_err!(
+			Dao::set_tally_method(RuntimeOrigin::root(), topic_id, TallyMethod::Plain),
+			Error::<Test, _>::VoteAlreadyOpen
+		);
+	});
+}
+
+#[test]
+fn test_plain_and_quadratic_tally() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		// Three voters with a single share against one voter with four shares.
+		let voters = [(3, 1), (4, 1), (5, 1), (6, 4)];
+		let plain = raise_topic_with_tally_method(TallyMethod::Plain, 1, &voters);
+		let quadratic = raise_topic_with_tally_method(TallyMethod::Quadratic, 1, &voters);
+		let share = Balances::total_balance(&3);
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		for topic_id in [plain, quadratic] {
+			vote(3, topic_id, vec![0]);
+			vote(4, topic_id, vec![0]);
+			vote(5, topic_id, vec![0]);
+			vote(6, topic_id, vec![1]);
+		}
+		assert_eq!(Dao::get_vote_result(quadratic, 0), Some(3 * share.integer_sqrt()));
+		assert_eq!(Dao::get_vote_result(quadratic, 1), Some(2 * share.integer_sqrt()));
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), plain));
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), quadratic));
+		assert_eq!(
+			Dao::get_topic_outcome_by_id(plain),
+			Some(TopicOutcome {
+				winning_options: BoundedVec::try_from(vec![1]).unwrap(),
+				winning_vote_weight: 4 * share,
+				turnout: 4,
+			})
+		);
+		assert_eq!(
+			Dao::get_topic_outcome_by_id(quadratic),
+			Some(TopicOutcome {
+				winning_options: BoundedVec::try_from(vec![0]).unwrap(),
+				winning_vote_weight: 3 * share.integer_sqrt(),
+				turnout: 4,
+			})
+		);
+	});
+}
+
+#[test]
+fn test_ranked_choice_tally() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let voters = [(3, 4), (4, 3), (5, 2), (6, 1)];
+		let topic_id = raise_topic_with_tally_method(TallyMethod::RankedChoice, 3, &voters);
+		let share = Balances::total_balance(&3);
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		vote(3, topic_id, vec![0, 3, 2]);
+		vote(4, topic_id, vec![1, 2, 0]);
+		vote(5, topic_id, vec![2, 1, 0]);
+		vote(6, topic_id, vec![3, 1, 0]);
+		assert_eq!(
+			RankedBallots::<Test>::get(topic_id, 4),
+			Some((BoundedVec::try_from(vec![1, 2, 0]).unwrap(), 3 * share))
+		);
+		// Only the first preferences are in the ballot box.
+		assert_eq!(Dao::get_vote_result(topic_id, 0), Some(4 * share));
+		assert_eq!(Dao::get_vote_result(topic_id, 1), Some(3 * share));
+		assert_eq!(Dao::get_vote_result(topic_id, 2), Some(2 * share));
+		assert_eq!(Dao::get_vote_result(topic_id, 3), Some(share));
+
+		// "delta" is eliminated first and its ballot goes to "bravo", then "charlie" is
+		// eliminated and its ballot goes to "bravo" too, which then holds 6 shares out of 10.
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+		assert_eq!(
+			Dao::get_topic_outcome_by_id(topic_id),
+			Some(TopicOutcome {
+				winning_options: BoundedVec::try_from(vec![1]).unwrap(),
+				winning_vote_weight: 6 * share,
+				turnout: 4,
+			})
+		);
+	});
+}
+
+#[test]
+fn test_ranked_choice_tally_with_tie() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let voters = [(3, 1), (4, 1)];
+		let topic_id = raise_topic_with_tally_method(TallyMethod::RankedChoice, 2, &voters);
+		let share = Balances::total_balance(&3);
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		vote(3, topic_id, vec![2, 0]);
+		vote(4, topic_id, vec![0, 2]);
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+		assert_eq!(
+			Dao::get_topic_outcome_by_id(topic_id),
+			Some(TopicOutcome {
+				winning_options: BoundedVec::try_from(vec![0, 2]).unwrap(),
+				winning_vote_weight: share,
+				turnout: 2,
+			})
+		);
+	});
+}
+
+#[test]
+fn test_ranked_choice_tally_moves_ballots_past_eliminated_options() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let voters = [(3, 4), (4, 3), (5, 2), (6, 1)];
+		let topic_id = raise_topic_with_tally_method(TallyMethod::RankedChoice, 3, &voters);
+		let share = Balances::total_balance(&3);
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		vote(3, topic_id, vec![0, 1, 2]);
+		vote(4, topic_id, vec![1, 0, 2]);
+		vote(5, topic_id, vec![2, 3, 1]);
+		vote(6, topic_id, vec![3, 2, 0]);
+
+		// "delta" is eliminated first and its ballot goes to "charlie", which then ties with
+		// "bravo" at the bottom. Both are eliminated at once: the ballot of 4 goes to "alpha",
+		// the ballot of 5 has no running option left, and the ballot of 6 skips "charlie" for
+		// "alpha", which holds all 8 counted shares.
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+		assert_eq!(
+			Dao::get_topic_outcome_by_id(topic_id),
+			Some(TopicOutcome {
+				winning_options: BoundedVec::try_from(vec![0]).unwrap(),
+				winning_vote_weight: 8 * share,
+				turnout: 4,
+			})
+		);
+	});
+}
+
+#[test]
+fn test_ranked_choice_options_are_capped() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let raiser = 2;
+		let options = |count: u32| {
+			(0..count)
+				.map(|option| format!("option {}", option).into_bytes())
+				.collect::<Vec<_>>()
+		};
+		let too_many = <Test as Config>::MaxRankedChoiceOptions::get() + 1;
+
+		let topic_id = TopicCount::<Test>::get().unwrap_or_default();
+		assert_ok!(Dao::raise_topic(
+			RuntimeOrigin::signed(raiser),
+			"dao title".as_bytes().to_vec(),
+			"dao description".as_bytes().to_vec(),
+			TALLY_VOTING_PERIOD_START,
+			TALLY_VOTING_PERIOD_END,
+			options(too_many),
+			1
+		));
+		assert_err!(
+			Dao::set_tally_method(
+				RuntimeOrigin::signed(raiser),
+				topic_id,
+				TallyMethod::RankedChoice
+			),
+			Error::<Test, _>::OptionTooMany
+		);
+
+		let topic_id = raise_topic_with_tally_method(TallyMethod::RankedChoice, 1, &[]);
+		assert_err!(
+			Dao::edit_topic(
+				RuntimeOrigin::signed(raiser),
+				topic_id,
+				"dao title".as_bytes().to_vec(),
+				"dao description".as_bytes().to_vec(),
+				TALLY_VOTING_PERIOD_START,
+				TALLY_VOTING_PERIOD_END,
+				options(too_many),
+				1
+			),
+			Error::<Test, _>::OptionTooMany
+		);
+		assert_ok!(Dao::edit_topic(
+			RuntimeOrigin::signed(raiser),
+			topic_id,
+			"dao title".as_bytes().to_vec(),
+			"dao description".as_bytes().to_vec(),
+			TALLY_VOTING_PERIOD_START,
+			TALLY_VOTING_PERIOD_END,
+			options(too_many - 1),
+			1
+		));
+	});
+}
+
+#[test]
+fn test_threshold_tally() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let voters = [(3, 1), (4, 1), (5, 1)];
+		let share = Balances::total_balance(&3);
+		let no_outcome = TopicOutcome {
+			winning_options: BoundedVec::default(),
+			winning_vote_weight: 0,
+			turnout: 3,
+		};
+		// "alpha" receives two thirds of the weight from three voters.
+		let cases = [
+			(
+				3,
+				60,
+				TopicOutcome {
+					winning_options: BoundedVec::try_from(vec![0]).unwrap(),
+					winning_vote_weight: 2 * share,
+					turnout: 3,
+				},
+			),
+			(3, 70, no_outcome.clone()),
+			(4, 60, no_outcome),
+		];
+		let topic_ids = cases
+			.iter()
+			.map(|(quorum, approval, _)| {
+				raise_topic_with_tally_method(
+					TallyMethod::Threshold {
+						quorum: *quorum,
+						approval: Perbill::from_percent(*approval),
+					},
+					1,
+					&voters,
+				)
+			})
+			.collect::<Vec<_>>();
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		for topic_id in topic_ids.iter() {
+			vote(3, *topic_id, vec![0]);
+			vote(4, *topic_id, vec![0]);
+			vote(5, *topic_id, vec![1]);
+		}
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + 1);
+		for (topic_id, (_, _, outcome)) in topic_ids.into_iter().zip(cases) {
+			assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+			assert_eq!(Dao::get_topic_outcome_by_id(topic_id), Some(outcome));
+		}
+	});
+}
+
+#[test]
+fn test_topics_open_and_close_automatically() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id = raise_topic_with_tally_method(TallyMethod::Plain, 1, &[(3, 1)]);
+		assert_eq!(
+			ActiveTopics::<Test>::get().into_inner(),
+			vec![ActiveTopic {
+				id: topic_id,
+				voting_period_start: TALLY_VOTING_PERIOD_START,
+				voting_period_end: TALLY_VOTING_PERIOD_END,
+				reveal_period: 0,
+				opened: false,
+			}]
+		);
+
+		<Dao as Hooks<u64>>::on_initialize(System::block_number());
+		assert_eq!(Dao::get_topic_by_id(topic_id).unwrap().status, TopicStatus::Pending);
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		<Dao as Hooks<u64>>::on_initialize(System::block_number());
+		assert_eq!(Dao::get_topic_by_id(topic_id).unwrap().status, TopicStatus::Open);
+		System::assert_last_event(Event::<Test>::TopicOpened { id: topic_id }.into());
+		assert!(ActiveTopics::<Test>::get()[0].opened);
+		vote(3, topic_id, vec![1]);
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + 1);
+		<Dao as Hooks<u64>>::on_initialize(System::block_number());
+		assert_eq!(Dao::get_topic_by_id(topic_id).unwrap().status, TopicStatus::Closed);
+		System::assert_last_event(
+			Event::<Test>::TopicClosed {
+				id: topic_id,
+				winning_options: BoundedVec::try_from(vec![1]).unwrap(),
+				turnout: 1,
+			}
+			.into(),
+		);
+		assert!(ActiveTopics::<Test>::get().is_empty());
+		assert_err!(
+			Dao::close_topic(RuntimeOrigin::signed(3), topic_id),
+			Error::<Test, _>::InvalidTopicStatus
+		);
+	});
+}
+
+#[test]
+fn test_auto_close_is_bounded_per_block() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_ids: Vec<u64> = (0..4)
+			.map(|_| raise_topic_with_tally_method(TallyMethod::Plain, 1, &[]))
+			.collect();
+		let status = |topic_id| Dao::get_topic_by_id(topic_id).unwrap().status;
+
+		// A manually closed topic is no longer tracked.
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(3), topic_ids[0]));
+		assert_eq!(ActiveTopics::<Test>::get().len(), 3);
+
+		// `MaxAutoClosePerBlock` is 2 in the mock.
+		<Dao as Hooks<u64>>::on_initialize(System::block_number());
+		assert_eq!(status(topic_ids[1]), TopicStatus::Closed);
+		assert_eq!(status(topic_ids[2]), TopicStatus::Closed);
+		assert_eq!(status(topic_ids[3]), TopicStatus::Pending);
+		assert_eq!(ActiveTopics::<Test>::get().len(), 1);
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + 2);
+		<Dao as Hooks<u64>>::on_initialize(System::block_number());
+		assert_eq!(status(topic_ids[3]), TopicStatus::Closed);
+		assert!(ActiveTopics::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn test_block_number_period_mode() {
+	new_test_ext().execute_with(|| {
+		DaoPeriodMode::set(PeriodMode::BlockNumber);
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let raise = |start: u64, end: u64| {
+			Dao::raise_topic(
+				RuntimeOrigin::signed(2),
+				"dao title".as_bytes().to_vec(),
+				"dao description".as_bytes().to_vec(),
+				start,
+				end,
+				vec!["yes".as_bytes().to_vec(), "no".as_bytes().to_vec()],
+				1,
+			)
+		};
+
+		// The block number is 6 and the minimum delay is 3_600 blocks.
+		assert_err!(raise(3_000, 30_000), Error::<Test, _>::InvalidVotingPeriodStart);
+		assert_err!(raise(10_000, 15_000), Error::<Test, _>::InvalidVotingPeriodEnd);
+		assert_ok!(raise(10_000, 30_000));
+		let topic_id = 0;
+
+		// Moving the timestamp does not open the topic.
+		set_now_in_seconds(10_000);
+		<Dao as Hooks<u64>>::on_initialize(System::block_number());
+		assert_eq!(Dao::get_topic_by_id(topic_id).unwrap().status, TopicStatus::Pending);
+
+		System::set_block_number(10_000);
+		<Dao as Hooks<u64>>::on_initialize(System::block_number());
+		assert_eq!(Dao::get_topic_by_id(topic_id).unwrap().status, TopicStatus::Open);
+
+		System::set_block_number(30_001);
+		<Dao as Hooks<u64>>::on_initialize(System::block_number());
+		assert_eq!(Dao::get_topic_by_id(topic_id).unwrap().status, TopicStatus::Closed);
+	});
+}
+
+#[test]
+fn test_commit_reveal_voting() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id = raise_topic_with_tally_method(TallyMethod::Plain, 1, &[(3, 1), (4, 1)]);
+		assert_ok!(Dao::set_voting_mode(
+			RuntimeOrigin::signed(2),
+			topic_id,
+			VotingMode::CommitReveal
+		));
+		assert_eq!(ActiveTopics::<Test>::get()[0].reveal_period, 24 * 60 * 60);
+
+		let options: BoundedVec<_, _> = BoundedVec::try_from(vec![1]).unwrap();
+		let salt = [42u8; 32];
+		let commitment = <Test as frame_system::Config>::Hashing::hash_of(&(&options, &salt));
+		assert_err!(
+			Dao::commit_vote(RuntimeOrigin::signed(3), topic_id, commitment),
+			Error::<Test, _>::VoteNotOpen
+		);
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		assert_err!(
+			Dao::vote_topic(RuntimeOrigin::signed(3), topic_id, options.clone()),
+			Error::<Test, _>::InvalidVotingMode
+		);
+		assert_err!(
+			Dao::commit_vote(RuntimeOrigin::signed(5), topic_id, commitment),
+			Error::<Test, _>::VoterHasNoVotingRight
+		);
+		assert_ok!(Dao::commit_vote(RuntimeOrigin::signed(3), topic_id, commitment));
+		System::assert_last_event(Event::<Test>::VoteCommitted { topic_id, voter: 3 }.into());
+		assert_ok!(Dao::commit_vote(RuntimeOrigin::signed(4), topic_id, commitment));
+		assert_eq!(BallotBox::<Test>::iter_prefix(topic_id).count(), 0);
+
+		// Ballots cannot be revealed while the voting period is running.
+		assert_err!(
+			Dao::reveal_vote(RuntimeOrigin::signed(3), topic_id, options.clone(), salt),
+			Error::<Test, _>::VoteNotOpen
+		);
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + 1);
+		assert_err!(
+			Dao::commit_vote(RuntimeOrigin::signed(3), topic_id, commitment),
+			Error::<Test, _>::VoteClosed
+		);
+		assert_err!(
+			Dao::reveal_vote(
+				RuntimeOrigin::signed(3),
+				topic_id,
+				BoundedVec::try_from(vec![0]).unwrap(),
+				salt
+			),
+			Error::<Test, _>::InvalidVoteReveal
+		);
+		assert_err!(
+			Dao::reveal_vote(RuntimeOrigin::signed(5), topic_id, options.clone(), salt),
+			Error::<Test, _>::NoVoteCommitment
+		);
+		assert_ok!(Dao::reveal_vote(RuntimeOrigin::signed(3), topic_id, options.clone(), salt));
+		assert!(VoteCommitments::<Test>::get(topic_id, 3).is_none());
+		assert_err!(
+			Dao::reveal_vote(RuntimeOrigin::signed(3), topic_id, options, salt),
+			Error::<Test, _>::NoVoteCommitment
+		);
+
+		// The topic stays open during the reveal window.
+		<Dao as Hooks<u64>>::on_initialize(System::block_number());
+		assert_err!(
+			Dao::close_topic(RuntimeOrigin::signed(3), topic_id),
+			Error::<Test, _>::VoteNotEnded
+		);
+
+		// The ballot of voter 4 is never revealed and not counted.
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + 24 * 60 * 60 + 1);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(3), topic_id));
+		let outcome = TopicOutcomes::<Test>::get(topic_id).unwrap();
+		assert_eq!(outcome.winning_options.into_inner(), vec![1]);
+		assert_eq!(outcome.turnout, 1);
+	});
+}
+
+#[test]
+fn test_runtime_api_queries() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let first = raise_topic_with_tally_method(TallyMethod::Plain, 1, &[(3, 1), (4, 1)]);
+		let second = raise_topic_with_tally_method(TallyMethod::Quadratic, 1, &[]);
+
+		let topics = Dao::topics(None, 10);
+		assert_eq!(topics.len(), 2);
+		let page = Dao::topics(None, 1);
+		assert_eq!(page.len(), 1);
+		let next_page = Dao::topics(Some(page[0].id), 10);
+		assert_eq!(next_page.len(), 1);
+		assert_ne!(page[0].id, next_page[0].id);
+
+		let info = Dao::topic_info(second).unwrap();
+		assert_eq!(info.title, b"dao title".to_vec());
+		assert_eq!(info.options.len(), 4);
+		assert_eq!(info.tally_method, TallyMethod::Quadratic);
+		assert_eq!(info.voting_mode, VotingMode::Snapshot);
+		assert!(Dao::topic_info(42).is_none());
+
+		assert_eq!(Dao::voter_status(first, &5), VoterStatus::NoVotingRight);
+		assert!(matches!(Dao::voter_status(first, &3), VoterStatus::NotVoted { .. }));
+		assert!(!Dao::can_vote(first, &3));
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		assert!(Dao::can_vote(first, &3));
+		assert!(!Dao::can_vote(first, &5));
+		vote(3, first, vec![2]);
+		assert_eq!(Dao::voter_status(first, &3), VoterStatus::Voted);
+		assert!(!Dao::can_vote(first, &3));
+
+		let results = Dao::topic_results(first).unwrap();
+		assert_eq!(results.turnout, 1);
+		assert_eq!(results.votes.len(), 1);
+		assert_eq!(results.votes[0].index, 2);
+		assert!(results.winning_options.is_none());
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + 1);
+		assert!(!Dao::can_vote(first, &4));
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(3), first));
+		assert_eq!(Dao::topic_results(first).unwrap().winning_options, Some(vec![2]));
+		assert!(Dao::topic_results(42).is_none());
+	});
+}
+
+#[test]
+fn test_vote_delegation() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id =
+			raise_topic_with_tally_method(TallyMethod::Plain, 1, &[(3, 1), (4, 1), (5, 1)]);
+		let weight = VotingRightTokenCollection::<Test>::get(topic_id, 3).unwrap();
+
+		assert_err!(
+			Dao::delegate(RuntimeOrigin::signed(4), 4, DelegationScope::Global),
+			Error::<Test, _>::SelfDelegation
+		);
+		assert_err!(
+			Dao::delegate(RuntimeOrigin::signed(4), 3, DelegationScope::Topic(42)),
+			Error::<Test, _>::UnknownTopic
+		);
+		assert_ok!(Dao::delegate(RuntimeOrigin::signed(4), 3, DelegationScope::Global));
+		System::assert_last_event(
+			Event::<Test>::Delegated { delegator: 4, delegate: 3, scope: DelegationScope::Global }
+				.into(),
+		);
+		// A delegation limited to the topic takes precedence over a global one.
+		assert_ok!(Dao::delegate(RuntimeOrigin::signed(5), 6, DelegationScope::Global));
+		assert_ok!(Dao::delegate(RuntimeOrigin::signed(5), 3, DelegationScope::Topic(topic_id)));
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		vote(3, topic_id, vec![1]);
+		System::assert_has_event(
+			Event::<Test>::DelegatedVotesCounted {
+				topic_id,
+				delegate: 3,
+				delegators: 2,
+				vote_weight: 2 * weight,
+			}
+			.into(),
+		);
+		assert_eq!(BallotBox::<Test>::get(topic_id, 1), Some(3 * weight));
+
+		// Voting directly overrides the delegation.
+		vote(4, topic_id, vec![2]);
+		System::assert_has_event(
+			Event::<Test>::DelegatedVoteWithdrawn {
+				topic_id,
+				delegator: 4,
+				delegate: 3,
+				vote_weight: weight,
+			}
+			.into(),
+		);
+		assert_eq!(BallotBox::<Test>::get(topic_id, 1), Some(2 * weight));
+		assert_eq!(BallotBox::<Test>::get(topic_id, 2), Some(weight));
+		assert_eq!(TopicTurnout::<Test>::get(topic_id), 2);
+
+		assert_ok!(Dao::undelegate(RuntimeOrigin::signed(5), DelegationScope::Topic(topic_id)));
+		System::assert_last_event(
+			Event::<Test>::Undelegated { delegator: 5, scope: DelegationScope::Topic(topic_id) }
+				.into(),
+		);
+		assert_err!(
+			Dao::undelegate(RuntimeOrigin::signed(5), DelegationScope::Topic(topic_id)),
+			Error::<Test, _>::NotDelegated
+		);
+		assert_eq!(Delegators::<Test>::get(3).into_inner(), vec![(4, DelegationScope::Global)]);
+		assert_eq!(Delegators::<Test>::get(6).into_inner(), vec![(5, DelegationScope::Global)]);
+	});
+}
+
+#[test]
+fn test_delegators_who_voted_are_not_counted_twice() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id = raise_topic_with_tally_method(TallyMethod::Quadratic, 1, &[(3, 1), (4, 1)]);
+		let weight = VotingRightTokenCollection::<Test>::get(topic_id, 3).unwrap();
+		assert_ok!(Dao::delegate(RuntimeOrigin::signed(4), 3, DelegationScope::Global));
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		vote(4, topic_id, vec![0]);
+		vote(3, topic_id, vec![1]);
+		assert_eq!(BallotBox::<Test>::get(topic_id, 0), Some(weight.integer_sqrt()));
+		assert_eq!(BallotBox::<Test>::get(topic_id, 1), Some(weight.integer_sqrt()));
+		assert_eq!(DelegatedVotes::<Test>::iter_prefix(topic_id).count(), 0);
+	});
+}
+
+#[test]
+fn test_revoke_voting_right_token_withdraws_delegated_vote() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id = raise_topic_with_tally_method(TallyMethod::Plain, 2, &[(3, 1), (4, 1)]);
+		let weight = VotingRightTokenCollection::<Test>::get(topic_id, 4).unwrap();
+		assert_ok!(Dao::delegate(RuntimeOrigin::signed(4), 3, DelegationScope::Global));
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		vote(3, topic_id, vec![0, 1]);
+		assert_eq!(BallotBox::<Test>::get(topic_id, 0), Some(2 * weight));
+		assert_eq!(BallotBox::<Test>::get(topic_id, 1), Some(2 * weight));
+
+		// The token of the delegator is unspent, but its weight counts for the delegate.
+		assert_ok!(Dao::revoke_voting_right_token(RuntimeOrigin::signed(2), topic_id, 4));
+		System::assert_has_event(
+			Event::<Test>::DelegatedVoteWithdrawn {
+				topic_id,
+				delegator: 4,
+				delegate: 3,
+				vote_weight: weight,
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::<Test>::VotingRightTokenRevoked { topic_id, voter: 4 }.into(),
+		);
+		assert_eq!(BallotBox::<Test>::get(topic_id, 0), Some(weight));
+		assert_eq!(BallotBox::<Test>::get(topic_id, 1), Some(weight));
+		assert!(!DelegatedVotes::<Test>::contains_key(topic_id, 4));
+		assert!(!VotingRightTokenCollection::<Test>::contains_key(topic_id, 4));
+	});
+}
+
+/// `TopicRetentionPeriod` in the mock.
+const RETENTION_PERIOD: u64 = 60 * 24 * ONE_HOUR;
+
+#[test]
+fn test_clear_topic_in_batches() {
+	let mut ext = new_test_ext();
+	let voters = [(3, 1), (4, 1), (5, 1), (6, 1), (7, 1), (8, 1)];
+	let retained_until = TALLY_VOTING_PERIOD_END + RETENTION_PERIOD;
+	let topic_id = ext.execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id = raise_topic_with_tally_method(TallyMethod::Plain, 1, &voters);
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		for (voter, _) in voters {
+			vote(voter, topic_id, vec![voter % 2]);
+		}
+
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + 1);
+		assert_err!(
+			Dao::clear_topic(RuntimeOrigin::signed(9), topic_id),
+			Error::<Test, _>::InvalidTopicStatus
+		);
+		assert_ok!(Dao::close_topic(RuntimeOrigin::signed(9), topic_id));
+		assert_err!(
+			Dao::clear_topic(RuntimeOrigin::signed(9), topic_id),
+			Error::<Test, _>::RetentionPeriodNotOver
+		);
+		topic_id
+	});
+	// Removal limits only apply to entries already committed to the backend.
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		set_now_in_seconds(retained_until);
+		assert!(Balances::reserved_balance(2) > 0);
+
+		assert_ok!(Dao::clear_topic(RuntimeOrigin::signed(9), topic_id));
+		System::assert_last_event(
+			Event::<Test>::TopicEntriesRemoved { id: topic_id, removed: 4 }.into(),
+		);
+		assert!(TopicCollection::<Test>::contains_key(topic_id));
+		assert!(TopicClearCursors::<Test>::contains_key(topic_id));
+
+		let mut calls = 1;
+		while TopicCollection::<Test>::contains_key(topic_id) {
+			assert!(calls < 4, "voter entries should be removed in a few batches");
+			assert_ok!(Dao::clear_topic(RuntimeOrigin::signed(9), topic_id));
+			calls += 1;
+		}
+		System::assert_last_event(Event::<Test>::TopicCleared { id: topic_id }.into());
+
+		assert_eq!(VotingRightTokenCollection::<Test>::iter_prefix(topic_id).count(), 0);
+		assert_eq!(BallotBox::<Test>::iter_prefix(topic_id).count(), 0);
+		assert!(!TopicClearCursors::<Test>::contains_key(topic_id));
+		assert!(!TopicDepositOf::<Test>::contains_key(topic_id));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(TopicTurnout::<Test>::get(topic_id), 0);
+		assert_eq!(Dao::get_outcome(topic_id).unwrap().turnout, voters.len() as u32);
+		assert_err!(
+			Dao::clear_topic(RuntimeOrigin::signed(9), topic_id),
+			Error::<Test, _>::UnknownTopic
+		);
+	});
+}
+
+#[test]
+fn test_migration_tracks_running_topics_beyond_max_active_topics() {
+	new_test_ext().execute_with(|| {
+		let now = 1_000_000;
+		set_now_in_seconds(now);
+
+		// Topics in the v0 format, the first ones ended, then ones still running.
+		let max_active_topics = <Test as Config>::MaxActiveTopics::get() as u64;
+		let ended = 10;
+		let topic_count = max_active_topics + 6;
+		for topic_id in 0..topic_count {
+			let voting_period_end =
+				if topic_id < ended { now - ended + topic_id } else { now + topic_id };
+			frame_support::storage::unhashed::put(
+				&TopicCollection::<Test>::hashed_key_for(topic_id),
+				&(
+					2u64,
+					b"dao title".to_vec(),
+					b"dao description".to_vec(),
+					voting_period_end - 100,
+					voting_period_end,
+					vec![b"alpha".to_vec(), b"bravo".to_vec()],
+					1u32,
+				),
+			);
+		}
+		StorageVersion::new(0).put::<Dao>();
+		migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Dao::on_chain_storage_version(), 1);
+		let mut tracked = ActiveTopics::<Test>::get()
+			.into_iter()
+			.map(|topic| topic.id)
+			.collect::<Vec<_>>();
+		tracked.sort();
+		// Every running topic is tracked, only the ended ones which can be closed by anyone are
+		// dropped.
+		assert_eq!(tracked.len() as u64, max_active_topics);
+		assert!((ended..topic_count).all(|topic_id| tracked.contains(&topic_id)));
+		assert_eq!(tracked[..4], [0, 1, 2, 3]);
+		assert_eq!(TopicCollection::<Test>::iter_keys().count() as u64, topic_count);
+	});
+}
+
+#[test]
+fn test_migration_enforces_retention_period() {
+	let mut ext = new_test_ext();
+	let voters = [(3, 1), (4, 1), (5, 1), (6, 1), (7, 1), (8, 1)];
+	let (expired, retained) = ext.execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let expired = raise_topic_with_tally_method(TallyMethod::Plain, 1, &voters);
+		let retained = raise_topic_with_tally_method(TallyMethod::Plain, 1, &[(3, 1)]);
+		TopicCollection::<Test>::mutate(retained, |details| {
+			details.as_mut().unwrap().voting_period_end = 10 * TALLY_VOTING_PERIOD_END
+		});
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		vote(3, expired, vec![1]);
+		(expired, retained)
+	});
+	// Removal limits only apply to entries already committed to the backend.
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		set_now_in_seconds(TALLY_VOTING_PERIOD_END + RETENTION_PERIOD);
+		StorageVersion::new(1).put::<Dao>();
+		migration::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+		// The expired topic is closed right away, but only cleared by the hooks.
+		assert_eq!(Dao::on_chain_storage_version(), 2);
+		assert_eq!(Dao::get_outcome(expired).unwrap().winning_options.into_inner(), vec![1]);
+		assert!(TopicClearCursors::<Test>::contains_key(expired));
+		assert!(!TopicClearCursors::<Test>::contains_key(retained));
+
+		// `RemoveItemsLimit` voter entries per block.
+		Dao::on_initialize(System::block_number());
+		System::assert_has_event(
+			Event::<Test>::TopicEntriesRemoved { id: expired, removed: 4 }.into(),
+		);
+		assert!(TopicCollection::<Test>::contains_key(expired));
+
+		let mut blocks = 1;
+		while TopicCollection::<Test>::contains_key(expired) {
+			assert!(blocks < 4, "voter entries should be removed in a few blocks");
+			Dao::on_initialize(System::block_number());
+			blocks += 1;
+		}
+		System::assert_has_event(Event::<Test>::TopicCleared { id: expired }.into());
+		assert_eq!(VotingRightTokenCollection::<Test>::iter_prefix(expired).count(), 0);
+		assert!(!TopicClearCursors::<Test>::contains_key(expired));
+		assert_eq!(Dao::get_outcome(expired).unwrap().winning_options.into_inner(), vec![1]);
+		assert!(TopicCollection::<Test>::contains_key(retained));
+		assert!(VotingRightTokenCollection::<Test>::contains_key(retained, 3));
+	});
+}
+
+#[test]
+fn test_migration_counts_turnout_over_blocks() {
+	new_test_ext().execute_with(|| {
+		let now = 1_000_000;
+		set_now_in_seconds(now);
+
+		// A running topic in the v0 format, six of its eight voters have voted.
+		let topic_id = 0;
+		frame_support::storage::unhashed::put(
+			&TopicCollection::<Test>::hashed_key_for(topic_id),
+			&(
+				2u64,
+				b"dao title".to_vec(),
+				b"dao description".to_vec(),
+				now - 100,
+				now + 1_000,
+				vec![b"alpha".to_vec(), b"bravo".to_vec()],
+				1u32,
+			),
+		);
+		TopicCount::<Test>::put(1);
+		for voter in 3..11 {
+			let vote_weight = if voter < 9 { 0 } else { UNITS };
+			VotingRightTokenCollection::<Test>::insert(topic_id, voter, vote_weight);
+		}
+		StorageVersion::new(0).put::<Dao>();
+		migration::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Dao::on_chain_storage_version(), 1);
+		assert!(TurnoutCountCursor::<Test>::exists());
+		assert_eq!(TopicTurnout::<Test>::get(topic_id), 0);
+
+		// Votes cast while counting are counted once, whether the token was counted already or
+		// not.
+		vote(9, topic_id, vec![1]);
+		Dao::on_initialize(System::block_number());
+		vote(10, topic_id, vec![1]);
+		set_now_in_seconds(now + 1_001);
+		assert_err!(
+			Dao::close_topic(RuntimeOrigin::signed(9), topic_id),
+			Error::<Test, _>::TurnoutBeingCounted
+		);
+
+		// `RemoveItemsLimit` voting right tokens per block, then the hooks close the topic.
+		let mut blocks = 1;
+		while TurnoutCountCursor::<Test>::exists() {
+			assert!(blocks < 4, "voting right tokens should be counted in a few blocks");
+			assert!(Dao::get_outcome(topic_id).is_none());
+			Dao::on_initialize(System::block_number());
+			blocks += 1;
+		}
+		assert_eq!(TopicTurnout::<Test>::get(topic_id), 8);
+		assert_eq!(Dao::get_outcome(topic_id).unwrap().turnout, 8);
+	});
+}
+
+#[test]
+fn test_active_topics_are_capped_per_raiser_and_kept_for_force_origin() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let raise = |raiser: u64| {
+			Dao::raise_topic(
+				RuntimeOrigin::signed(raiser),
+				b"dao title".to_vec(),
+				b"dao description".to_vec(),
+				TALLY_VOTING_PERIOD_START,
+				TALLY_VOTING_PERIOD_END,
+				vec![b"alpha".to_vec(), b"bravo".to_vec()],
+				1,
+			)
+		};
+		let per_raiser = <Test as Config>::MaxActiveTopicsPerRaiser::get();
+		let unreserved = <Test as Config>::MaxActiveTopics::get() -
+			<Test as Config>::ReservedActiveTopics::get();
+
+		let first = TopicCount::<Test>::get().unwrap_or_default();
+		for _ in 0..per_raiser {
+			assert_ok!(raise(2));
+		}
+		assert_err!(raise(2), Error::<Test, _>::TooManyActiveTopicsOfRaiser);
+		// A topic that is no longer tracked gives its slot back.
+		assert_ok!(Dao::cancel_topic(RuntimeOrigin::signed(2), first));
+		assert_eq!(ActiveTopicsOf::<Test>::get(2), per_raiser - 1);
+		assert_ok!(raise(2));
+
+		let mut raiser = 3;
+		while (ActiveTopics::<Test>::get().len() as u32) < unreserved {
+			if ActiveTopicsOf::<Test>::get(raiser) == per_raiser {
+				raiser += 1;
+			}
+			assert_ok!(raise(raiser));
+		}
+		assert_err!(raise(raiser + 1), Error::<Test, _>::TooManyActiveTopics);
+
+		// The remaining slots are kept for topics raised through `ForceOrigin`, which do not
+		// count towards the limit of their raiser.
+		assert_ok!(Dao::force_raise_topic(
+			RuntimeOrigin::root(),
+			2,
+			b"dao title".to_vec(),
+			b"dao description".to_vec(),
+			TALLY_VOTING_PERIOD_START,
+			TALLY_VOTING_PERIOD_END,
+			vec![b"alpha".to_vec(), b"bravo".to_vec()],
+			1
+		));
+		assert_eq!(ActiveTopics::<Test>::get().len() as u32, unreserved + 1);
+		assert_eq!(ActiveTopicsOf::<Test>::get(2), per_raiser);
+	});
+}
+
+#[test]
+fn test_edit_topic() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id = raise_topic_with_tally_method(TallyMethod::Quadratic, 1, &[(3, 1)]);
+		let deposit = TopicDepositOf::<Test>::get(topic_id).unwrap();
+		assert_ok!(Dao::set_option_call(
+			RuntimeOrigin::signed(2),
+			topic_id,
+			3,
+			Some(Box::new(RuntimeCall::System(frame_system::Call::remark { remark: vec![] })))
+		));
+
+		let edit = |who: u64, options: Vec<&str>, voting_period_start: u64| {
+			Dao::edit_topic(
+				RuntimeOrigin::signed(who),
+				topic_id,
+				"dao title, amended".as_bytes().to_vec(),
+				"dao description".as_bytes().to_vec(),
+				voting_period_start,
+				voting_period_start + 1_000_000,
+				options.into_iter().map(|option| option.as_bytes().to_vec()).collect(),
+				1,
+			)
+		};
+
+		assert_err!(edit(3, vec!["alpha", "bravo"], 2_000_000), Error::<Test, _>::NoPermission);
+		assert_err!(edit(2, vec!["alpha", "alpha"], 2_000_000), Error::<Test, _>::DuplicatedOption);
+		assert_err!(
+			edit(2, vec!["alpha", "bravo"], 90),
+			Error::<Test, _>::InvalidVotingPeriodStart
+		);
+
+		assert_ok!(edit(2, vec!["alpha", "bravo", "charlie"], 2_000_000));
+		System::assert_has_event(
+			Event::<Test>::TopicOptionCallSet { id: topic_id, option: 3, call_hash: None }.into(),
+		);
+		System::assert_last_event(Event::<Test>::TopicEdited { id: topic_id }.into());
+
+		let details = Dao::get_topic_by_id(topic_id).unwrap();
+		assert_eq!(details.title.into_inner(), "dao title, amended".as_bytes().to_vec());
+		assert_eq!(details.options.len(), 3);
+		assert_eq!(details.voting_period_start, 2_000_000);
+		assert_eq!(details.tally_method, TallyMethod::Quadratic);
+		assert!(!TopicOptionCalls::<Test>::contains_key(topic_id, 3));
+		let active_topic = ActiveTopics::<Test>::get().into_iter().next().unwrap();
+		assert_eq!(active_topic.voting_period_start, 2_000_000);
+		assert_eq!(active_topic.voting_period_end, 3_000_000);
+
+		// The deposit follows the size of the topic.
+		let new_deposit = TopicDepositOf::<Test>::get(topic_id).unwrap();
+		// Four more bytes at `TopicDepositPerByte`.
+		assert_eq!(new_deposit, deposit + 4 * 1_000);
+		assert_eq!(Balances::reserved_balance(2), new_deposit);
+
+		set_now_in_seconds(2_000_000);
+		assert_err!(edit(2, vec!["alpha", "bravo"], 3_000_000), Error::<Test, _>::VoteAlreadyOpen);
+	});
+}
+
+#[test]
+fn test_cancel_topic() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		let topic_id = raise_topic_with_tally_method(TallyMethod::Plain, 1, &[(3, 1)]);
+		let opened_topic_id = raise_topic_with_tally_method(TallyMethod::Plain, 1, &[(3, 1)]);
+		let deposit = TopicDepositOf::<Test>::get(topic_id).unwrap();
+		assert_err!(
+			Dao::cancel_topic(RuntimeOrigin::signed(3), topic_id),
+			Error::<Test, _>::NoPermission
+		);
+		assert_ok!(Dao::cancel_topic(RuntimeOrigin::signed(2), topic_id));
+		System::assert_has_event(
+			Event::<Test>::TopicDepositRefunded { id: topic_id, raiser: 2, amount: deposit }.into(),
+		);
+		System::assert_last_event(Event::<Test>::TopicCancelled { id: topic_id }.into());
+		assert_eq!(Dao::get_topic_by_id(topic_id).unwrap().status, TopicStatus::Cancelled);
+		assert_eq!(Balances::reserved_balance(2), deposit);
+		assert!(ActiveTopics::<Test>::get().iter().all(|topic| topic.id != topic_id));
+
+		assert_err!(
+			Dao::issue_voting_right_token(RuntimeOrigin::signed(2), topic_id, vec![4], None),
+			Error::<Test, _>::TopicCancelled
+		);
+		set_now_in_seconds(TALLY_VOTING_PERIOD_START);
+		assert_err!(
+			Dao::vote_topic(
+				RuntimeOrigin::signed(3),
+				topic_id,
+				BoundedVec::try_from(vec![0]).unwrap()
+			),
+			Error::<Test, _>::TopicCancelled
+		);
+		assert_err!(
+			Dao::cancel_topic(RuntimeOrigin::root(), topic_id),
+			Error::<Test, _>::InvalidTopicStatus
+		);
+
+		// Once voting opened, only `ForceOrigin` can cancel a topic.
+		vote(3, opened_topic_id, vec![0]);
+		assert_err!(
+			Dao::cancel_topic(RuntimeOrigin::signed(2), opened_topic_id),
+			Error::<Test, _>::InvalidTopicStatus
+		);
+		assert_ok!(Dao::cancel_topic(RuntimeOrigin::root(), opened_topic_id));
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn test_asset_and_collection_weight_sources() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(46_000);
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 90_000));
+
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 7, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 7, 3, 500));
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			1,
+			pallet_nfts::CollectionConfig {
+				settings: pallet_nfts::CollectionSettings::all_enabled(),
+				max_supply: None,
+				mint_settings: Default::default(),
+			}
+		));
+		for item in 0..8 {
+			let owner = if item < 2 { 4 } else { 5 };
+			assert_ok!(Nfts::mint(RuntimeOrigin::signed(1), 0, item, owner, None));
+		}
+
+		let asset_topic = raise_topic_with_tally_method(TallyMethod::Plain, 1, &[]);
+		assert_err!(
+			Dao::set_weight_source(RuntimeOrigin::signed(2), asset_topic, WeightSource::Asset(8)),
+			Error::<Test, _>::UnknownAsset
+		);
+		assert_ok!(Dao::set_weight_source(
+			RuntimeOrigin::signed(2),
+			asset_topic,
+			WeightSource::Asset(7)
+		));
+		System::assert_last_event(
+			Event::<Test>::TopicWeightSourceSet { id: asset_topic, source: WeightSource::Asset(7) }
+				.into(),
+		);
+		assert_ok!(Dao::issue_voting_right_token(
+			RuntimeOrigin::signed(2),
+			asset_topic,
+			vec![3, 4],
+			Some(2)
+		));
+		assert_eq!(VotingRightTokenCollection::<Test>::get(asset_topic, 3), Some(1_000));
+		assert!(!VotingRightTokenCollection::<Test>::contains_key(asset_topic, 4));
+		// The source cannot change once tokens were issued.
+		assert_err!(
+			Dao::set_weight_source(RuntimeOrigin::signed(2), asset_topic, WeightSource::Native),
+			Error::<Test, _>::VotingRightTokenIssued
+		);
+
+		let collection_topic = raise_topic_with_tally_method(TallyMethod::Plain, 1, &[]);
+		assert_err!(
+			Dao::set_weight_source(
+				RuntimeOrigin::signed(2),
+				collection_topic,
+				WeightSource::Collection(1)
+			),
+			Error::<Test, _>::UnknownCollection
+		);
+		assert_ok!(Dao::set_weight_source(
+			RuntimeOrigin::signed(2),
+			collection_topic,
+			WeightSource::Collection(0)
+		));
+		assert_ok!(Dao::issue_voting_right_token(
+			RuntimeOrigin::signed(2),
+			collection_topic,
+			vec![3, 4, 5],
+			None
+		));
+		assert!(!VotingRightTokenCollection::<Test>::contains_key(collection_topic, 3));
+		assert_eq!(VotingRightTokenCollection::<Test>::get(collection_topic, 4), Some(2));
+		// `MaxItemsPerVoter` is 4 in the mock.
+		assert_eq!(VotingRightTokenCollection::<Test>::get(collection_topic, 5), Some(4));
+	});
+}
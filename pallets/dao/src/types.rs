@@ -1,5 +1,9 @@
 use codec::{Decode, Encode};
-use frame_support::{pallet_prelude::*, sp_runtime::Saturating};
+use frame_support::{
+	pallet_prelude::*,
+	sp_runtime::{Perbill, Saturating},
+};
+use sp_std::{ops::Div, vec::Vec};
 
 use crate::macros::impl_incrementable;
 
@@ -21,12 +25,316 @@ pub struct TopicDetails<AccountId: PartialEq, StringLimit: Get<u32>, OptionLimit
 	pub(super) voting_period_end: u64,
 	pub(super) options: BoundedVec<BoundedVec<u8, StringLimit>, OptionLimit>, // options' names
 	pub(super) required_answer_number: u32,
+	pub(super) status: TopicStatus,
+	pub(super) tally_method: TallyMethod,
+}
+
+/// Lifecycle of a topic.
+#[derive(
+	Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum TopicStatus {
+	/// The topic is raised and its voting period has not started yet.
+	#[default]
+	Pending,
+	/// Voting is in progress; entered when the voting period starts or the first vote is cast.
+	Open,
+	/// The voting period is over and the outcome has been tallied.
+	Closed,
+	/// The outcome is final.
+	Finalized,
+	/// The topic was withdrawn and has no outcome.
+	Cancelled,
+}
+
+/// The unit of the voting periods of topics.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum PeriodMode {
+	/// Voting periods are Unix times, in seconds.
+	Timestamp,
+	/// Voting periods are block numbers.
+	BlockNumber,
+}
+
+/// A topic whose voting period has not ended yet, as tracked by the pallet hooks.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ActiveTopic<TopicId> {
+	pub id: TopicId,
+	pub voting_period_start: u64,
+	pub voting_period_end: u64,
+	/// The length of the reveal window following the voting period, zero unless the topic votes
+	/// with [`VotingMode::CommitReveal`].
+	pub reveal_period: u64,
+	/// Whether the topic was opened, either by the hooks or by its first vote.
+	pub opened: bool,
+}
+
+impl<TopicId> ActiveTopic<TopicId> {
+	/// The time after which the topic can be closed.
+	pub fn ends_at(&self) -> u64 {
+		self.voting_period_end.saturating_add(self.reveal_period)
+	}
+}
+
+/// The maximum length of the storage key kept in a [`ClearCursor`].
+pub const MAX_CLEAR_CURSOR_LENGTH: u32 = 256;
+
+/// How far the voter entries of a topic have been removed by `clear_topic`.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ClearCursor {
+	/// The index of the storage map being cleared, in the order they are cleared.
+	pub map: u8,
+	/// Where the removal from the map stopped, if it did not remove every entry of the topic.
+	pub key: Option<BoundedVec<u8, ConstU32<MAX_CLEAR_CURSOR_LENGTH>>>,
+}
+
+/// How the ballots of a topic are counted when it is closed.
+#[derive(
+	Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum TallyMethod {
+	/// Every chosen option receives the full vote weight; the options with the most weight win.
+	#[default]
+	Plain,
+	/// Every chosen option receives the square root of the vote weight.
+	Quadratic,
+	/// Voters rank options by preference. The options with the least weight are eliminated and
+	/// their ballots passed on to the next preference until an option holds a strict majority.
+	RankedChoice,
+	/// Counted as [`TallyMethod::Plain`], but there is no winner unless at least `quorum` voters
+	/// cast a ballot and the winning options hold at least `approval` of the ballot box weight.
+	Threshold { quorum: u32, approval: Perbill },
+}
+
+/// How the vote weight of a topic's voters is determined.
+#[derive(
+	Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum VotingMode {
+	/// The weight is a snapshot of the voter's holdings in the topic's [`WeightSource`], taken
+	/// when the voting right token is issued.
+	#[default]
+	Snapshot,
+	/// The weight is the balance the voter locks when voting, scaled by a [`Conviction`].
+	Locked,
+	/// The weight is a snapshot, as with [`VotingMode::Snapshot`], but the ballots are sealed:
+	/// voters commit to the hash of their options during the voting period and reveal them in
+	/// the reveal window that follows. Only revealed ballots are counted.
+	CommitReveal,
+}
+
+/// What the voting right tokens of a topic are weighed with when they are issued.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum WeightSource<AssetId, CollectionId> {
+	/// The voter's balance of the native currency.
+	Native,
+	/// The voter's balance of a fungible asset.
+	Asset(AssetId),
+	/// One vote per item of a non-fungible collection the voter holds.
+	Collection(CollectionId),
+}
+
+impl<AssetId, CollectionId> Default for WeightSource<AssetId, CollectionId> {
+	fn default() -> Self {
+		WeightSource::Native
+	}
+}
+
+/// A value denoting the strength of conviction of a locked vote, as in `pallet_democracy`.
+#[derive(
+	Clone,
+	Copy,
+	Default,
+	Encode,
+	Decode,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum Conviction {
+	/// 0.1x votes, unlocked once the topic is closed.
+	#[default]
+	None,
+	/// 1x votes, locked for a vote locking period after the voting period ends.
+	Locked1x,
+	/// 2x votes, locked for 2x vote locking periods after the voting period ends.
+	Locked2x,
+	/// 3x votes, locked for 4x...
+	Locked3x,
+	/// 4x votes, locked for 8x...
+	Locked4x,
+	/// 5x votes, locked for 16x...
+	Locked5x,
+	/// 6x votes, locked for 32x...
+	Locked6x,
+}
+
+impl Conviction {
+	/// The amount of time (in number of periods) that our conviction implies a successful voter's
+	/// balance should be locked for.
+	pub fn lock_periods(self) -> u32 {
+		match self {
+			Conviction::None => 0,
+			Conviction::Locked1x => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked3x => 4,
+			Conviction::Locked4x => 8,
+			Conviction::Locked5x => 16,
+			Conviction::Locked6x => 32,
+		}
+	}
+
+	/// The votes of a voter who locks `capital` with this conviction.
+	pub fn votes<B: From<u8> + Saturating + Div<Output = B>>(self, capital: B) -> B {
+		match self {
+			Conviction::None => capital / 10u8.into(),
+			x => capital.saturating_mul((x as u8).into()),
+		}
+	}
+}
+
+/// The topics a delegation applies to.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum DelegationScope<TopicId> {
+	/// Every topic, unless the delegator delegated the topic to someone else.
+	Global,
+	/// A single topic.
+	Topic(TopicId),
+}
+
+/// Balance locked by a voter on a topic with [`VotingMode::Locked`].
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct VoteLock<TopicId, Balance> {
+	/// The topic the balance was locked for.
+	pub topic_id: TopicId,
+	/// The locked balance.
+	pub amount: Balance,
+	/// Time, in the unit of `Config::PeriodMode`, before which the balance stays locked.
+	pub unlock_at: u64,
+}
+
+/// Tallied result of a closed topic.
+#[derive(Clone, Encode, Decode, Eq, PartialEqNoBound, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(OptionLimit))]
+pub struct TopicOutcome<OptionIndex: PartialEq, VoteWeight: PartialEq, OptionLimit: Get<u32>> {
+	/// Options sharing the highest vote weight, in ascending order. Empty if nobody voted.
+	pub winning_options: BoundedVec<OptionIndex, OptionLimit>,
+	/// Vote weight received by each of the winning options.
+	pub winning_vote_weight: VoteWeight,
+	/// Number of voters who cast a ballot.
+	pub turnout: u32,
+}
+
+/// Origin of the calls enacted by the DAO pallet.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(I))]
+#[codec(mel_bound(TopicId: MaxEncodedLen))]
+pub enum RawOrigin<TopicId, I> {
+	/// The call attached to the winning option of the given topic.
+	Topic(TopicId),
+	/// The call attached to the winning option of the given topic, raised through
+	/// `ForceOrigin`.
+	ForcedTopic(TopicId),
+	/// Dummy to manage the fact we have instancing.
+	_Phantom(PhantomData<I>),
 }
 
 #[derive(
 	Clone, Encode, Decode, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug, TypeInfo, MaxEncodedLen,
 )]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct TopicVotingResult<OptionIndex, VoteWeight> {
 	pub index: OptionIndex,
 	pub vote_weight: VoteWeight,
 }
+
+/// A topic, as exposed by the runtime API.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TopicInfo<TopicId, AccountId> {
+	pub id: TopicId,
+	pub raiser: AccountId,
+	#[cfg_attr(feature = "std", serde(with = "text"))]
+	pub title: Vec<u8>,
+	#[cfg_attr(feature = "std", serde(with = "text"))]
+	pub description: Vec<u8>,
+	pub voting_period_start: u64,
+	pub voting_period_end: u64,
+	#[cfg_attr(feature = "std", serde(with = "texts"))]
+	pub options: Vec<Vec<u8>>,
+	pub required_answer_number: u32,
+	pub status: TopicStatus,
+	pub tally_method: TallyMethod,
+	pub voting_mode: VotingMode,
+}
+
+/// The ballots and outcome of a topic, as exposed by the runtime API.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TopicResults<OptionIndex, VoteWeight> {
+	/// Vote weight received by every option that received any.
+	pub votes: Vec<TopicVotingResult<OptionIndex, VoteWeight>>,
+	/// Number of voters who cast a ballot.
+	pub turnout: u32,
+	/// The winning options, once the topic is closed.
+	pub winning_options: Option<Vec<OptionIndex>>,
+}
+
+/// Where an account stands on a topic, as exposed by the runtime API.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum VoterStatus<VoteWeight> {
+	/// The account holds no voting right token for the topic.
+	NoVotingRight,
+	/// The account holds an unspent voting right token of the given weight.
+	NotVoted { vote_weight: VoteWeight },
+	/// The account committed to a sealed ballot it has not revealed yet.
+	Committed { vote_weight: VoteWeight },
+	/// The account voted.
+	Voted,
+}
+
+/// Serializes UTF-8 text stored as bytes as a string.
+#[cfg(feature = "std")]
+mod text {
+	use serde::{Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&String::from_utf8_lossy(bytes))
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+		String::deserialize(deserializer).map(String::into_bytes)
+	}
+}
+
+/// Serializes a list of UTF-8 texts stored as bytes as a list of strings.
+#[cfg(feature = "std")]
+mod texts {
+	use serde::{ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+
+	pub fn serialize<S: Serializer>(texts: &[Vec<u8>], serializer: S) -> Result<S::Ok, S::Error> {
+		let mut seq = serializer.serialize_seq(Some(texts.len()))?;
+		for bytes in texts {
+			seq.serialize_element(&String::from_utf8_lossy(bytes))?;
+		}
+		seq.end()
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<Vec<Vec<u8>>, D::Error> {
+		Vec::<String>::deserialize(deserializer)
+			.map(|texts| texts.into_iter().map(String::into_bytes).collect())
+	}
+}
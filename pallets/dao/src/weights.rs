@@ -0,0 +1,747 @@
+//! Weights for pallet_dao
+//!
+//! THESE ARE NOT BENCHMARK RESULTS. The values below are hand-written estimates of the benchmarks
+//! in `benchmarking.rs`: the storage accesses are exact, the execution times are upper bounds.
+//! The runtimes use a copy of them in `runtime/<runtime>/src/weights/pallet_dao.rs`, which
+//! `scripts/benchmark-dao.sh` overwrites with weights generated on reference hardware.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_dao.
+pub trait WeightInfo {
+	fn raise_topic(t: u32, d: u32, o: u32, ) -> Weight;
+	fn force_raise_topic(t: u32, d: u32, o: u32, ) -> Weight;
+	fn issue_voting_right_token(v: u32, n: u32, ) -> Weight;
+	fn vote_topic(o: u32, d: u32, ) -> Weight;
+	fn close_topic(o: u32, ) -> Weight;
+	fn close_ranked_choice_topic(o: u32, v: u32, ) -> Weight;
+	fn finalize_topic(o: u32, ) -> Weight;
+	fn set_option_call() -> Weight;
+	fn slash_topic(o: u32, ) -> Weight;
+	fn vote_topic_with_lock(o: u32, ) -> Weight;
+	fn set_voting_mode() -> Weight;
+	fn unlock_vote(l: u32, ) -> Weight;
+	fn revoke_voting_right_token(o: u32, ) -> Weight;
+	fn add_topic_issuer() -> Weight;
+	fn remove_topic_issuer() -> Weight;
+	fn set_tally_method() -> Weight;
+	fn on_initialize(a: u32, ) -> Weight;
+	fn commit_vote() -> Weight;
+	fn reveal_vote(o: u32, ) -> Weight;
+	fn delegate() -> Weight;
+	fn undelegate() -> Weight;
+	fn clear_topic(n: u32, o: u32, ) -> Weight;
+	fn edit_topic(t: u32, d: u32, o: u32, ) -> Weight;
+	fn cancel_topic(o: u32, ) -> Weight;
+	fn set_weight_source() -> Weight;
+}
+
+/// Weights for pallet_dao using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dao TopicCount (r:1 w:1)
+	/// Storage: Dao TopicDepositOf (r:0 w:1)
+	/// Storage: Dao TopicCollection (r:0 w:1)
+	/// Storage: Dao ActiveTopicsOf (r:1 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// The range of component `t` is `[1, 256]`.
+	/// The range of component `d` is `[1, 2048]`.
+	/// The range of component `o` is `[2, 1024]`.
+	fn raise_topic(t: u32, d: u32, o: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 3_593)
+			.saturating_add(Weight::from_parts(1_300, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(1_300, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(2_100_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dao TopicCount (r:1 w:1)
+	/// Storage: Dao TopicCollection (r:0 w:1)
+	/// Storage: Dao ForcedTopics (r:0 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// The range of component `t` is `[1, 256]`.
+	/// The range of component `d` is `[1, 2048]`.
+	/// The range of component `o` is `[2, 1024]`.
+	fn force_raise_topic(t: u32, d: u32, o: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 1_489)
+			.saturating_add(Weight::from_parts(1_300, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(1_300, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(2_100_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicIssuers (r:1 w:0)
+	/// Storage: Dao TopicWeightSource (r:1 w:0)
+	/// Storage: Dao VotingRightTokenCollection (r:1024 w:1024)
+	/// Storage: System Account (r:1024 w:0)
+	/// Storage: Nfts Account (r:16384 w:0)
+	/// The range of component `v` is `[1, 1024]`.
+	/// The range of component `n` is `[0, 16]`.
+	fn issue_voting_right_token(v: u32, n: u32, ) -> Weight {
+		Weight::from_parts(26_000_000, 5_500)
+			.saturating_add(Weight::from_parts(18_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(v.into()).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(v.into()).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(v.into())))
+			.saturating_add(Weight::from_parts(0, 5_206).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(v.into()).saturating_mul(n.into()))
+	}
+	/// Storage: Dao TopicVotingMode (r:1 w:0)
+	/// Storage: Dao TopicCollection (r:2 w:1)
+	/// Storage: Dao VotingRightTokenCollection (r:1025 w:1)
+	/// Storage: Dao DelegatedVotes (r:1025 w:1025)
+	/// Storage: Dao BallotBox (r:1023 w:1023)
+	/// Storage: Dao TopicTurnout (r:1 w:1)
+	/// Storage: Dao RankedBallots (r:0 w:1)
+	/// Storage: Dao Delegators (r:1 w:0)
+	/// Storage: Dao Delegations (r:1024 w:0)
+	/// Storage: Dao DelegateBallots (r:0 w:1)
+	/// The range of component `o` is `[1, 1023]`.
+	/// The range of component `d` is `[0, 1024]`.
+	fn vote_topic(o: u32, d: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 6_000)
+			.saturating_add(Weight::from_parts(4_500_000, 0).saturating_mul(o.into()))
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(o.into()))
+			.saturating_add(Weight::from_parts(0, 7_700).saturating_mul(d.into()))
+	}
+	/// Storage: Dao TopicVotingMode (r:1 w:0)
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao BallotBox (r:1024 w:0)
+	/// Storage: Dao TopicTurnout (r:1 w:0)
+	/// Storage: Dao TopicOutcomes (r:0 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// The range of component `o` is `[2, 1024]`.
+	fn close_topic(o: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(Weight::from_parts(3_800_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicVotingMode (r:1 w:0)
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao RankedBallots (r:1024 w:0)
+	/// Storage: Dao TopicTurnout (r:1 w:0)
+	/// Storage: Dao TopicOutcomes (r:0 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// The range of component `o` is `[2, 32]`.
+	/// The range of component `v` is `[0, 1024]`.
+	fn close_ranked_choice_topic(o: u32, v: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(v.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 4_200).saturating_mul(v.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao TopicDepositOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dao TopicOutcomes (r:1 w:0)
+	/// Storage: Dao TopicOptionCalls (r:1024 w:1024)
+	/// Storage: Preimage StatusFor (r:1024 w:1024)
+	/// Storage: Scheduler Agenda (r:1 w:1)
+	/// The range of component `o` is `[2, 1024]`.
+	fn finalize_topic(o: u32, ) -> Weight {
+		Weight::from_parts(68_000_000, 8_000)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_700).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicOptionCalls (r:1 w:1)
+	/// Storage: Preimage StatusFor (r:1 w:1)
+	/// Storage: Preimage PreimageFor (r:0 w:1)
+	fn set_option_call() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao TopicOptionCalls (r:1024 w:1024)
+	/// Storage: Preimage StatusFor (r:1024 w:1024)
+	/// Storage: Dao TopicDepositOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// The range of component `o` is `[2, 1024]`.
+	fn slash_topic(o: u32, ) -> Weight {
+		Weight::from_parts(50_000_000, 6_000)
+			.saturating_add(Weight::from_parts(5_500_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_700).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicVotingMode (r:1 w:0)
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao VotingRightTokenCollection (r:1 w:1)
+	/// Storage: Dao BallotBox (r:1023 w:1023)
+	/// Storage: Dao TopicTurnout (r:1 w:1)
+	/// Storage: Dao RankedBallots (r:0 w:1)
+	/// Storage: Dao VotingLocks (r:1 w:1)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dao DelegatedVotes (r:1 w:1)
+	/// The range of component `o` is `[1, 1023]`.
+	fn vote_topic_with_lock(o: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 8_000)
+			.saturating_add(Weight::from_parts(4_500_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicVotingMode (r:0 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	fn set_voting_mode() -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao VotingLocks (r:1 w:1)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `l` is `[1, 64]`.
+	fn unlock_vote(l: u32, ) -> Weight {
+		Weight::from_parts(35_000_000, 6_000)
+			.saturating_add(Weight::from_parts(150_000, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 60).saturating_mul(l.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicIssuers (r:1 w:0)
+	/// Storage: Dao VotingRightTokenCollection (r:1 w:1)
+	/// Storage: Dao DelegatedVotes (r:1 w:1)
+	/// Storage: Dao DelegateBallots (r:1 w:0)
+	/// Storage: Dao BallotBox (r:1023 w:1023)
+	/// The range of component `o` is `[2, 1024]`.
+	fn revoke_voting_right_token(o: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 5_500)
+			.saturating_add(Weight::from_parts(3_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicIssuers (r:1 w:1)
+	fn add_topic_issuer() -> Weight {
+		Weight::from_parts(25_000_000, 5_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicIssuers (r:1 w:1)
+	fn remove_topic_issuer() -> Weight {
+		Weight::from_parts(25_000_000, 5_500)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	fn set_tally_method() -> Weight {
+		Weight::from_parts(22_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// Storage: Dao TopicCollection (r:64 w:64)
+	/// The range of component `a` is `[0, 64]`.
+	fn on_initialize(a: u32, ) -> Weight {
+		Weight::from_parts(4_000_000, 1_500)
+			.saturating_add(Weight::from_parts(7_000_000, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(a.into())))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(a.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicVotingMode (r:1 w:0)
+	/// Storage: Dao VotingRightTokenCollection (r:1 w:0)
+	/// Storage: Dao VoteCommitments (r:0 w:1)
+	fn commit_vote() -> Weight {
+		Weight::from_parts(28_000_000, 5_500)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dao TopicVotingMode (r:2 w:0)
+	/// Storage: Dao VoteCommitments (r:1 w:1)
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao VotingRightTokenCollection (r:1 w:1)
+	/// Storage: Dao BallotBox (r:1023 w:1023)
+	/// Storage: Dao TopicTurnout (r:1 w:1)
+	/// Storage: Dao RankedBallots (r:0 w:1)
+	/// Storage: Dao DelegatedVotes (r:1 w:1)
+	/// The range of component `o` is `[1, 1023]`.
+	fn reveal_vote(o: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 4_000)
+			.saturating_add(Weight::from_parts(4_500_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao Delegations (r:1 w:1)
+	/// Storage: Dao Delegators (r:2 w:2)
+	fn delegate() -> Weight {
+		Weight::from_parts(40_000_000, 80_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Dao Delegations (r:1 w:1)
+	/// Storage: Dao Delegators (r:1 w:1)
+	fn undelegate() -> Weight {
+		Weight::from_parts(30_000_000, 40_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao TopicClearCursors (r:1 w:1)
+	/// Storage: Dao VotingRightTokenCollection (r:0 w:1000)
+	/// Storage: Dao TopicOptionCalls (r:1024 w:1024)
+	/// Storage: Preimage StatusFor (r:1024 w:1024)
+	/// Storage: Dao TopicDepositOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dao TopicTurnout (r:0 w:1)
+	/// Storage: Dao TopicVotingMode (r:0 w:1)
+	/// Storage: Dao TopicIssuers (r:0 w:1)
+	/// The range of component `n` is `[0, 1000]`.
+	/// The range of component `o` is `[0, 1024]`.
+	fn clear_topic(n: u32, o: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 12_000)
+			.saturating_add(Weight::from_parts(2_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_700).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao TopicDepositOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dao TopicOptionCalls (r:1025 w:1024)
+	/// Storage: Preimage StatusFor (r:1024 w:1024)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// The range of component `t` is `[1, 256]`.
+	/// The range of component `d` is `[1, 2048]`.
+	/// The range of component `o` is `[2, 1024]`.
+	fn edit_topic(t: u32, d: u32, o: u32, ) -> Weight {
+		Weight::from_parts(62_000_000, 8_000)
+			.saturating_add(Weight::from_parts(1_300, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(1_300, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(8_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_700).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// Storage: Dao TopicOptionCalls (r:1024 w:1024)
+	/// Storage: Preimage StatusFor (r:1024 w:1024)
+	/// Storage: Dao TopicDepositOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `o` is `[2, 1024]`.
+	fn cancel_topic(o: u32, ) -> Weight {
+		Weight::from_parts(52_000_000, 8_000)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(o.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_700).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao VotingRightTokenCollection (r:1 w:0)
+	/// Storage: Assets Asset (r:1 w:0)
+	/// Storage: Dao TopicWeightSource (r:0 w:1)
+	fn set_weight_source() -> Weight {
+		Weight::from_parts(22_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dao TopicCount (r:1 w:1)
+	/// Storage: Dao TopicDepositOf (r:0 w:1)
+	/// Storage: Dao TopicCollection (r:0 w:1)
+	/// Storage: Dao ActiveTopicsOf (r:1 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// The range of component `t` is `[1, 256]`.
+	/// The range of component `d` is `[1, 2048]`.
+	/// The range of component `o` is `[2, 1024]`.
+	fn raise_topic(t: u32, d: u32, o: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 3_593)
+			.saturating_add(Weight::from_parts(1_300, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(1_300, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(2_100_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: Dao TopicCount (r:1 w:1)
+	/// Storage: Dao TopicCollection (r:0 w:1)
+	/// Storage: Dao ForcedTopics (r:0 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// The range of component `t` is `[1, 256]`.
+	/// The range of component `d` is `[1, 2048]`.
+	/// The range of component `o` is `[2, 1024]`.
+	fn force_raise_topic(t: u32, d: u32, o: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 1_489)
+			.saturating_add(Weight::from_parts(1_300, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(1_300, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(2_100_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicIssuers (r:1 w:0)
+	/// Storage: Dao TopicWeightSource (r:1 w:0)
+	/// Storage: Dao VotingRightTokenCollection (r:1024 w:1024)
+	/// Storage: System Account (r:1024 w:0)
+	/// Storage: Nfts Account (r:16384 w:0)
+	/// The range of component `v` is `[1, 1024]`.
+	/// The range of component `n` is `[0, 16]`.
+	fn issue_voting_right_token(v: u32, n: u32, ) -> Weight {
+		Weight::from_parts(26_000_000, 5_500)
+			.saturating_add(Weight::from_parts(18_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(4_000_000, 0).saturating_mul(v.into()).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(v.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(v.into()).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(v.into())))
+			.saturating_add(Weight::from_parts(0, 5_206).saturating_mul(v.into()))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(v.into()).saturating_mul(n.into()))
+	}
+	/// Storage: Dao TopicVotingMode (r:1 w:0)
+	/// Storage: Dao TopicCollection (r:2 w:1)
+	/// Storage: Dao VotingRightTokenCollection (r:1025 w:1)
+	/// Storage: Dao DelegatedVotes (r:1025 w:1025)
+	/// Storage: Dao BallotBox (r:1023 w:1023)
+	/// Storage: Dao TopicTurnout (r:1 w:1)
+	/// Storage: Dao RankedBallots (r:0 w:1)
+	/// Storage: Dao Delegators (r:1 w:0)
+	/// Storage: Dao Delegations (r:1024 w:0)
+	/// Storage: Dao DelegateBallots (r:0 w:1)
+	/// The range of component `o` is `[1, 1023]`.
+	/// The range of component `d` is `[0, 1024]`.
+	fn vote_topic(o: u32, d: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 6_000)
+			.saturating_add(Weight::from_parts(4_500_000, 0).saturating_mul(o.into()))
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(o.into()))
+			.saturating_add(Weight::from_parts(0, 7_700).saturating_mul(d.into()))
+	}
+	/// Storage: Dao TopicVotingMode (r:1 w:0)
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao BallotBox (r:1024 w:0)
+	/// Storage: Dao TopicTurnout (r:1 w:0)
+	/// Storage: Dao TopicOutcomes (r:0 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// The range of component `o` is `[2, 1024]`.
+	fn close_topic(o: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(Weight::from_parts(3_800_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicVotingMode (r:1 w:0)
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao RankedBallots (r:1024 w:0)
+	/// Storage: Dao TopicTurnout (r:1 w:0)
+	/// Storage: Dao TopicOutcomes (r:0 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// The range of component `o` is `[2, 32]`.
+	/// The range of component `v` is `[0, 1024]`.
+	fn close_ranked_choice_topic(o: u32, v: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(v.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 4_200).saturating_mul(v.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao TopicDepositOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dao TopicOutcomes (r:1 w:0)
+	/// Storage: Dao TopicOptionCalls (r:1024 w:1024)
+	/// Storage: Preimage StatusFor (r:1024 w:1024)
+	/// Storage: Scheduler Agenda (r:1 w:1)
+	/// The range of component `o` is `[2, 1024]`.
+	fn finalize_topic(o: u32, ) -> Weight {
+		Weight::from_parts(68_000_000, 8_000)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_700).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicOptionCalls (r:1 w:1)
+	/// Storage: Preimage StatusFor (r:1 w:1)
+	/// Storage: Preimage PreimageFor (r:0 w:1)
+	fn set_option_call() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao TopicOptionCalls (r:1024 w:1024)
+	/// Storage: Preimage StatusFor (r:1024 w:1024)
+	/// Storage: Dao TopicDepositOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// The range of component `o` is `[2, 1024]`.
+	fn slash_topic(o: u32, ) -> Weight {
+		Weight::from_parts(50_000_000, 6_000)
+			.saturating_add(Weight::from_parts(5_500_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_700).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicVotingMode (r:1 w:0)
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao VotingRightTokenCollection (r:1 w:1)
+	/// Storage: Dao BallotBox (r:1023 w:1023)
+	/// Storage: Dao TopicTurnout (r:1 w:1)
+	/// Storage: Dao RankedBallots (r:0 w:1)
+	/// Storage: Dao VotingLocks (r:1 w:1)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dao DelegatedVotes (r:1 w:1)
+	/// The range of component `o` is `[1, 1023]`.
+	fn vote_topic_with_lock(o: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 8_000)
+			.saturating_add(Weight::from_parts(4_500_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicVotingMode (r:0 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	fn set_voting_mode() -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao VotingLocks (r:1 w:1)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `l` is `[1, 64]`.
+	fn unlock_vote(l: u32, ) -> Weight {
+		Weight::from_parts(35_000_000, 6_000)
+			.saturating_add(Weight::from_parts(150_000, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 60).saturating_mul(l.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicIssuers (r:1 w:0)
+	/// Storage: Dao VotingRightTokenCollection (r:1 w:1)
+	/// Storage: Dao DelegatedVotes (r:1 w:1)
+	/// Storage: Dao DelegateBallots (r:1 w:0)
+	/// Storage: Dao BallotBox (r:1023 w:1023)
+	/// The range of component `o` is `[2, 1024]`.
+	fn revoke_voting_right_token(o: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 5_500)
+			.saturating_add(Weight::from_parts(3_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicIssuers (r:1 w:1)
+	fn add_topic_issuer() -> Weight {
+		Weight::from_parts(25_000_000, 5_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicIssuers (r:1 w:1)
+	fn remove_topic_issuer() -> Weight {
+		Weight::from_parts(25_000_000, 5_500)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	fn set_tally_method() -> Weight {
+		Weight::from_parts(22_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// Storage: Dao TopicCollection (r:64 w:64)
+	/// The range of component `a` is `[0, 64]`.
+	fn on_initialize(a: u32, ) -> Weight {
+		Weight::from_parts(4_000_000, 1_500)
+			.saturating_add(Weight::from_parts(7_000_000, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(a.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(a.into())))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(a.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao TopicVotingMode (r:1 w:0)
+	/// Storage: Dao VotingRightTokenCollection (r:1 w:0)
+	/// Storage: Dao VoteCommitments (r:0 w:1)
+	fn commit_vote() -> Weight {
+		Weight::from_parts(28_000_000, 5_500)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Dao TopicVotingMode (r:2 w:0)
+	/// Storage: Dao VoteCommitments (r:1 w:1)
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao VotingRightTokenCollection (r:1 w:1)
+	/// Storage: Dao BallotBox (r:1023 w:1023)
+	/// Storage: Dao TopicTurnout (r:1 w:1)
+	/// Storage: Dao RankedBallots (r:0 w:1)
+	/// Storage: Dao DelegatedVotes (r:1 w:1)
+	/// The range of component `o` is `[1, 1023]`.
+	fn reveal_vote(o: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 4_000)
+			.saturating_add(Weight::from_parts(4_500_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao Delegations (r:1 w:1)
+	/// Storage: Dao Delegators (r:2 w:2)
+	fn delegate() -> Weight {
+		Weight::from_parts(40_000_000, 80_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Dao Delegations (r:1 w:1)
+	/// Storage: Dao Delegators (r:1 w:1)
+	fn undelegate() -> Weight {
+		Weight::from_parts(30_000_000, 40_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao TopicClearCursors (r:1 w:1)
+	/// Storage: Dao VotingRightTokenCollection (r:0 w:1000)
+	/// Storage: Dao TopicOptionCalls (r:1024 w:1024)
+	/// Storage: Preimage StatusFor (r:1024 w:1024)
+	/// Storage: Dao TopicDepositOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dao TopicTurnout (r:0 w:1)
+	/// Storage: Dao TopicVotingMode (r:0 w:1)
+	/// Storage: Dao TopicIssuers (r:0 w:1)
+	/// The range of component `n` is `[0, 1000]`.
+	/// The range of component `o` is `[0, 1024]`.
+	fn clear_topic(n: u32, o: u32, ) -> Weight {
+		Weight::from_parts(45_000_000, 12_000)
+			.saturating_add(Weight::from_parts(2_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_700).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao TopicDepositOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Storage: Dao TopicOptionCalls (r:1025 w:1024)
+	/// Storage: Preimage StatusFor (r:1024 w:1024)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// The range of component `t` is `[1, 256]`.
+	/// The range of component `d` is `[1, 2048]`.
+	/// The range of component `o` is `[2, 1024]`.
+	fn edit_topic(t: u32, d: u32, o: u32, ) -> Weight {
+		Weight::from_parts(62_000_000, 8_000)
+			.saturating_add(Weight::from_parts(1_300, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(1_300, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(8_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_700).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:1)
+	/// Storage: Dao ActiveTopics (r:1 w:1)
+	/// Storage: Dao TopicOptionCalls (r:1024 w:1024)
+	/// Storage: Preimage StatusFor (r:1024 w:1024)
+	/// Storage: Dao TopicDepositOf (r:1 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// The range of component `o` is `[2, 1024]`.
+	fn cancel_topic(o: u32, ) -> Weight {
+		Weight::from_parts(52_000_000, 8_000)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(o.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(o.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(o.into())))
+			.saturating_add(Weight::from_parts(0, 2_700).saturating_mul(o.into()))
+	}
+	/// Storage: Dao TopicCollection (r:1 w:0)
+	/// Storage: Dao VotingRightTokenCollection (r:1 w:0)
+	/// Storage: Assets Asset (r:1 w:0)
+	/// Storage: Dao TopicWeightSource (r:0 w:1)
+	fn set_weight_source() -> Weight {
+		Weight::from_parts(22_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -5,30 +5,182 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
 pub use pallet::*;
 
+/// Domain tag prefixed to the encoding of a [`FinalityAttestation`] before signing, so that the
+/// signature cannot pass for one over another message of the GRANDPA key, such as a vote.
+pub const ATTESTATION_SIGNING_CONTEXT: &[u8] = b"thx-finality-attest";
+
+/// A validator's view of GRANDPA finality.
+///
+/// Validators sign its [`signing_payload`](Self::signing_payload) with their GRANDPA session key
+/// and submit it through `attest_finality`.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct FinalityAttestation<BlockNumber> {
+	/// GRANDPA set id the validator is voting in.
+	pub set_id: u64,
+	/// Index of the validator in the current GRANDPA authority list.
+	pub authority_index: u32,
+	/// Number of the last block the validator has finalized.
+	pub finalized_number: BlockNumber,
+}
+
+impl<BlockNumber: Encode> FinalityAttestation<BlockNumber> {
+	/// The bytes signed by the attesting authority: [`ATTESTATION_SIGNING_CONTEXT`] followed by
+	/// the SCALE encoding of the attestation.
+	pub fn signing_payload(&self) -> Vec<u8> {
+		let mut payload = ATTESTATION_SIGNING_CONTEXT.to_vec();
+		self.encode_to(&mut payload);
+		payload
+	}
+}
+
+/// Finality health as seen on-chain from authority attestations.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum StallState<BlockNumber> {
+	/// Finality lag is below the warning threshold, or nothing was attested yet.
+	Healthy,
+	/// Finality lag crossed `LagWarningThreshold` at block `since`.
+	Lagging { since: BlockNumber, last_finalized: BlockNumber },
+	/// Finality lag crossed `StallThreshold` at block `since`.
+	Stalled { since: BlockNumber, last_finalized: BlockNumber },
+}
+
+impl<BlockNumber> Default for StallState<BlockNumber> {
+	fn default() -> Self {
+		Self::Healthy
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{pallet_prelude::*, storage};
+	use frame_support::{
+		pallet_prelude::*,
+		storage,
+		traits::{
+			schedule::{self, v3::Named as ScheduleNamed, DispatchTime},
+			StorePreimage,
+		},
+		Hashable, StorageHasher,
+	};
 	use frame_system::pallet_prelude::*;
-	use sp_runtime::traits::Zero;
+	use pallet_grandpa::{
+		fg_primitives::{AuthoritySignature, KEY_TYPE as GRANDPA_KEY_TYPE},
+		AuthorityList,
+	};
+	use sp_runtime::{
+		traits::{Saturating, Zero},
+		RuntimeAppPublic,
+	};
+	use sp_std::{boxed::Box, vec::Vec};
+
+	use super::{FinalityAttestation, StallState};
+
+	/// Number of blocks an attestation stays valid in the transaction pool.
+	const ATTESTATION_LONGEVITY: u64 = 64;
+
+	/// Largest step `reconcile_set_id` may move `CurrentSetId` backwards.
+	pub const MAX_SET_ID_ROLLBACK: u64 = 16;
+
+	/// Scheduler priority of a delayed rescue call.
+	const RESCUE_PRIORITY: schedule::Priority = schedule::HARD_DEADLINE;
+
+	pub type CallOf<T> = <T as frame_system::Config>::RuntimeCall;
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config + pallet_grandpa::Config {
+	pub trait Config:
+		frame_system::Config + pallet_grandpa::Config + pallet_session::Config
+	{
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+		/// The overarching call type, used to schedule delayed rescues.
+		type RuntimeCall: From<Call<Self>> + IsType<<Self as frame_system::Config>::RuntimeCall>;
+
+		/// Origin allowed to run the rescue calls directly.
+		///
+		/// Delayed rescues are dispatched by the scheduler as root, so this origin must accept
+		/// root for `schedule_rescue` to be useful.
+		type RescueOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Origin allowed to schedule a rescue call to run after `RescueDelay`.
+		///
+		/// Set to `NeverEnsureOrigin` to disable delayed rescues.
+		type DelayOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Number of blocks between scheduling a delayed rescue and running it, during which
+		/// `RescueOrigin` can cancel it.
+		#[pallet::constant]
+		type RescueDelay: Get<Self::BlockNumber>;
+
+		/// The caller origin, overarching type of all pallets origins.
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+		/// The scheduler used to run delayed rescues.
+		type Scheduler: ScheduleNamed<Self::BlockNumber, CallOf<Self>, Self::PalletsOrigin>;
+
+		/// The preimage provider used to store delayed rescue calls.
+		type Preimages: StorePreimage;
+
 		/// Minimum number of blocks between rescue calls.
 		#[pallet::constant]
 		type RescueCooldown: Get<Self::BlockNumber>;
+
+		/// Finality lag, in blocks, past which `FinalityLagWarning` is emitted.
+		#[pallet::constant]
+		type LagWarningThreshold: Get<Self::BlockNumber>;
+
+		/// Finality lag, in blocks, past which finality is considered stalled.
+		#[pallet::constant]
+		type StallThreshold: Get<Self::BlockNumber>;
+
+		/// Whether the rescue calls require finality to be stalled first.
+		#[pallet::constant]
+		type RequireStallForRescue: Get<bool>;
+
+		/// Priority of the unsigned finality attestations in the transaction pool.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn last_rescue_block)]
 	pub type LastRescueBlock<T: Config> = StorageValue<_, T::BlockNumber>;
 
+	/// Last finalized block number attested by each authority, keyed by set id and authority
+	/// index.
+	#[pallet::storage]
+	#[pallet::getter(fn attestations)]
+	pub type Attestations<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u64, Twox64Concat, u32, T::BlockNumber>;
+
+	/// Set id whose attestations are kept in `Attestations`. Once GRANDPA moves to another set,
+	/// they are removed at the start of the next block.
+	#[pallet::storage]
+	pub type AttestedSetId<T: Config> = StorageValue<_, u64>;
+
+	/// Highest finalized block number attested by any authority.
+	#[pallet::storage]
+	#[pallet::getter(fn observed_finalized)]
+	pub type ObservedFinalized<T: Config> = StorageValue<_, T::BlockNumber>;
+
+	/// Finality health, updated at the start of every block.
+	#[pallet::storage]
+	#[pallet::getter(fn stall_state)]
+	pub type StallStatus<T: Config> = StorageValue<_, StallState<T::BlockNumber>, ValueQuery>;
+
+	/// Index of the next delayed rescue, used to derive its scheduler task name.
+	#[pallet::storage]
+	#[pallet::getter(fn next_rescue_index)]
+	pub type NextRescueIndex<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -40,6 +192,30 @@ pub mod pallet {
 			old_set_id: u64,
 			new_set_id: u64,
 		},
+		/// An authority attested its last finalized block.
+		FinalityAttested { set_id: u64, authority_index: u32, finalized_number: T::BlockNumber },
+		/// A rescue median was computed from authority attestations.
+		AttestedMedianComputed { set_id: u64, attestation_count: u32, median: T::BlockNumber },
+		/// Finality lags behind block production by more than `LagWarningThreshold`.
+		FinalityLagWarning { lag: T::BlockNumber, last_finalized: T::BlockNumber },
+		/// Finality lags behind block production by more than `StallThreshold`.
+		FinalityStalled { lag: T::BlockNumber, last_finalized: T::BlockNumber },
+		/// Finality lag dropped back below `LagWarningThreshold`.
+		FinalityRecovered { last_finalized: T::BlockNumber },
+		/// A forced change to an explicitly given authority set was scheduled.
+		AuthoritySetForced {
+			block_number: T::BlockNumber,
+			median: T::BlockNumber,
+			authorities: AuthorityList,
+			old_set_id: u64,
+			new_set_id: u64,
+		},
+		/// `CurrentSetId` was realigned with the set id used by the GRANDPA client.
+		SetIdReconciled { old_set_id: u64, new_set_id: u64, session_index: u32 },
+		/// A rescue call was scheduled to run at block `when`.
+		RescueScheduled { index: u32, when: T::BlockNumber },
+		/// A scheduled rescue call was cancelled.
+		RescueCancelled { index: u32 },
 	}
 
 	#[pallet::error]
@@ -50,6 +226,48 @@ pub mod pallet {
 		NoAuthorities,
 		/// Failed to schedule authority change.
 		ScheduleChangeFailed,
+		/// The attestation is for a different GRANDPA set id.
+		WrongSetId,
+		/// No GRANDPA authority exists at the attested index.
+		InvalidAuthorityIndex,
+		/// The attestation signature does not match the authority key.
+		InvalidSignature,
+		/// The attested block has not been produced yet.
+		AttestationFromFuture,
+		/// The authority already attested the same or a later block.
+		StaleAttestation,
+		/// Fewer than 2/3 of the authorities attested in the current set.
+		InsufficientAttestations,
+		/// Rescue requires finality to be stalled, and it is not.
+		FinalityNotStalled,
+		/// The new authority set has more than `MaxAuthorities` members.
+		TooManyAuthorities,
+		/// The new authority set lists the same key twice.
+		DuplicateAuthority,
+		/// An authority in the new set has a zero weight.
+		ZeroAuthorityWeight,
+		/// An authority key is not registered as anyone's GRANDPA session key.
+		UnknownSessionKey,
+		/// An authority key belongs to an account outside the current validator set.
+		NotAValidator,
+		/// `CurrentSetId` already equals the expected set id.
+		SetIdAlreadyAligned,
+		/// The expected set id is more than `MAX_SET_ID_ROLLBACK` behind `CurrentSetId`.
+		SetIdRollbackTooLarge,
+		/// Only the rescue calls of this pallet can be scheduled.
+		NotARescueCall,
+		/// No delayed rescue with this index is pending.
+		UnknownRescue,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::track_finality_lag(now);
+			T::DbWeight::get()
+				.reads_writes(2, 1)
+				.saturating_add(Self::clear_previous_attestations())
+		}
 	}
 
 	#[pallet::call]
@@ -63,17 +281,431 @@ pub mod pallet {
 		/// `median` must be the last finalized block number, obtained from
 		/// `chain_getFinalizedHead` RPC.
 		///
-		/// Can only be called by root (via sudo).
+		/// If `RequireStallForRescue` is set, finality must have been detected as stalled.
+		///
+		/// The dispatch origin must be `RescueOrigin`.
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::DbWeight::get().reads_writes(5, 6))]
+		#[pallet::weight(T::DbWeight::get().reads_writes(
+			6 + u64::from(T::MaxAuthorities::get()),
+			6 + u64::from(T::MaxAuthorities::get()),
+		))]
 		pub fn rescue_finality(
 			origin: OriginFor<T>,
 			median: T::BlockNumber,
 		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
+			T::RescueOrigin::ensure_origin(origin)?;
+
+			Self::do_rescue(median)?;
+
+			// Emergency operation - no fee
+			Ok(Pays::No.into())
+		}
+
+		/// Record a GRANDPA authority's last finalized block.
+		///
+		/// Submitted as an unsigned transaction carrying an attestation signed with the
+		/// authority's GRANDPA session key. Only a strictly newer block replaces an earlier
+		/// attestation of the same authority.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(4, 2))]
+		pub fn attest_finality(
+			origin: OriginFor<T>,
+			attestation: FinalityAttestation<T::BlockNumber>,
+			signature: AuthoritySignature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			Self::check_attestation(&attestation, &signature)?;
+
+			let FinalityAttestation { set_id, authority_index, finalized_number } = attestation;
+			Attestations::<T>::insert(set_id, authority_index, finalized_number);
+			ObservedFinalized::<T>::mutate(|observed| {
+				if observed.map_or(true, |o| o < finalized_number) {
+					*observed = Some(finalized_number);
+				}
+			});
+			Self::deposit_event(Event::FinalityAttested {
+				set_id,
+				authority_index,
+				finalized_number,
+			});
+			Ok(())
+		}
+
+		/// Emergency rescue of GRANDPA finality using the attested median.
+		///
+		/// Same as `rescue_finality`, but `median` is the median finalized block attested by
+		/// the authorities of the current set. At least 2/3 of them must have attested.
+		///
+		/// The dispatch origin must be `RescueOrigin`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(
+			7 + u64::from(T::MaxAuthorities::get()),
+			6 + u64::from(T::MaxAuthorities::get()),
+		))]
+		pub fn rescue_finality_attested(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::RescueOrigin::ensure_origin(origin)?;
+
+			let set_id = pallet_grandpa::Pallet::<T>::current_set_id();
+			let authority_count = pallet_grandpa::Pallet::<T>::grandpa_authorities().len();
+			ensure!(authority_count > 0, Error::<T>::NoAuthorities);
+
+			let mut attested: Vec<T::BlockNumber> =
+				Attestations::<T>::iter_prefix_values(set_id).collect();
+			ensure!(
+				attested.len() * 3 >= authority_count * 2,
+				Error::<T>::InsufficientAttestations
+			);
+			// Take the lower median so that the forced change never lands past a block that
+			// a majority of the attesting authorities has finalized.
+			attested.sort();
+			let median = attested[(attested.len() - 1) / 2];
+			let attestation_count = attested.len() as u32;
+
+			Self::deposit_event(Event::AttestedMedianComputed {
+				set_id,
+				attestation_count,
+				median,
+			});
+			Self::do_rescue(median)?;
+
+			// Emergency operation - no fee
+			Ok(Pays::No.into())
+		}
+
+		/// Emergency rescue of GRANDPA finality with an explicit authority set.
+		///
+		/// Same as `rescue_finality`, but the forced change switches to `new_set` instead of
+		/// reusing the current authorities. Every key in `new_set` must be the registered
+		/// GRANDPA session key of a current validator.
+		///
+		/// The dispatch origin must be `RescueOrigin`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(
+			7 + 2 * new_set.len() as u64 + u64::from(T::MaxAuthorities::get()),
+			6 + u64::from(T::MaxAuthorities::get()),
+		))]
+		pub fn force_authorities(
+			origin: OriginFor<T>,
+			new_set: AuthorityList,
+			median: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::RescueOrigin::ensure_origin(origin)?;
 
+			Self::ensure_valid_authority_set(&new_set)?;
+			let (block_number, old_set_id, new_set_id) =
+				Self::force_change(median, new_set.clone())?;
+
+			Self::deposit_event(Event::AuthoritySetForced {
+				block_number,
+				median,
+				authorities: new_set,
+				old_set_id,
+				new_set_id,
+			});
+
+			// Emergency operation - no fee
+			Ok(Pays::No.into())
+		}
+
+		/// Realign `CurrentSetId` with the set id the GRANDPA client is running.
+		///
+		/// Sets `CurrentSetId` to `expected_client_set_id` and maps it to the current session
+		/// in `SetIdSession`, so that equivocation reports for the set keep working. When
+		/// moving backwards, the `SetIdSession` entries of the skipped set ids are removed.
+		/// GRANDPA state that is not tied to the set id is left alone.
+		///
+		/// The dispatch origin must be `RescueOrigin`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2 + MAX_SET_ID_ROLLBACK))]
+		pub fn reconcile_set_id(
+			origin: OriginFor<T>,
+			expected_client_set_id: u64,
+		) -> DispatchResultWithPostInfo {
+			T::RescueOrigin::ensure_origin(origin)?;
+
+			let current_set_id_key = storage::storage_prefix(b"Grandpa", b"CurrentSetId");
+			let old_set_id: u64 = storage::unhashed::get_or_default(&current_set_id_key);
+			ensure!(old_set_id != expected_client_set_id, Error::<T>::SetIdAlreadyAligned);
+			ensure!(
+				old_set_id.saturating_sub(expected_client_set_id) <= MAX_SET_ID_ROLLBACK,
+				Error::<T>::SetIdRollbackTooLarge
+			);
+
+			for set_id in expected_client_set_id.saturating_add(1)..=old_set_id {
+				storage::unhashed::kill(&Self::set_id_session_key(set_id));
+			}
+			let session_index = pallet_session::Pallet::<T>::current_index();
+			storage::unhashed::put(
+				&Self::set_id_session_key(expected_client_set_id),
+				&session_index,
+			);
+			storage::unhashed::put(&current_set_id_key, &expected_client_set_id);
+
+			log::info!(
+				target: "runtime::finality-rescue",
+				"CurrentSetId reconciled {} -> {} in session {}",
+				old_set_id,
+				expected_client_set_id,
+				session_index,
+			);
+
+			Self::deposit_event(Event::SetIdReconciled {
+				old_set_id,
+				new_set_id: expected_client_set_id,
+				session_index,
+			});
+
+			// Emergency operation - no fee
+			Ok(Pays::No.into())
+		}
+
+		/// Schedule a rescue call to run as root after `RescueDelay` blocks.
+		///
+		/// `call` must be `rescue_finality`, `rescue_finality_attested`, `force_authorities` or
+		/// `reconcile_set_id`. Until it runs, `RescueOrigin` can cancel it with
+		/// `cancel_rescue`.
+		///
+		/// The dispatch origin must be `DelayOrigin`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 3))]
+		pub fn schedule_rescue(origin: OriginFor<T>, call: Box<Call<T>>) -> DispatchResult {
+			T::DelayOrigin::ensure_origin(origin)?;
+			ensure!(
+				matches!(
+					*call,
+					Call::rescue_finality { .. } |
+						Call::rescue_finality_attested { .. } |
+						Call::force_authorities { .. } |
+						Call::reconcile_set_id { .. }
+				),
+				Error::<T>::NotARescueCall
+			);
+
+			let index = NextRescueIndex::<T>::get();
+			let when =
+				<frame_system::Pallet<T>>::block_number().saturating_add(T::RescueDelay::get());
+			let call: CallOf<T> = <T as Config>::RuntimeCall::from(*call).into();
+			let call = T::Preimages::bound(call)?;
+			T::Scheduler::schedule_named(
+				Self::rescue_task_name(index),
+				DispatchTime::At(when),
+				None,
+				RESCUE_PRIORITY,
+				frame_system::RawOrigin::Root.into(),
+				call,
+			)?;
+			NextRescueIndex::<T>::put(index.wrapping_add(1));
+
+			Self::deposit_event(Event::RescueScheduled { index, when });
+			Ok(())
+		}
+
+		/// Cancel the delayed rescue `index` before it runs.
+		///
+		/// The dispatch origin must be `RescueOrigin`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 2))]
+		pub fn cancel_rescue(origin: OriginFor<T>, index: u32) -> DispatchResult {
+			T::RescueOrigin::ensure_origin(origin)?;
+
+			T::Scheduler::cancel_named(Self::rescue_task_name(index))
+				.map_err(|_| Error::<T>::UnknownRescue)?;
+
+			Self::deposit_event(Event::RescueCancelled { index });
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (attestation, signature) = match call {
+				Call::attest_finality { attestation, signature } => (attestation, signature),
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			Self::check_attestation(attestation, signature).map_err(|e| match e {
+				Error::<T>::WrongSetId | Error::<T>::StaleAttestation => InvalidTransaction::Stale,
+				Error::<T>::AttestationFromFuture => InvalidTransaction::Future,
+				_ => InvalidTransaction::BadProof,
+			})?;
+
+			ValidTransaction::with_tag_prefix("FinalityRescue")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((attestation.set_id, attestation.authority_index))
+				.longevity(ATTESTATION_LONGEVITY)
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Check that `attestation` is signed by a current authority and improves on the
+		/// authority's previous attestation.
+		fn check_attestation(
+			attestation: &FinalityAttestation<T::BlockNumber>,
+			signature: &AuthoritySignature,
+		) -> Result<(), Error<T>> {
+			ensure!(
+				attestation.set_id == pallet_grandpa::Pallet::<T>::current_set_id(),
+				Error::<T>::WrongSetId
+			);
+			ensure!(
+				attestation.finalized_number < <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::AttestationFromFuture
+			);
+
+			let authorities = pallet_grandpa::Pallet::<T>::grandpa_authorities();
+			let (authority, _) = authorities
+				.get(attestation.authority_index as usize)
+				.ok_or(Error::<T>::InvalidAuthorityIndex)?;
+			ensure!(
+				authority.verify(&attestation.signing_payload(), signature),
+				Error::<T>::InvalidSignature
+			);
+
+			if let Some(previous) =
+				Attestations::<T>::get(attestation.set_id, attestation.authority_index)
+			{
+				ensure!(attestation.finalized_number > previous, Error::<T>::StaleAttestation);
+			}
+			Ok(())
+		}
+
+		/// Remove the attestations of `AttestedSetId` once GRANDPA runs another set, at most
+		/// `MaxAuthorities` per block, then track the current set.
+		fn clear_previous_attestations() -> Weight {
+			let set_id = pallet_grandpa::Pallet::<T>::current_set_id();
+			let attested_set_id = match AttestedSetId::<T>::get() {
+				Some(attested_set_id) if attested_set_id == set_id =>
+					return T::DbWeight::get().reads(2),
+				Some(attested_set_id) => attested_set_id,
+				None => {
+					AttestedSetId::<T>::put(set_id);
+					return T::DbWeight::get().reads_writes(2, 1)
+				},
+			};
+
+			let result =
+				Attestations::<T>::clear_prefix(attested_set_id, T::MaxAuthorities::get(), None);
+			if result.maybe_cursor.is_none() {
+				AttestedSetId::<T>::put(set_id);
+			}
+			T::DbWeight::get()
+				.reads_writes(2 + u64::from(result.loops), 1 + u64::from(result.unique))
+		}
+
+		/// Move `StallStatus` according to the lag between `now` and the highest attested
+		/// finalized block, emitting an event on every transition.
+		fn track_finality_lag(now: T::BlockNumber) {
+			let last_finalized = match ObservedFinalized::<T>::get() {
+				Some(last_finalized) => last_finalized,
+				// Nothing attested yet, there is no view of finality to judge.
+				None => return,
+			};
+			let lag = now.saturating_sub(last_finalized);
+
+			let next = if lag >= T::StallThreshold::get() {
+				StallState::Stalled { since: now, last_finalized }
+			} else if lag >= T::LagWarningThreshold::get() {
+				StallState::Lagging { since: now, last_finalized }
+			} else {
+				StallState::Healthy
+			};
+			let current = StallStatus::<T>::get();
+			if sp_std::mem::discriminant(&current) == sp_std::mem::discriminant(&next) {
+				return
+			}
+
+			match next {
+				StallState::Stalled { .. } => {
+					log::warn!(
+						target: "runtime::finality-rescue",
+						"Finality stalled at #{:?}: last finalized #{:?}, lag {:?}",
+						now,
+						last_finalized,
+						lag,
+					);
+					Self::deposit_event(Event::FinalityStalled { lag, last_finalized });
+				},
+				StallState::Lagging { .. } =>
+					Self::deposit_event(Event::FinalityLagWarning { lag, last_finalized }),
+				StallState::Healthy =>
+					Self::deposit_event(Event::FinalityRecovered { last_finalized }),
+			}
+			StallStatus::<T>::put(next);
+		}
+
+		/// Check that `new_set` can become the GRANDPA authority set: bounded, free of
+		/// duplicates and made of the GRANDPA session keys of current validators.
+		fn ensure_valid_authority_set(new_set: &AuthorityList) -> DispatchResult {
+			ensure!(!new_set.is_empty(), Error::<T>::NoAuthorities);
+			ensure!(
+				new_set.len() <= T::MaxAuthorities::get() as usize,
+				Error::<T>::TooManyAuthorities
+			);
+
+			let validators = pallet_session::Pallet::<T>::validators();
+			for (index, (key, weight)) in new_set.iter().enumerate() {
+				ensure!(*weight > 0, Error::<T>::ZeroAuthorityWeight);
+				ensure!(
+					!new_set[..index].iter().any(|(other, _)| other == key),
+					Error::<T>::DuplicateAuthority
+				);
+				let owner = pallet_session::KeyOwner::<T>::get((GRANDPA_KEY_TYPE, key.encode()))
+					.ok_or(Error::<T>::UnknownSessionKey)?;
+				ensure!(validators.contains(&owner), Error::<T>::NotAValidator);
+			}
+			Ok(())
+		}
+
+		/// Scheduler task name of the delayed rescue `index`.
+		fn rescue_task_name(index: u32) -> schedule::v3::TaskName {
+			(b"finality-rescue", index).blake2_256()
+		}
+
+		/// Storage key of the GRANDPA `SetIdSession` entry for `set_id`.
+		fn set_id_session_key(set_id: u64) -> Vec<u8> {
+			let mut key = storage::storage_prefix(b"Grandpa", b"SetIdSession").to_vec();
+			key.extend(Twox64Concat::hash(&set_id.encode()));
+			key
+		}
+
+		/// Force a change to the current authorities at `median`.
+		fn do_rescue(median: T::BlockNumber) -> DispatchResult {
+			let authorities = pallet_grandpa::Pallet::<T>::grandpa_authorities();
+			let authority_count = authorities.len() as u32;
+			let (block_number, old_set_id, new_set_id) = Self::force_change(median, authorities)?;
+
+			Self::deposit_event(Event::FinalityRescueExecuted {
+				block_number,
+				median,
+				authority_count,
+				old_set_id,
+				new_set_id,
+			});
+			Ok(())
+		}
+
+		/// Clear stale GRANDPA state, force a change to `authorities` at `median` and bump
+		/// `CurrentSetId`. Attestations of the old set are removed.
+		///
+		/// Returns the current block number and the old and new set ids.
+		fn force_change(
+			median: T::BlockNumber,
+			authorities: AuthorityList,
+		) -> Result<(T::BlockNumber, u64, u64), DispatchError> {
 			let block_number = <frame_system::Pallet<T>>::block_number();
 
+			if T::RequireStallForRescue::get() {
+				ensure!(
+					matches!(StallStatus::<T>::get(), StallState::Stalled { .. }),
+					Error::<T>::FinalityNotStalled
+				);
+			}
+
 			// Cooldown check
 			if let Some(last) = LastRescueBlock::<T>::get() {
 				ensure!(
@@ -99,8 +731,7 @@ pub mod pallet {
 			storage::unhashed::kill(&next_forced_key);
 			storage::unhashed::kill(&stalled_key);
 
-			// Step 2: Get current authorities
-			let authorities = pallet_grandpa::Pallet::<T>::grandpa_authorities();
+			// Step 2: Check the new authorities
 			ensure!(!authorities.is_empty(), Error::<T>::NoAuthorities);
 			let authority_count = authorities.len() as u32;
 
@@ -125,7 +756,8 @@ pub mod pallet {
 			let new_set_id: u64 = old_set_id + 1;
 			storage::unhashed::put(&current_set_id_key, &new_set_id);
 
-			// Step 5: Record and emit
+			// Step 5: Drop attestations of the old set and record the rescue
+			let _ = Attestations::<T>::clear_prefix(old_set_id, T::MaxAuthorities::get(), None);
 			LastRescueBlock::<T>::put(block_number);
 
 			log::info!(
@@ -139,16 +771,7 @@ pub mod pallet {
 				new_set_id,
 			);
 
-			Self::deposit_event(Event::FinalityRescueExecuted {
-				block_number,
-				median,
-				authority_count,
-				old_set_id,
-				new_set_id,
-			});
-
-			// Emergency operation - no fee
-			Ok(Pays::No.into())
+			Ok((block_number, old_set_id, new_set_id))
 		}
 	}
 }
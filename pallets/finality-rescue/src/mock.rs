@@ -1,11 +1,14 @@
 use frame_support::{
-	construct_runtime, parameter_types,
-	traits::{ConstU32, ConstU64},
+	construct_runtime, ord_parameter_types, parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, EqualPrivilegeOnly},
+	weights::Weight,
 };
+use frame_system::{EnsureRoot, EnsureSignedBy};
 use sp_core::H256;
 use sp_runtime::{
+	impl_opaque_keys,
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup, OpaqueKeys},
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -18,6 +21,10 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
+		Balances: pallet_balances,
+		Preimage: pallet_preimage,
+		Scheduler: pallet_scheduler,
+		Session: pallet_session,
 		Grandpa: pallet_grandpa,
 		FinalityRescue: crate,
 	}
@@ -26,6 +33,15 @@ construct_runtime!(
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const RescueCooldown: u64 = 10;
+	pub const LagWarningThreshold: u64 = 5;
+	pub const StallThreshold: u64 = 10;
+	pub static RequireStallForRescue: bool = false;
+	pub const RescueDelay: u64 = 5;
+	pub MaximumSchedulerWeight: Weight = Weight::from_parts(1_000_000_000_000, u64::MAX);
+}
+
+ord_parameter_types! {
+	pub const DelayAccount: u64 = 1;
 }
 
 impl frame_system::Config for Test {
@@ -46,7 +62,7 @@ impl frame_system::Config for Test {
 	type DbWeight = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u128>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -55,6 +71,58 @@ impl frame_system::Config for Test {
 	type MaxConsumers = ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+	type Balance = u128;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type MaxLocks = ();
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = [u8; 8];
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+impl pallet_preimage::Config for Test {
+	type BaseDeposit = ConstU128<0>;
+	type ByteDeposit = ConstU128<0>;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+}
+
+impl pallet_scheduler::Config for Test {
+	type MaxScheduledPerBlock = ConstU32<100>;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type PalletsOrigin = OriginCaller;
+	type Preimages = Preimage;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type WeightInfo = ();
+}
+
+impl_opaque_keys! {
+	pub struct MockSessionKeys {
+		pub grandpa: Grandpa,
+	}
+}
+
+impl pallet_session::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ValidatorId = u64;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = pallet_session::PeriodicSessions<ConstU64<1>, ConstU64<0>>;
+	type NextSessionRotation = pallet_session::PeriodicSessions<ConstU64<1>, ConstU64<0>>;
+	type SessionManager = ();
+	type SessionHandler = <MockSessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = MockSessionKeys;
+	type WeightInfo = ();
+}
+
 impl pallet_grandpa::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
@@ -66,16 +134,44 @@ impl pallet_grandpa::Config for Test {
 
 impl crate::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type RescueOrigin = EnsureRoot<u64>;
+	type DelayOrigin = EnsureSignedBy<DelayAccount, u64>;
+	type RescueDelay = RescueDelay;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type Preimages = Preimage;
 	type RescueCooldown = RescueCooldown;
+	type LagWarningThreshold = LagWarningThreshold;
+	type StallThreshold = StallThreshold;
+	type RequireStallForRescue = RequireStallForRescue;
+	type UnsignedPriority = ConstU64<{ u64::MAX }>;
+}
+
+/// Session key pair of the test authority at `index`.
+pub fn authority_pair(index: u32) -> sp_core::ed25519::Pair {
+	use sp_core::Pair;
+	sp_core::ed25519::Pair::from_seed(&[index as u8 + 1; 32])
 }
 
-pub fn test_authorities() -> Vec<(pallet_grandpa::AuthorityId, u64)> {
-	use sp_core::crypto::UncheckedFrom;
-	vec![
-		(pallet_grandpa::AuthorityId::unchecked_from([1u8; 32]), 1),
-		(pallet_grandpa::AuthorityId::unchecked_from([2u8; 32]), 1),
-		(pallet_grandpa::AuthorityId::unchecked_from([3u8; 32]), 1),
-	]
+/// Session keys of the test validator whose GRANDPA key is `authority_pair(index)`.
+pub fn session_keys(index: u32) -> MockSessionKeys {
+	use sp_core::Pair;
+	MockSessionKeys { grandpa: authority_pair(index).public().into() }
+}
+
+/// Build an attestation of `finalized_number` by the authority at `index`, signed with its key.
+pub fn signed_attestation(
+	index: u32,
+	set_id: u64,
+	finalized_number: u64,
+) -> (crate::FinalityAttestation<u64>, pallet_grandpa::fg_primitives::AuthoritySignature) {
+	use sp_core::Pair;
+
+	let attestation =
+		crate::FinalityAttestation { set_id, authority_index: index, finalized_number };
+	let signature = authority_pair(index).sign(&attestation.signing_payload()).into();
+	(attestation, signature)
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -83,8 +179,12 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
-	<pallet_grandpa::GenesisConfig as GenesisBuild<Test>>::assimilate_storage(
-		&pallet_grandpa::GenesisConfig { authorities: test_authorities() },
+	// Validators 1, 2 and 3 hold the GRANDPA keys of authorities 0, 1 and 2. The session
+	// genesis hands them to GRANDPA as the initial authority set.
+	<pallet_session::GenesisConfig<Test> as GenesisBuild<Test>>::assimilate_storage(
+		&pallet_session::GenesisConfig {
+			keys: (0..3).map(|i| (u64::from(i) + 1, u64::from(i) + 1, session_keys(i))).collect(),
+		},
 		&mut t,
 	)
 	.unwrap();
@@ -1,6 +1,12 @@
 use crate::mock::*;
 use frame_support::{assert_noop, assert_ok, storage, traits::Hooks};
 
+/// Submit a valid attestation of `finalized_number` by the authority at `index` in set 0.
+fn attest(index: u32, finalized_number: u64) {
+	let (attestation, signature) = signed_attestation(index, 0, finalized_number);
+	assert_ok!(FinalityRescue::attest_finality(RuntimeOrigin::none(), attestation, signature));
+}
+
 #[test]
 fn rescue_finality_works() {
 	new_test_ext().execute_with(|| {
@@ -115,3 +121,419 @@ fn rescue_finality_on_finalize_applies_change() {
 		assert_eq!(authorities.len(), 3);
 	});
 }
+
+#[test]
+fn attest_finality_validates_attestations() {
+	use frame_support::pallet_prelude::*;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+
+		let (attestation, signature) = signed_attestation(0, 0, 5);
+		let call = crate::Call::attest_finality {
+			attestation: attestation.clone(),
+			signature: signature.clone(),
+		};
+		let validity = FinalityRescue::validate_unsigned(TransactionSource::External, &call);
+		assert_eq!(validity.map(|valid| valid.priority), Ok(u64::MAX));
+		assert_ok!(FinalityRescue::attest_finality(RuntimeOrigin::none(), attestation, signature));
+		assert_eq!(FinalityRescue::attestations(0, 0), Some(5));
+		System::assert_last_event(
+			crate::Event::FinalityAttested { set_id: 0, authority_index: 0, finalized_number: 5 }
+				.into(),
+		);
+
+		// Replaying or going backwards is stale.
+		let (attestation, signature) = signed_attestation(0, 0, 5);
+		assert_eq!(
+			FinalityRescue::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::attest_finality { attestation, signature }
+			),
+			InvalidTransaction::Stale.into(),
+		);
+
+		// Wrong set id.
+		let (attestation, signature) = signed_attestation(1, 1, 5);
+		assert_noop!(
+			FinalityRescue::attest_finality(RuntimeOrigin::none(), attestation, signature),
+			crate::Error::<Test>::WrongSetId
+		);
+
+		// Blocks that do not exist yet cannot be finalized.
+		let (attestation, signature) = signed_attestation(1, 0, 10);
+		assert_noop!(
+			FinalityRescue::attest_finality(RuntimeOrigin::none(), attestation, signature),
+			crate::Error::<Test>::AttestationFromFuture
+		);
+
+		// Unknown authority index.
+		let (attestation, signature) = signed_attestation(3, 0, 5);
+		assert_noop!(
+			FinalityRescue::attest_finality(RuntimeOrigin::none(), attestation, signature),
+			crate::Error::<Test>::InvalidAuthorityIndex
+		);
+
+		// Signed by someone else's key.
+		let (mut attestation, signature) = signed_attestation(1, 0, 5);
+		attestation.authority_index = 2;
+		assert_eq!(
+			FinalityRescue::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::attest_finality {
+					attestation: attestation.clone(),
+					signature: signature.clone(),
+				}
+			),
+			InvalidTransaction::BadProof.into(),
+		);
+		assert_noop!(
+			FinalityRescue::attest_finality(RuntimeOrigin::none(), attestation, signature),
+			crate::Error::<Test>::InvalidSignature
+		);
+
+		// Signatures over the bare encoding lack the domain tag.
+		let (attestation, _) = signed_attestation(1, 0, 5);
+		let signature = {
+			use codec::Encode;
+			use sp_core::Pair;
+			authority_pair(1).sign(&attestation.encode()).into()
+		};
+		assert_noop!(
+			FinalityRescue::attest_finality(RuntimeOrigin::none(), attestation, signature),
+			crate::Error::<Test>::InvalidSignature
+		);
+
+		// Only unsigned submissions are accepted.
+		let (attestation, signature) = signed_attestation(1, 0, 5);
+		assert_noop!(
+			FinalityRescue::attest_finality(RuntimeOrigin::signed(1), attestation, signature),
+			frame_support::error::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn rescue_finality_attested_uses_median() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(20);
+
+		// One of three authorities is not enough.
+		attest(0, 12);
+		assert_noop!(
+			FinalityRescue::rescue_finality_attested(RuntimeOrigin::root()),
+			crate::Error::<Test>::InsufficientAttestations
+		);
+		assert_noop!(
+			FinalityRescue::rescue_finality_attested(RuntimeOrigin::signed(1)),
+			frame_support::error::BadOrigin
+		);
+
+		// Two of three is, and the lower median is used.
+		attest(2, 8);
+		assert_ok!(FinalityRescue::rescue_finality_attested(RuntimeOrigin::root()));
+		assert!(System::events().iter().any(|e| matches!(
+			e.event,
+			RuntimeEvent::FinalityRescue(crate::Event::AttestedMedianComputed {
+				set_id: 0,
+				attestation_count: 2,
+				median: 8,
+			})
+		)));
+		System::assert_last_event(
+			crate::Event::FinalityRescueExecuted {
+				block_number: 20,
+				median: 8,
+				authority_count: 3,
+				old_set_id: 0,
+				new_set_id: 1,
+			}
+			.into(),
+		);
+
+		// Attestations of the old set are gone.
+		assert_eq!(crate::Attestations::<Test>::iter_prefix(0).count(), 0);
+		assert_eq!(FinalityRescue::last_rescue_block(), Some(20));
+	});
+}
+
+#[test]
+fn attestations_of_previous_sets_are_cleared() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(10);
+		FinalityRescue::on_initialize(10);
+		assert_eq!(crate::AttestedSetId::<Test>::get(), Some(0));
+
+		attest(0, 5);
+		attest(1, 6);
+
+		// The set id moves without a rescue, e.g. at a session change.
+		assert_ok!(FinalityRescue::reconcile_set_id(RuntimeOrigin::root(), 1));
+		let (attestation, signature) = signed_attestation(2, 1, 7);
+		assert_ok!(FinalityRescue::attest_finality(RuntimeOrigin::none(), attestation, signature));
+		assert_eq!(crate::Attestations::<Test>::iter_prefix(0).count(), 2);
+
+		FinalityRescue::on_initialize(11);
+		assert_eq!(crate::Attestations::<Test>::iter_prefix(0).count(), 0);
+		assert_eq!(FinalityRescue::attestations(1, 2), Some(7));
+		assert_eq!(crate::AttestedSetId::<Test>::get(), Some(1));
+	});
+}
+
+#[test]
+fn stall_detection_tracks_finality_lag() {
+	use crate::StallState;
+
+	new_test_ext().execute_with(|| {
+		// Without attestations there is no view of finality.
+		System::set_block_number(100);
+		FinalityRescue::on_initialize(100);
+		assert_eq!(FinalityRescue::stall_state(), StallState::Healthy);
+
+		attest(0, 98);
+		attest(2, 96);
+		assert_eq!(FinalityRescue::observed_finalized(), Some(98));
+		System::reset_events();
+		FinalityRescue::on_initialize(101);
+		assert_eq!(FinalityRescue::stall_state(), StallState::Healthy);
+		assert!(System::events().is_empty());
+
+		FinalityRescue::on_initialize(103);
+		assert_eq!(
+			FinalityRescue::stall_state(),
+			StallState::Lagging { since: 103, last_finalized: 98 }
+		);
+		System::assert_last_event(
+			crate::Event::FinalityLagWarning { lag: 5, last_finalized: 98 }.into(),
+		);
+
+		// Staying in the same state neither moves `since` nor emits again.
+		System::reset_events();
+		FinalityRescue::on_initialize(105);
+		assert_eq!(
+			FinalityRescue::stall_state(),
+			StallState::Lagging { since: 103, last_finalized: 98 }
+		);
+		assert!(System::events().is_empty());
+
+		FinalityRescue::on_initialize(108);
+		assert_eq!(
+			FinalityRescue::stall_state(),
+			StallState::Stalled { since: 108, last_finalized: 98 }
+		);
+		System::assert_last_event(
+			crate::Event::FinalityStalled { lag: 10, last_finalized: 98 }.into(),
+		);
+
+		System::set_block_number(109);
+		attest(1, 107);
+		FinalityRescue::on_initialize(109);
+		assert_eq!(FinalityRescue::stall_state(), StallState::Healthy);
+		System::assert_last_event(crate::Event::FinalityRecovered { last_finalized: 107 }.into());
+	});
+}
+
+#[test]
+fn rescue_finality_can_require_stall() {
+	new_test_ext().execute_with(|| {
+		RequireStallForRescue::set(true);
+		System::set_block_number(20);
+
+		assert_noop!(
+			FinalityRescue::rescue_finality(RuntimeOrigin::root(), 0),
+			crate::Error::<Test>::FinalityNotStalled
+		);
+
+		attest(0, 5);
+		FinalityRescue::on_initialize(20);
+		assert_ok!(FinalityRescue::rescue_finality(RuntimeOrigin::root(), 5));
+
+		RequireStallForRescue::set(false);
+	});
+}
+
+#[test]
+fn force_authorities_works() {
+	use sp_core::Pair;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let new_set: pallet_grandpa::AuthorityList =
+			vec![(authority_pair(0).public().into(), 1), (authority_pair(2).public().into(), 2)];
+		assert_ok!(FinalityRescue::force_authorities(RuntimeOrigin::root(), new_set.clone(), 0));
+		System::assert_last_event(
+			crate::Event::AuthoritySetForced {
+				block_number: 1,
+				median: 0,
+				authorities: new_set.clone(),
+				old_set_id: 0,
+				new_set_id: 1,
+			}
+			.into(),
+		);
+		assert_eq!(pallet_grandpa::Pallet::<Test>::current_set_id(), 1);
+		assert_eq!(FinalityRescue::last_rescue_block(), Some(1));
+
+		// The forced change applies the new set in the same block.
+		Grandpa::on_finalize(1);
+		assert_eq!(pallet_grandpa::Pallet::<Test>::grandpa_authorities(), new_set);
+
+		// Forcing the set counts as a rescue for the cooldown.
+		System::set_block_number(5);
+		assert_noop!(
+			FinalityRescue::force_authorities(RuntimeOrigin::root(), new_set, 0),
+			crate::Error::<Test>::CooldownNotElapsed
+		);
+	});
+}
+
+#[test]
+fn force_authorities_validates_new_set() {
+	use sp_core::Pair;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = |index| -> pallet_grandpa::AuthorityId { authority_pair(index).public().into() };
+
+		assert_noop!(
+			FinalityRescue::force_authorities(RuntimeOrigin::signed(1), vec![(key(0), 1)], 0),
+			frame_support::error::BadOrigin
+		);
+		assert_noop!(
+			FinalityRescue::force_authorities(RuntimeOrigin::root(), vec![], 0),
+			crate::Error::<Test>::NoAuthorities
+		);
+		assert_noop!(
+			FinalityRescue::force_authorities(RuntimeOrigin::root(), vec![(key(0), 1); 101], 0),
+			crate::Error::<Test>::TooManyAuthorities
+		);
+		assert_noop!(
+			FinalityRescue::force_authorities(
+				RuntimeOrigin::root(),
+				vec![(key(0), 1), (key(1), 1), (key(0), 1)],
+				0
+			),
+			crate::Error::<Test>::DuplicateAuthority
+		);
+		assert_noop!(
+			FinalityRescue::force_authorities(RuntimeOrigin::root(), vec![(key(1), 0)], 0),
+			crate::Error::<Test>::ZeroAuthorityWeight
+		);
+		assert_noop!(
+			FinalityRescue::force_authorities(RuntimeOrigin::root(), vec![(key(4), 1)], 0),
+			crate::Error::<Test>::UnknownSessionKey
+		);
+
+		// Account 4 registers a GRANDPA key but is not in the validator set.
+		System::inc_providers(&4);
+		assert_ok!(Session::set_keys(RuntimeOrigin::signed(4), session_keys(3), vec![]));
+		assert_noop!(
+			FinalityRescue::force_authorities(
+				RuntimeOrigin::root(),
+				vec![(key(0), 1), (key(3), 1)],
+				0
+			),
+			crate::Error::<Test>::NotAValidator
+		);
+	});
+}
+
+#[test]
+fn reconcile_set_id_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			FinalityRescue::reconcile_set_id(RuntimeOrigin::signed(1), 5),
+			frame_support::error::BadOrigin
+		);
+		assert_noop!(
+			FinalityRescue::reconcile_set_id(RuntimeOrigin::root(), 0),
+			crate::Error::<Test>::SetIdAlreadyAligned
+		);
+
+		// Move forward to the client's set id.
+		assert_ok!(FinalityRescue::reconcile_set_id(RuntimeOrigin::root(), 5));
+		System::assert_last_event(
+			crate::Event::SetIdReconciled { old_set_id: 0, new_set_id: 5, session_index: 0 }.into(),
+		);
+		assert_eq!(pallet_grandpa::Pallet::<Test>::current_set_id(), 5);
+		assert_eq!(pallet_grandpa::Pallet::<Test>::session_for_set(5), Some(0));
+
+		// Moving back drops the session entries of the skipped set ids.
+		assert_ok!(FinalityRescue::reconcile_set_id(RuntimeOrigin::root(), 3));
+		assert_eq!(pallet_grandpa::Pallet::<Test>::current_set_id(), 3);
+		assert_eq!(pallet_grandpa::Pallet::<Test>::session_for_set(3), Some(0));
+		assert_eq!(pallet_grandpa::Pallet::<Test>::session_for_set(5), None);
+
+		// Large rollbacks are refused.
+		assert_ok!(FinalityRescue::reconcile_set_id(RuntimeOrigin::root(), 30));
+		assert_noop!(
+			FinalityRescue::reconcile_set_id(RuntimeOrigin::root(), 3),
+			crate::Error::<Test>::SetIdRollbackTooLarge
+		);
+	});
+}
+
+#[test]
+fn schedule_rescue_runs_after_delay() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let call = Box::new(crate::Call::rescue_finality { median: 0 });
+
+		assert_noop!(
+			FinalityRescue::schedule_rescue(RuntimeOrigin::signed(2), call.clone()),
+			frame_support::error::BadOrigin
+		);
+		assert_noop!(
+			FinalityRescue::schedule_rescue(
+				RuntimeOrigin::signed(1),
+				Box::new(crate::Call::cancel_rescue { index: 0 })
+			),
+			crate::Error::<Test>::NotARescueCall
+		);
+
+		assert_ok!(FinalityRescue::schedule_rescue(RuntimeOrigin::signed(1), call));
+		System::assert_last_event(crate::Event::RescueScheduled { index: 0, when: 6 }.into());
+		assert_eq!(FinalityRescue::next_rescue_index(), 1);
+
+		// Nothing happens before the delay is over.
+		System::set_block_number(5);
+		Scheduler::on_initialize(5);
+		assert_eq!(FinalityRescue::last_rescue_block(), None);
+
+		// The scheduler dispatches the rescue as root.
+		System::set_block_number(6);
+		Scheduler::on_initialize(6);
+		assert_eq!(FinalityRescue::last_rescue_block(), Some(6));
+		assert_eq!(pallet_grandpa::Pallet::<Test>::current_set_id(), 1);
+	});
+}
+
+#[test]
+fn cancel_rescue_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(FinalityRescue::schedule_rescue(
+			RuntimeOrigin::signed(1),
+			Box::new(crate::Call::reconcile_set_id { expected_client_set_id: 5 })
+		));
+
+		// Only the rescue origin can cancel, not the one that scheduled.
+		assert_noop!(
+			FinalityRescue::cancel_rescue(RuntimeOrigin::signed(1), 0),
+			frame_support::error::BadOrigin
+		);
+		assert_ok!(FinalityRescue::cancel_rescue(RuntimeOrigin::root(), 0));
+		System::assert_last_event(crate::Event::RescueCancelled { index: 0 }.into());
+		assert_noop!(
+			FinalityRescue::cancel_rescue(RuntimeOrigin::root(), 0),
+			crate::Error::<Test>::UnknownRescue
+		);
+
+		System::set_block_number(6);
+		Scheduler::on_initialize(6);
+		assert_eq!(pallet_grandpa::Pallet::<Test>::current_set_id(), 0);
+	});
+}
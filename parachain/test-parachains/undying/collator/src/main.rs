@@ -68,6 +68,9 @@ fn main() -> Result<()> {
 					None,
 					None,
 					None,
+					Default::default(),
+					Default::default(),
+					false,
 				)
 				.map_err(|e| e.to_string())?;
 				let mut overseer_handle = full_node
@@ -0,0 +1,175 @@
+// Copyright 2017-2022 Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for the DAO pallet, backed by the `DaoApi` runtime API of the thxnet runtimes.
+//!
+//! It is not part of [`create_full`](crate::create_full), nodes only serve it on the thxnet chains.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_dao::{TopicInfo, TopicResults, VoterStatus};
+use pallet_dao_runtime_api::DaoApi as DaoRuntimeApi;
+use polkadot_primitives::{AccountId, Block, Hash};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+
+/// Topic identifier of the thxnet runtimes.
+pub type TopicId = u64;
+/// Option index of the thxnet runtimes.
+pub type OptionIndex = u64;
+/// Vote weight of the thxnet runtimes.
+pub type VoteWeight = u128;
+
+/// The maximum number of topics returned by `dao_topics`.
+pub const MAX_TOPICS_PER_PAGE: u32 = 100;
+
+/// DAO RPC methods.
+#[rpc(server)]
+pub trait DaoApi<BlockHash> {
+	/// Returns at most `limit` topics, starting after the topic `start_after`.
+	///
+	/// `limit` is capped at [`MAX_TOPICS_PER_PAGE`]. Pass the id of the last topic returned as
+	/// `start_after` to get the next page.
+	#[method(name = "dao_topics")]
+	fn topics(
+		&self,
+		start_after: Option<TopicId>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<TopicInfo<TopicId, AccountId>>>;
+
+	/// Returns the topic `topic_id`, if any.
+	#[method(name = "dao_topic")]
+	fn topic(
+		&self,
+		topic_id: TopicId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<TopicInfo<TopicId, AccountId>>>;
+
+	/// Returns the ballots cast on the topic `topic_id` and its outcome, if any.
+	#[method(name = "dao_topicResults")]
+	fn topic_results(
+		&self,
+		topic_id: TopicId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<TopicResults<OptionIndex, VoteWeight>>>;
+
+	/// Returns where `who` stands on the topic `topic_id`.
+	#[method(name = "dao_voterStatus")]
+	fn voter_status(
+		&self,
+		topic_id: TopicId,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<VoterStatus<VoteWeight>>;
+
+	/// Returns whether `who` can vote on the topic `topic_id` now.
+	#[method(name = "dao_canVote")]
+	fn can_vote(&self, topic_id: TopicId, who: AccountId, at: Option<BlockHash>)
+		-> RpcResult<bool>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to the runtime failed, e.g. because the runtime has no DAO pallet.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+/// Provides the DAO RPC methods.
+pub struct Dao<C> {
+	client: Arc<C>,
+}
+
+impl<C> Dao<C> {
+	/// Creates a new instance of the DAO RPC.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Display) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query the DAO.",
+		Some(e.to_string()),
+	))
+	.into()
+}
+
+impl<C> DaoApiServer<Hash> for Dao<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: DaoRuntimeApi<Block, AccountId, TopicId, OptionIndex, VoteWeight>,
+{
+	fn topics(
+		&self,
+		start_after: Option<TopicId>,
+		limit: u32,
+		at: Option<Hash>,
+	) -> RpcResult<Vec<TopicInfo<TopicId, AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.topics(at, start_after, limit.min(MAX_TOPICS_PER_PAGE))
+			.map_err(runtime_error)
+	}
+
+	fn topic(
+		&self,
+		topic_id: TopicId,
+		at: Option<Hash>,
+	) -> RpcResult<Option<TopicInfo<TopicId, AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().topic(at, topic_id).map_err(runtime_error)
+	}
+
+	fn topic_results(
+		&self,
+		topic_id: TopicId,
+		at: Option<Hash>,
+	) -> RpcResult<Option<TopicResults<OptionIndex, VoteWeight>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().topic_results(at, topic_id).map_err(runtime_error)
+	}
+
+	fn voter_status(
+		&self,
+		topic_id: TopicId,
+		who: AccountId,
+		at: Option<Hash>,
+	) -> RpcResult<VoterStatus<VoteWeight>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().voter_status(at, topic_id, who).map_err(runtime_error)
+	}
+
+	fn can_vote(&self, topic_id: TopicId, who: AccountId, at: Option<Hash>) -> RpcResult<bool> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().can_vote(at, topic_id, who).map_err(runtime_error)
+	}
+}
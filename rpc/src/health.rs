@@ -0,0 +1,148 @@
+// Copyright 2026 THX Network Contributors.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! RPC interface reporting whether a validator takes part in consensus.
+//!
+//! The report tells which keys the node holds, so the method is unsafe and only served on
+//! local or explicitly unsafe RPC interfaces. Readiness probes of orchestrators should use the
+//! `/health/validator` endpoint that validators serve on their Prometheus port instead.
+
+use std::{
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+};
+
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_rpc::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+
+/// How long a report is reused, so that frequent probes do not query authority discovery for
+/// every validator each time.
+const REPORT_CACHE_DURATION: Duration = Duration::from_secs(5);
+
+/// The outcome of a single readiness condition.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthCheck {
+	/// Name of the condition, e.g. `peers`.
+	pub name: String,
+	/// Whether the condition holds.
+	pub ok: bool,
+	/// What was observed, e.g. `12 peers, at least 3 required`.
+	pub detail: String,
+}
+
+/// The readiness of a validator.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorHealthReport {
+	/// The conditions that were checked.
+	pub checks: Vec<HealthCheck>,
+}
+
+impl ValidatorHealthReport {
+	/// Adds the outcome of a condition to the report.
+	pub fn push(&mut self, name: &str, ok: bool, detail: String) {
+		self.checks.push(HealthCheck { name: name.into(), ok, detail });
+	}
+
+	/// Whether all conditions hold.
+	pub fn is_ready(&self) -> bool {
+		self.checks.iter().all(|check| check.ok)
+	}
+}
+
+/// Evaluates the readiness conditions of the node.
+#[async_trait]
+pub trait ValidatorHealthProvider: Send + Sync + 'static {
+	/// Checks all readiness conditions.
+	async fn report(&self) -> ValidatorHealthReport;
+}
+
+/// Reuses the reports of the wrapped provider for a few seconds, so that the RPC and the HTTP
+/// endpoint share them.
+pub struct CachedValidatorHealth<P> {
+	provider: P,
+	last_report: Mutex<Option<(Instant, ValidatorHealthReport)>>,
+}
+
+impl<P> CachedValidatorHealth<P> {
+	/// Wraps `provider`.
+	pub fn new(provider: P) -> Self {
+		Self { provider, last_report: Mutex::new(None) }
+	}
+}
+
+#[async_trait]
+impl<P: ValidatorHealthProvider> ValidatorHealthProvider for CachedValidatorHealth<P> {
+	async fn report(&self) -> ValidatorHealthReport {
+		let cached = self
+			.last_report
+			.lock()
+			.expect("poisoned only if a previous report panicked; qed")
+			.as_ref()
+			.filter(|(created, _)| created.elapsed() < REPORT_CACHE_DURATION)
+			.map(|(_, report)| report.clone());
+		if let Some(report) = cached {
+			return report
+		}
+
+		let report = self.provider.report().await;
+		*self
+			.last_report
+			.lock()
+			.expect("poisoned only if a previous report panicked; qed") =
+			Some((Instant::now(), report.clone()));
+		report
+	}
+}
+
+/// Validator health RPC methods.
+#[rpc(server)]
+pub trait ValidatorHealthApi {
+	/// Returns the readiness conditions of the validator if all of them hold.
+	///
+	/// Fails with the error code [`NOT_READY`] and the report as error data otherwise, so that
+	/// probes only need to look for a `result`. The report may be a few seconds old.
+	#[method(name = "system_validatorHealth")]
+	async fn validator_health(&self) -> RpcResult<ValidatorHealthReport>;
+}
+
+/// Error code returned while not all readiness conditions hold.
+pub const NOT_READY: i32 = 1;
+
+/// Provides the validator health RPC methods.
+pub struct ValidatorHealth<P> {
+	provider: Arc<P>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<P: ValidatorHealthProvider> ValidatorHealth<P> {
+	/// Creates a new instance of the validator health RPC.
+	pub fn new(provider: Arc<P>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { provider, deny_unsafe }
+	}
+}
+
+#[async_trait]
+impl<P: ValidatorHealthProvider> ValidatorHealthApiServer for ValidatorHealth<P> {
+	async fn validator_health(&self) -> RpcResult<ValidatorHealthReport> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let report = self.provider.report().await;
+		if report.is_ready() {
+			return Ok(report)
+		}
+
+		Err(CallError::Custom(ErrorObject::owned(
+			NOT_READY,
+			"The validator is not ready.",
+			Some(report),
+		))
+		.into())
+	}
+}
@@ -37,6 +37,9 @@ use sp_consensus_babe::BabeApi;
 use sp_keystore::SyncCryptoStorePtr;
 use txpool_api::TransactionPool;
 
+pub mod dao;
+pub mod health;
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = RpcModule<()>;
 
@@ -23,6 +23,7 @@ pub mod pallet_bounties;
 pub mod pallet_child_bounties;
 pub mod pallet_collective_council;
 pub mod pallet_collective_technical_committee;
+pub mod pallet_dao;
 pub mod pallet_democracy;
 pub mod pallet_election_provider_multi_phase;
 pub mod pallet_elections_phragmen;
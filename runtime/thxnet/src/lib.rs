@@ -869,6 +869,7 @@ parameter_types! {
 	pub const MaxPeerDataEncodingSize: u32 = 1_000;
 	pub const RootSpendOriginMaxAmount: Balance = Balance::MAX;
 	pub const CouncilSpendOriginMaxAmount: Balance = Balance::MAX;
+	pub const DaoSpendOriginMaxAmount: Balance = 10_000 * DOLLARS;
 }
 
 type ApproveOrigin = EitherOfDiverse<
@@ -893,12 +894,16 @@ impl pallet_treasury::Config for Runtime {
 	type MaxApprovals = MaxApprovals;
 	type WeightInfo = weights::pallet_treasury::WeightInfo<Runtime>;
 	type SpendOrigin = EitherOf<
-		frame_system::EnsureRootWithSuccess<AccountId, RootSpendOriginMaxAmount>,
-		EnsureWithSuccess<
-			pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 5>,
-			AccountId,
-			CouncilSpendOriginMaxAmount,
+		EitherOf<
+			frame_system::EnsureRootWithSuccess<AccountId, RootSpendOriginMaxAmount>,
+			EnsureWithSuccess<
+				pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 5>,
+				AccountId,
+				CouncilSpendOriginMaxAmount,
+			>,
 		>,
+		// Calls enacted by a DAO topic raised through governance.
+		EnsureWithSuccess<pallet_dao::EnsureForcedTopic<u64>, AccountId, DaoSpendOriginMaxAmount>,
 	>;
 }
 
@@ -1126,6 +1131,17 @@ parameter_types! {
 	pub const MetadataDepositBase: Balance = 10 * DOLLARS;
 	pub const MetadataDepositPerByte: Balance = 1 * DOLLARS;
 	pub const DaoTopicRaiserBalanceLowerBound: Balance = 1_000_000 * UNITS;
+	pub const DaoTopicDepositBase: Balance = deposit(1, 88);
+	pub const DaoTopicDepositPerByte: Balance = deposit(0, 1);
+	pub const DaoVoteLockId: LockIdentifier = *b"dao/vote";
+	pub const DaoVoteLockingPeriod: u64 = 7 * 24 * 60 * 60;
+	pub const DaoPeriodMode: pallet_dao::PeriodMode = pallet_dao::PeriodMode::Timestamp;
+	pub const DaoMinVotingDelay: u64 = 60 * 60;
+	pub const DaoMaxVotingDelay: u64 = 3 * 730 * 60 * 60;
+	pub const DaoMinVotingDuration: u64 = 3 * 60 * 60;
+	pub const DaoMaxVotingDuration: u64 = 3 * 730 * 60 * 60;
+	pub const DaoRevealPeriod: u64 = 24 * 60 * 60;
+	pub const DaoTopicRetentionPeriod: u64 = 365 * 24 * 60 * 60;
 }
 
 impl pallet_assets::Config for Runtime {
@@ -1135,7 +1151,9 @@ impl pallet_assets::Config for Runtime {
 	type AssetIdParameter = parity_scale_codec::Compact<u32>;
 	type Currency = Balances;
 	type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
-	type ForceOrigin = EnsureRoot<AccountId>;
+	/// Root, or a DAO topic raised through governance, e.g. to create an asset or hand its issuer
+	/// role to a minter.
+	type ForceOrigin = EitherOfDiverse<EnsureRoot<AccountId>, pallet_dao::EnsureForcedTopic<u64>>;
 	type AssetDeposit = AssetDeposit;
 	type AssetAccountDeposit = ConstU128<DOLLARS>;
 	type MetadataDepositBase = MetadataDepositBase;
@@ -1165,33 +1183,94 @@ parameter_types! {
 }
 
 impl pallet_dao::Config for Runtime {
+	type AssetId = u32;
+	type Assets = Assets;
+	type CollectionId = u32;
 	type Currency = Balances;
 	type CurrencyUnits = ConstU128<{ UNITS }>;
 	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxActiveTopics = ConstU32<256>;
+	type MaxActiveTopicsPerRaiser = ConstU32<16>;
+	type MaxAutoClosePerBlock = ConstU32<4>;
+	type MaxDelegators = ConstU32<64>;
+	type MaxIssuersPerTopic = ConstU32<16>;
+	type MaxItemsPerVoter = ConstU32<16>;
+	type MaxRankedChoiceOptions = ConstU32<32>;
+	type MaxRankedChoiceVoters = ConstU32<1024>;
+	type MaxVoteLocks = ConstU32<64>;
+	type MaxVotersPerIssuance = ConstU32<1024>;
+	type MaxVotingDelay = DaoMaxVotingDelay;
+	type MaxVotingDuration = DaoMaxVotingDuration;
+	type MinEnactmentTurnout = ConstU32<100>;
+	type MinVotingDelay = DaoMinVotingDelay;
+	type MinVotingDuration = DaoMinVotingDuration;
+	type Nfts = Nfts;
 	type OptionIndex = u64;
+	type PalletsOrigin = OriginCaller;
+	type PeriodMode = DaoPeriodMode;
+	type Preimages = Preimage;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type ReservedActiveTopics = ConstU32<32>;
+	type RevealPeriod = DaoRevealPeriod;
 	type RuntimeEvent = RuntimeEvent;
+	type Scheduler = Scheduler;
+	type Slashed = Treasury;
 	type StringLimit = ConstU32<{ 2048 * 4 }>;
+	type TopicDepositBase = DaoTopicDepositBase;
+	type TopicDepositPerByte = DaoTopicDepositPerByte;
 	type TopicDescriptionMaximumLength = ConstU32<2048>;
 	type TopicDescriptionMinimumLength = ConstU32<1>;
 	type TopicId = u64;
 	type TopicOptionMaximumLength = ConstU32<256>;
 	type TopicOptionMaximumNumber = ConstU32<1024>;
 	type TopicOptionMinimumLength = ConstU32<1>;
-	type TopicRaiserBalanceLowerBound = ConstU128<1_000_000>;
+	type TopicRaiserBalanceLowerBound = DaoTopicRaiserBalanceLowerBound;
+	type TopicRetentionPeriod = DaoTopicRetentionPeriod;
 	type TopicTitleMaximumLength = ConstU32<256>;
 	type TopicTitleMinimumLength = ConstU32<1>;
 	type UnixTime = pallet_timestamp::Pallet<Runtime>;
 	type Vote = u128;
+	type VoteLockId = DaoVoteLockId;
+	type VoteLockingPeriod = DaoVoteLockingPeriod;
+	type WeightInfo = weights::pallet_dao::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	/// Minimum blocks between rescue_finality calls (~10 min at 6s/block).
 	pub const RescueCooldown: BlockNumber = 100;
+	/// Finality lag that raises `FinalityLagWarning` (~2 min at 6s/block).
+	pub const FinalityLagWarningThreshold: BlockNumber = 20;
+	/// Finality lag that marks finality as stalled (~10 min at 6s/block).
+	pub const FinalityStallThreshold: BlockNumber = 100;
+	/// Stall detection relies on validators attesting; keep rescue available without it.
+	pub const RequireStallForRescue: bool = false;
+	/// Delay before a rescue scheduled by the technical committee runs (~5 min at 6s/block).
+	pub const FinalityRescueDelay: BlockNumber = 50;
+	/// Attestations only matter during an emergency, let them through first.
+	pub const FinalityAttestationPriority: TransactionPriority = TransactionPriority::max_value();
 }
 
 impl pallet_finality_rescue::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	/// Root, or two thirds of the technical committee.
+	type RescueOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 2, 3>,
+	>;
+	/// Half of the technical committee can schedule a rescue, which `RescueOrigin` can cancel
+	/// during `FinalityRescueDelay`.
+	type DelayOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 2>;
+	type RescueDelay = FinalityRescueDelay;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type Preimages = Preimage;
 	type RescueCooldown = RescueCooldown;
+	type LagWarningThreshold = FinalityLagWarningThreshold;
+	type StallThreshold = FinalityStallThreshold;
+	type RequireStallForRescue = RequireStallForRescue;
+	type UnsignedPriority = FinalityAttestationPriority;
 }
 
 impl pallet_nfts::Config for Runtime {
@@ -1629,8 +1708,8 @@ construct_runtime! {
 		AssetTxPayment: pallet_asset_tx_payment::{Pallet, Storage, Event<T>} = 131,
 		Assets: pallet_assets::{Pallet, Call, Storage, Event<T>} = 132,
 		Nfts: pallet_nfts::{Pallet, Call, Storage, Event<T>} = 133,
-		Dao: pallet_dao::{Pallet, Call, Storage, Event<T>}  = 134,
-		FinalityRescue: pallet_finality_rescue::{Pallet, Call, Storage, Event<T>} = 135,
+		Dao: pallet_dao::{Pallet, Call, Storage, Event<T>, Origin<T>}  = 134,
+		FinalityRescue: pallet_finality_rescue::{Pallet, Call, Storage, Event<T>, ValidateUnsigned} = 135,
 
 		// Consensus support.
 		// Authorship must be before session in order to note author in the correct session and era
@@ -1878,6 +1957,8 @@ pub type Migrations = (
 		Runtime,
 		NominationPoolsMigrationV4OldPallet,
 	>,
+	pallet_dao::migration::v1::MigrateToV1<Runtime>,
+	pallet_dao::migration::v2::MigrateToV2<Runtime>,
 	FixGrandpaFinalityDeadlock,
 );
 
@@ -1922,6 +2003,7 @@ mod benches {
 		// Substrate
 		[pallet_assets, Assets]
 		[pallet_nfts, Nfts]
+		[pallet_dao, Dao]
 		[pallet_bags_list, VoterList]
 		[pallet_balances, Balances]
 		[frame_benchmarking::baseline, Baseline::<Runtime>]
@@ -2330,6 +2412,35 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_dao_runtime_api::DaoApi<
+		Block,
+		AccountId,
+		u64,
+		u64,
+		u128,
+	> for Runtime
+	{
+		fn topics(start_after: Option<u64>, limit: u32) -> Vec<pallet_dao::TopicInfo<u64, AccountId>> {
+			Dao::topics(start_after, limit)
+		}
+
+		fn topic(topic_id: u64) -> Option<pallet_dao::TopicInfo<u64, AccountId>> {
+			Dao::topic_info(topic_id)
+		}
+
+		fn topic_results(topic_id: u64) -> Option<pallet_dao::TopicResults<u64, u128>> {
+			Dao::topic_results(topic_id)
+		}
+
+		fn voter_status(topic_id: u64, who: AccountId) -> pallet_dao::VoterStatus<u128> {
+			Dao::voter_status(topic_id, &who)
+		}
+
+		fn can_vote(topic_id: u64, who: AccountId) -> bool {
+			Dao::can_vote(topic_id, &who)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
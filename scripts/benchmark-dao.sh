@@ -0,0 +1,27 @@
+#!/usr/bin/env bash
+set -e
+
+# Benchmarks pallet_dao for both thxnet runtimes and writes the weights to
+# runtime/<runtime>/src/weights/pallet_dao.rs. Should be run on a reference machine,
+# with a node built by `cargo build --profile production --features runtime-benchmarks`.
+
+polkadot="${POLKADOT:-./target/production/polkadot}"
+
+declare -A chains=(
+  [thxnet]=thxnet-mainnet
+  [thxnet-testnet]=thxnet-testnet
+)
+
+for runtime in "${!chains[@]}"; do
+  echo "[+] Benchmarking pallet_dao for $runtime"
+  "$polkadot" benchmark pallet \
+    --chain="${chains[$runtime]}" \
+    --steps=50 \
+    --repeat=20 \
+    --pallet=pallet_dao \
+    --extrinsic='*' \
+    --execution=wasm \
+    --wasm-execution=compiled \
+    --header=./file_header.txt \
+    --output="./runtime/$runtime/src/weights/pallet_dao.rs"
+done